@@ -105,12 +105,23 @@ export default defineConfig({
           { text: "add", link: "/reference/commands/add" },
           { text: "merge", link: "/reference/commands/merge" },
           { text: "remove", link: "/reference/commands/remove" },
+          { text: "gc", link: "/reference/commands/gc" },
+          { text: "du", link: "/reference/commands/du" },
+          { text: "files", link: "/reference/commands/files" },
           { text: "list", link: "/reference/commands/list" },
           { text: "open", link: "/reference/commands/open" },
           { text: "close", link: "/reference/commands/close" },
           { text: "path", link: "/reference/commands/path" },
+          { text: "pr create", link: "/reference/commands/pr" },
+          { text: "prompts", link: "/reference/commands/prompts" },
+          { text: "ci-feedback", link: "/reference/commands/ci-feedback" },
+          {
+            text: "review-feedback",
+            link: "/reference/commands/review-feedback",
+          },
           { text: "dashboard", link: "/reference/commands/dashboard" },
           { text: "init", link: "/reference/commands/init" },
+          { text: "config", link: "/reference/commands/config" },
           { text: "claude prune", link: "/reference/commands/claude" },
           { text: "completions", link: "/reference/commands/completions" },
           { text: "docs", link: "/reference/commands/docs" },
@@ -1,4 +1,5 @@
-use crate::command::args::{MultiArgs, PromptArgs, RescueArgs, SetupFlags};
+use crate::command::args::{MultiArgs, PromptArgs, PromptTemplateArgs, RescueArgs, SetupFlags};
+use crate::workflow::files_sync::SyncDirection;
 use crate::{claude, command, config, git, nerdfont};
 use anyhow::{Context, Result};
 use clap::{CommandFactory, Parser, Subcommand};
@@ -173,19 +174,25 @@ struct Cli {
     command: Commands,
 }
 
+// Parsed once per run, so the size of `Add` doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// Create a new worktree and tmux window
     Add {
         /// Name of the branch (creates if it doesn't exist) or remote ref (e.g., origin/feature).
-        /// When used with --pr, this becomes the custom local branch name.
-        #[arg(required_unless_present_any = ["pr", "auto_name"], value_parser = GitBranchParser::new())]
+        /// When used with --pr or a single --issue, this becomes the custom local branch name.
+        #[arg(required_unless_present_any = ["pr", "auto_name", "issue", "prompt_file", "template"], value_parser = GitBranchParser::new())]
         branch_name: Option<String>,
 
         /// Pull request number to checkout
         #[arg(long, conflicts_with_all = ["base", "auto_name"])]
         pr: Option<u32>,
 
+        /// Issue number to work on; its title and body become the prompt. Repeat to create one worktree per issue
+        #[arg(long, conflicts_with_all = ["pr", "with_changes", "count", "foreach"])]
+        issue: Vec<u32>,
+
         /// Generate branch name from prompt using LLM
         #[arg(short = 'A', long = "auto-name", conflicts_with = "pr")]
         auto_name: bool,
@@ -201,6 +208,9 @@ enum Commands {
         #[command(flatten)]
         prompt: PromptArgs,
 
+        #[command(flatten)]
+        template: PromptTemplateArgs,
+
         #[command(flatten)]
         setup: SetupFlags,
 
@@ -210,6 +220,10 @@ enum Commands {
         #[command(flatten)]
         multi: MultiArgs,
 
+        /// Apply a named profile from the `profiles` config section
+        #[arg(long)]
+        profile: Option<String>,
+
         /// Block until the created tmux window is closed
         #[arg(short = 'W', long)]
         wait: bool,
@@ -266,6 +280,10 @@ enum Commands {
         #[arg(long, group = "merge_strategy")]
         squash: bool,
 
+        /// Generate the squash commit message with the configured LLM (opens your editor to review it)
+        #[arg(short = 'g', long)]
+        generate_message: bool,
+
         /// Keep the worktree, window, and branch after merging (skip cleanup)
         #[arg(short = 'k', long)]
         keep: bool,
@@ -277,6 +295,22 @@ enum Commands {
         /// Show a system notification on successful merge
         #[arg(long)]
         notification: bool,
+
+        /// Merge the branch's PR through the forge (gh, glab or Gitea API) instead of merging locally; --rebase/--squash pick the merge method
+        #[arg(long, conflicts_with_all = ["into", "generate_message"])]
+        via_pr: bool,
+
+        /// With --via-pr, wait for the PR's checks to pass before merging
+        #[arg(long, conflicts_with = "auto")]
+        wait: bool,
+
+        /// With --via-pr, enable auto-merge so the forge merges the PR once its checks pass
+        #[arg(long)]
+        auto: bool,
+
+        /// With --via-pr, stop waiting for checks or the merge after this many seconds
+        #[arg(long, value_name = "SECONDS", default_value_t = 1800)]
+        timeout: u64,
     },
 
     /// Remove a worktree, tmux window, and branch without merging
@@ -303,14 +337,104 @@ enum Commands {
         keep_branch: bool,
     },
 
+    /// Remove stale worktrees matching gc policies (age, merged, gone upstream, closed PR)
+    Gc {
+        /// Collect worktrees whose last commit is older than N days
+        #[arg(long, value_name = "DAYS")]
+        older_than: Option<u64>,
+
+        /// Collect worktrees whose branch is merged into the main branch
+        #[arg(long)]
+        merged: bool,
+
+        /// Collect worktrees whose upstream remote branch has been deleted
+        #[arg(long)]
+        gone: bool,
+
+        /// Collect worktrees whose PR is closed or merged (requires gh or glab CLI)
+        #[arg(long)]
+        pr_closed: bool,
+
+        /// Also collect worktrees that still have an open window
+        #[arg(long)]
+        include_open: bool,
+
+        /// Show what would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip confirmation
+        #[arg(short, long)]
+        force: bool,
+
+        /// Keep the local branches (only remove worktrees and windows)
+        #[arg(short = 'k', long)]
+        keep_branch: bool,
+    },
+
+    /// Show disk usage per worktree (tracked, ignored, and copied files)
+    Du {
+        /// Delete ignored build directories listed in `du.clean` in worktrees without a window
+        #[arg(long)]
+        clean: bool,
+
+        /// With --clean, also clean worktrees that still have an open window
+        #[arg(long)]
+        include_open: bool,
+
+        /// Skip confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Sync `files.copy` paths between the main worktree and a linked worktree
+    Files {
+        #[command(subcommand)]
+        command: FilesCommands,
+    },
+
     /// List all worktrees
     #[command(visible_alias = "ls")]
     List {
-        /// Show PR status for each worktree (requires gh CLI)
+        /// Show PR status for each worktree (requires gh or glab CLI)
         #[arg(long)]
         pr: bool,
     },
 
+    /// Work with pull requests for worktree branches
+    Pr {
+        #[command(subcommand)]
+        command: PrCommands,
+    },
+
+    /// List and show prompts from the prompt library
+    Prompts {
+        #[command(subcommand)]
+        command: PromptsCommands,
+    },
+
+    /// Send a worktree's failing CI checks and their logs to its agent
+    CiFeedback {
+        /// Worktree name (defaults to current directory)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Print the prompt instead of sending it to the agent
+        #[arg(long)]
+        print: bool,
+    },
+
+    /// Send the unresolved review comments on a worktree's PR to its agent
+    ReviewFeedback {
+        /// Worktree name (defaults to current directory)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Print the prompt instead of sending it to the agent
+        #[arg(long)]
+        print: bool,
+    },
+
     /// Get the filesystem path of a worktree
     Path {
         /// Worktree name (directory name)
@@ -321,6 +445,12 @@ enum Commands {
     /// Generate example .workmux.yaml configuration file
     Init,
 
+    /// Inspect and check workmux configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+
     /// Show detailed documentation (renders README.md)
     Docs,
 
@@ -385,6 +515,64 @@ enum Commands {
     /// Output git branches for shell completion (internal use)
     #[command(hide = true, name = "_complete-git-branches")]
     CompleteGitBranches,
+
+    /// Run background post_create hooks for a job (internal use)
+    #[command(hide = true, name = "_run-background-hooks")]
+    RunBackgroundHooks { job: std::path::PathBuf },
+
+    /// Wait for background post_create hooks to finish (internal use)
+    #[command(hide = true, name = "_wait-background-hooks")]
+    WaitBackgroundHooks { job: std::path::PathBuf },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the merged configuration with the file each value comes from
+    Show {
+        /// Apply the `overrides` entries matching this branch
+        #[arg(long)]
+        branch: Option<String>,
+    },
+
+    /// Check config files for unknown keys and invalid values
+    Validate {
+        /// Config files to check (defaults to the global and project configs)
+        paths: Vec<std::path::PathBuf>,
+    },
+
+    /// Print a JSON Schema for .workmux.yaml (for editor completion)
+    Schema,
+}
+
+#[derive(Subcommand)]
+enum PrCommands {
+    /// Push a worktree's branch and open a pull request against its base branch
+    Create {
+        /// Worktree name (defaults to current directory)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Generate the title and body with the configured LLM
+        #[arg(short = 'g', long)]
+        generate: bool,
+
+        /// Open the PR as a draft
+        #[arg(short = 'd', long)]
+        draft: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PromptsCommands {
+    /// List the prompts in .workmux/prompts/ and ~/.config/workmux/prompts/
+    #[command(visible_alias = "ls")]
+    List,
+
+    /// Print a prompt from the library
+    Show {
+        /// Prompt name (its path in the library without `.md`)
+        name: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -393,6 +581,55 @@ enum ClaudeCommands {
     Prune,
 }
 
+#[derive(Subcommand)]
+enum FilesCommands {
+    /// Copy changed files from a worktree back to the main worktree
+    Pull {
+        #[command(flatten)]
+        args: FilesSyncArgs,
+    },
+    /// Copy changed files from the main worktree into a worktree
+    Push {
+        #[command(flatten)]
+        args: FilesSyncArgs,
+    },
+}
+
+#[derive(clap::Args)]
+struct FilesSyncArgs {
+    /// Worktree name (directory name or branch)
+    #[arg(value_parser = WorktreeHandleParser::new())]
+    name: String,
+
+    /// Only copy these files or directories (relative paths)
+    paths: Vec<String>,
+
+    /// Show what would be copied without copying anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Show a diff for each modified file
+    #[arg(long)]
+    diff: bool,
+
+    /// Skip confirmation
+    #[arg(short, long)]
+    force: bool,
+}
+
+impl FilesSyncArgs {
+    fn run(self, direction: SyncDirection) -> Result<()> {
+        command::files::run(
+            direction,
+            &self.name,
+            &self.paths,
+            self.dry_run,
+            self.diff,
+            self.force,
+        )
+    }
+}
+
 /// Check if the command should show the nerdfont setup prompt.
 /// Only commands that display icons should trigger the prompt.
 fn should_prompt_nerdfont(cmd: &Commands) -> bool {
@@ -425,24 +662,30 @@ pub fn run() -> Result<()> {
         Commands::Add {
             branch_name,
             pr,
+            issue,
             auto_name,
             base,
             name,
             prompt,
+            template,
             setup,
             rescue,
             multi,
+            profile,
             wait,
         } => command::add::run(
             branch_name.as_deref(),
             pr,
+            &issue,
             auto_name,
             base.as_deref(),
             name,
             prompt,
+            template,
             setup,
             rescue,
             multi,
+            profile.as_deref(),
             wait,
         ),
         Commands::Open {
@@ -459,18 +702,30 @@ pub fn run() -> Result<()> {
             ignore_uncommitted,
             rebase,
             squash,
+            generate_message,
             keep,
             no_verify,
             notification,
+            via_pr,
+            wait,
+            auto,
+            timeout,
         } => command::merge::run(
             name.as_deref(),
             into.as_deref(),
             ignore_uncommitted,
             rebase,
             squash,
+            generate_message,
             keep,
             no_verify,
             notification,
+            command::merge::PrMergeFlags {
+                via_pr,
+                wait,
+                auto,
+                timeout,
+            },
         ),
         Commands::Remove {
             names,
@@ -479,9 +734,59 @@ pub fn run() -> Result<()> {
             force,
             keep_branch,
         } => command::remove::run(names, gone, all, force, keep_branch),
+        Commands::Gc {
+            older_than,
+            merged,
+            gone,
+            pr_closed,
+            include_open,
+            dry_run,
+            force,
+            keep_branch,
+        } => command::gc::run(
+            command::gc::GcArgs {
+                older_than,
+                merged,
+                gone,
+                pr_closed,
+            },
+            include_open,
+            dry_run,
+            force,
+            keep_branch,
+        ),
+        Commands::Du {
+            clean,
+            include_open,
+            force,
+        } => command::du::run(clean, include_open, force),
+        Commands::Files { command } => match command {
+            FilesCommands::Pull { args } => args.run(SyncDirection::Pull),
+            FilesCommands::Push { args } => args.run(SyncDirection::Push),
+        },
         Commands::List { pr } => command::list::run(pr),
+        Commands::Pr { command } => match command {
+            PrCommands::Create {
+                name,
+                generate,
+                draft,
+            } => command::pr::run_create(name.as_deref(), generate, draft),
+        },
+        Commands::Prompts { command } => match command {
+            PromptsCommands::List => command::prompts::run_list(),
+            PromptsCommands::Show { name } => command::prompts::run_show(&name),
+        },
+        Commands::CiFeedback { name, print } => command::ci_feedback::run(name.as_deref(), print),
+        Commands::ReviewFeedback { name, print } => {
+            command::review_feedback::run(name.as_deref(), print)
+        }
         Commands::Path { name } => command::path::run(&name),
         Commands::Init => crate::config::Config::init(),
+        Commands::Config { command } => match command {
+            ConfigCommands::Show { branch } => command::config::run_show(branch.as_deref()),
+            ConfigCommands::Validate { paths } => command::config::run_validate(paths),
+            ConfigCommands::Schema => command::config::run_schema(),
+        },
         Commands::Docs => command::docs::run(),
         Commands::Changelog => command::changelog::run(),
         Commands::Dashboard { preview_size, diff } => command::dashboard::run(preview_size, diff),
@@ -514,6 +819,8 @@ pub fn run() -> Result<()> {
             }
             Ok(())
         }
+        Commands::RunBackgroundHooks { job } => crate::workflow::background_hooks::run_job(&job),
+        Commands::WaitBackgroundHooks { job } => crate::workflow::background_hooks::wait(&job),
     }
 }
 
@@ -108,30 +108,3 @@ impl<'a> Cmd<'a> {
         Ok(success)
     }
 }
-
-/// Helper to create a shell command with additional environment variables
-pub fn shell_command_with_env(
-    command: &str,
-    workdir: &Path,
-    env_vars: &[(&str, &str)],
-) -> Result<()> {
-    let mut cmd = Command::new("sh");
-    cmd.arg("-c").arg(command).current_dir(workdir);
-
-    for (key, value) in env_vars {
-        cmd.env(key, value);
-    }
-
-    let status = cmd
-        .status()
-        .with_context(|| format!("Failed to execute shell command: {}", command))?;
-
-    if !status.success() {
-        return Err(anyhow!(
-            "Shell command failed with exit code {}: {}",
-            status.code().unwrap_or(-1),
-            command
-        ));
-    }
-    Ok(())
-}
@@ -1,21 +1,23 @@
 use crate::multiplexer::{create_backend, detect_backend, util::prefixed};
-use crate::prompt::{Prompt, PromptDocument, foreach_from_frontmatter};
+use crate::prompt::{
+    Prompt, PromptDocument, PromptMetadata, foreach_from_frontmatter, parse_prompt_document,
+};
 use crate::spinner;
 use crate::template::{
-    TemplateEnv, WorktreeSpec, create_template_env, generate_worktree_specs, parse_foreach_matrix,
-    render_prompt_body, validate_template_variables,
+    DEFAULT_BRANCH_TEMPLATE, TemplateEnv, WorktreeSpec, add_template_vars, create_template_env,
+    generate_worktree_specs, parse_foreach_matrix, render_prompt_body, validate_template_variables,
 };
 use crate::workflow::SetupOptions;
 use crate::workflow::pr::detect_remote_branch;
 use crate::workflow::prompt_loader::{PromptLoadArgs, load_prompt, parse_prompt_with_frontmatter};
-use crate::{config, git, workflow};
+use crate::{config, git, prompt_library, workflow};
 use anyhow::{Context, Result, anyhow};
 use serde_json::Value;
 use std::collections::BTreeMap;
 use std::io::{IsTerminal, Read};
 
 // Re-export the arg types that are used by the CLI
-pub use super::args::{MultiArgs, PromptArgs, RescueArgs, SetupFlags};
+pub use super::args::{MultiArgs, PromptArgs, PromptTemplateArgs, RescueArgs, SetupFlags};
 
 /// Variable name exposed to templates for stdin input lines
 const STDIN_INPUT_VAR: &str = "input";
@@ -33,14 +35,10 @@ fn generate_branch_name_with_spinner(
 ) -> Result<String> {
     let prompt_text = prompt_text.ok_or_else(|| anyhow!("Prompt is required for --auto-name"))?;
 
-    let model = config.auto_name.as_ref().and_then(|c| c.model.as_deref());
-    let system_prompt = config
-        .auto_name
-        .as_ref()
-        .and_then(|c| c.system_prompt.as_deref());
+    let auto_name = config.auto_name.clone().unwrap_or_default();
 
     let generated = spinner::with_spinner("Generating branch name", || {
-        crate::llm::generate_branch_name(prompt_text, model, system_prompt)
+        crate::llm::generate_branch_name(prompt_text, &auto_name)
     })?;
     println!("  Branch: {}", generated);
 
@@ -101,38 +99,80 @@ fn check_preconditions() -> Result<()> {
     Err(anyhow!(errors.join("\n")))
 }
 
+/// Frontmatter of the `--prompt-file`, read up front because it can choose the
+/// branch, agent and base.
+fn prompt_file_metadata(prompt_args: &PromptArgs) -> Result<PromptMetadata> {
+    match &prompt_args.prompt_file {
+        Some(path) => Ok(parse_prompt_document(&Prompt::FromFile(path.clone()))?.meta),
+        None => Ok(PromptMetadata::default()),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn run(
     branch_name: Option<&str>,
     pr: Option<u32>,
+    issues: &[u32],
     auto_name: bool,
     base: Option<&str>,
     name: Option<String>,
-    prompt_args: PromptArgs,
+    mut prompt_args: PromptArgs,
+    template: PromptTemplateArgs,
     setup: SetupFlags,
     rescue: RescueArgs,
-    multi: MultiArgs,
+    mut multi: MultiArgs,
+    profile: Option<&str>,
     wait: bool,
 ) -> Result<()> {
     // Ensure preconditions are met (git repo and tmux session)
     check_preconditions()?;
 
+    // A library prompt is used like a prompt file
+    if let Some(name) = &template.template {
+        prompt_args.prompt_file = Some(prompt_library::find(name)?.path);
+    }
+
+    // The prompt file's frontmatter fills in whatever the command line left out
+    let task = prompt_file_metadata(&prompt_args)?;
+    if multi.agent.is_empty() {
+        multi.agent = task.agent.clone();
+    }
+    if multi.branch_template.is_none() {
+        multi.branch_template = task.branch_template.clone();
+    }
+    let base = base.or(task.base.as_deref());
+    let name = name.or_else(|| task.name.clone());
+    let checks_out_existing = pr.is_some() || !issues.is_empty();
+    let branch_name = match branch_name {
+        None if !checks_out_existing => task.branch.as_deref(),
+        branch_name => branch_name,
+    };
+    let auto_name = auto_name || (task.auto_name && branch_name.is_none() && !checks_out_existing);
+    if branch_name.is_none() && !auto_name && !checks_out_existing {
+        return Err(anyhow!(
+            "A branch name is required. Pass one, use --auto-name, or set 'branch' or \
+            'auto_name: true' in the prompt frontmatter."
+        ));
+    }
+
+    let config = load_config(multi.agent.first().map(|s| s.as_str()), None, profile)?;
+    super::gc::run_on_add(&config);
+
     // Construct setup options from flags
     let mut options = SetupOptions::new(!setup.no_hooks, !setup.no_file_ops, !setup.no_pane_cmds);
     options.focus_window = !setup.background;
     options.open_if_exists = setup.open_if_exists;
 
     // If using --auto-name and config has auto_name.background = true, run in background
-    if auto_name && options.focus_window {
-        let config = config::Config::load(multi.agent.first().map(|s| s.as_str()))?;
-        if config
+    if auto_name
+        && options.focus_window
+        && config
             .auto_name
             .as_ref()
             .and_then(|c| c.background)
             .unwrap_or(false)
-        {
-            options.focus_window = false;
-        }
+    {
+        options.focus_window = false;
     }
 
     // Detect stdin input early
@@ -143,6 +183,26 @@ pub fn run(
     let is_explicit_multi =
         has_stdin || multi.foreach.is_some() || multi.count.is_some() || multi.agent.len() > 1;
 
+    if !issues.is_empty() {
+        if is_explicit_multi {
+            return Err(anyhow!(
+                "--issue cannot be combined with stdin input or multiple --agent flags. \
+                Repeat --issue to create several worktrees."
+            ));
+        }
+        let plan = IssueArgs {
+            issues,
+            branch_name,
+            auto_name,
+            base,
+            name: name.as_deref(),
+            prompt_args: &prompt_args,
+            vars: &template.vars,
+            multi: &multi,
+        };
+        return plan.run(options, profile, wait);
+    }
+
     // Handle auto-name: load prompt first, generate branch name
     // In multi-worktree mode with auto-name, we defer LLM generation to the loop
     let (final_branch_name, preloaded_prompt, remote_branch_for_pr, deferred_auto_name) =
@@ -176,7 +236,6 @@ pub fn run(
             } else {
                 // Single worktree mode - generate branch name now
                 let prompt_text = prompt.read_content()?;
-                let config = config::Config::load(multi.agent.first().map(|s| s.as_str()))?;
                 let generated = generate_branch_name_with_spinner(Some(&prompt_text), &config)?;
                 (generated, Some(prompt), None, false)
             }
@@ -188,7 +247,7 @@ pub fn run(
             // Normal flow: use provided branch name
             (
                 branch_name
-                    .expect("branch_name required when --pr and --auto-name not provided")
+                    .expect("branch_name required when --pr, --issue and --auto-name not provided")
                     .to_string(),
                 None,
                 None,
@@ -225,8 +284,11 @@ pub fn run(
 
     // Handle rescue flow early if requested
     if rescue.with_changes {
-        let (rescue_config, rescue_location) =
-            config::Config::load_with_location(multi.agent.first().map(|s| s.as_str()))?;
+        let (rescue_config, rescue_location) = load_config_with_location(
+            multi.agent.first().map(|s| s.as_str()),
+            Some(branch_name),
+            profile,
+        )?;
         let mux = create_backend(detect_backend());
         let rescue_context = workflow::WorkflowContext::new(rescue_config, mux, rescue_location)?;
         // Derive handle for rescue flow (uses config for naming strategy/prefix)
@@ -238,6 +300,7 @@ pub fn run(
             &rescue,
             &rescue_context,
             options.clone(),
+            profile,
             wait,
         )? {
             return Ok(());
@@ -308,18 +371,22 @@ pub fn run(
         determine_foreach_matrix(&multi, prompt_doc.as_ref(), stdin_lines)?;
 
     // Generate worktree specifications
-    let specs = generate_worktree_specs(
+    let mut specs = generate_worktree_specs(
         &template_base_name,
         &multi.agent,
         multi.count,
         effective_foreach_rows.as_deref(),
         &env,
-        &multi.branch_template,
+        multi
+            .branch_template
+            .as_deref()
+            .unwrap_or(DEFAULT_BRANCH_TEMPLATE),
     )?;
 
     if specs.is_empty() {
         return Err(anyhow!("No worktree specifications were generated"));
     }
+    add_template_vars(&mut specs, &template.vars)?;
 
     // Validate prompt template variables before proceeding to create worktrees.
     // We use the context from the first spec (variable schema is consistent across specs).
@@ -342,10 +409,124 @@ pub fn run(
         wait,
         deferred_auto_name,
         max_concurrent: multi.max_concurrent,
+        profile,
     };
     plan.execute()
 }
 
+/// Arguments for `add --issue`
+struct IssueArgs<'a> {
+    issues: &'a [u32],
+    branch_name: Option<&'a str>,
+    auto_name: bool,
+    base: Option<&'a str>,
+    name: Option<&'a str>,
+    prompt_args: &'a PromptArgs,
+    vars: &'a [(String, String)],
+    multi: &'a MultiArgs,
+}
+
+impl IssueArgs<'_> {
+    /// Create one worktree per issue. The issue is available to the prompt as
+    /// `{{ issue.* }}`, and is the prompt when none was given.
+    fn run(&self, options: SetupOptions, profile: Option<&str>, wait: bool) -> Result<()> {
+        if self.issues.len() > 1 && (self.branch_name.is_some() || self.name.is_some()) {
+            return Err(anyhow!(
+                "A branch name and --name can only be given with a single --issue"
+            ));
+        }
+
+        let issues = workflow::issue::fetch_issues(self.issues)?;
+
+        let prompt = load_prompt(&PromptLoadArgs {
+            prompt_editor: self.prompt_args.prompt_editor,
+            prompt_inline: self.prompt_args.prompt.as_deref(),
+            prompt_file: self.prompt_args.prompt_file.as_ref(),
+        })?;
+        let prompt_doc = match prompt {
+            Some(prompt) => {
+                let from_editor_or_file =
+                    self.prompt_args.prompt_editor || matches!(prompt, Prompt::FromFile(_));
+                parse_prompt_with_frontmatter(&prompt, from_editor_or_file)?
+            }
+            None => PromptDocument {
+                body: workflow::issue::DEFAULT_ISSUE_PROMPT.to_string(),
+                meta: Default::default(),
+            },
+        };
+        if prompt_doc.meta.foreach.is_some() {
+            return Err(anyhow!(
+                "Cannot use 'foreach' in the prompt frontmatter with --issue"
+            ));
+        }
+
+        let env = create_template_env();
+        let mut specs = Vec::with_capacity(issues.len());
+        for issue in &issues {
+            let branch_name = match self.branch_name {
+                Some(name) => name.to_string(),
+                None => workflow::issue::branch_name(issue),
+            };
+            let mut spec = generate_worktree_specs(
+                &branch_name,
+                &self.multi.agent,
+                None,
+                None,
+                &env,
+                self.multi
+                    .branch_template
+                    .as_deref()
+                    .unwrap_or(DEFAULT_BRANCH_TEMPLATE),
+            )?
+            .remove(0);
+            if let Value::Object(context) = &mut spec.template_context {
+                context.insert("issue".to_string(), serde_json::to_value(issue)?);
+            }
+            specs.push(spec);
+        }
+        add_template_vars(&mut specs, self.vars)?;
+
+        validate_template_variables(&env, &prompt_doc.body, &specs[0].template_context)
+            .context("Prompt template uses undefined variables")?;
+
+        let plan = CreationPlan {
+            specs: &specs,
+            resolved_base: self.base,
+            remote_branch: None,
+            prompt_doc: Some(&prompt_doc),
+            options,
+            env: &env,
+            explicit_name: self.name,
+            wait,
+            // The branch name is generated from each issue's rendered prompt
+            deferred_auto_name: self.auto_name,
+            max_concurrent: self.multi.max_concurrent,
+            profile,
+        };
+        plan.execute()
+    }
+}
+
+/// Load config for `agent`, applying branch overrides for `branch` (when known)
+/// and then `profile` on top if one was selected.
+fn load_config(
+    agent: Option<&str>,
+    branch: Option<&str>,
+    profile: Option<&str>,
+) -> Result<config::Config> {
+    config::Config::load(agent)?.for_branch(branch, profile, agent)
+}
+
+/// Like `load_config`, also returning where the project config was found.
+fn load_config_with_location(
+    agent: Option<&str>,
+    branch: Option<&str>,
+    profile: Option<&str>,
+) -> Result<(config::Config, Option<config::ConfigLocation>)> {
+    let (config, location) = config::Config::load_with_location(agent)?;
+    Ok((config.for_branch(branch, profile, agent)?, location))
+}
+
 /// Handle the rescue flow (--with-changes).
 /// Returns Ok(true) if rescue flow was handled, Ok(false) if normal flow should continue.
 fn handle_rescue_flow(
@@ -354,6 +535,7 @@ fn handle_rescue_flow(
     rescue: &RescueArgs,
     context: &workflow::WorkflowContext,
     options: SetupOptions,
+    profile: Option<&str>,
     wait: bool,
 ) -> Result<bool> {
     if !rescue.with_changes {
@@ -369,6 +551,9 @@ fn handle_rescue_flow(
         options,
     )
     .context("Failed to move uncommitted changes")?;
+    if let Some(profile) = profile {
+        git::set_branch_profile(branch_name, profile)?;
+    }
 
     println!(
         "✓ Moved uncommitted changes to new worktree for branch '{}'\n  Worktree: {}\n  Original worktree is now clean",
@@ -467,6 +652,7 @@ struct CreationPlan<'a> {
     wait: bool,
     deferred_auto_name: bool,
     max_concurrent: Option<u32>,
+    profile: Option<&'a str>,
 }
 
 impl<'a> CreationPlan<'a> {
@@ -503,10 +689,6 @@ impl<'a> CreationPlan<'a> {
                     }
                 }
             }
-            // Load config for this specific agent to ensure correct agent resolution
-            let (config, config_location) =
-                config::Config::load_with_location(spec.agent.as_deref())?;
-
             // Render prompt first (needed for deferred auto-name)
             let rendered_prompt = if let Some(doc) = self.prompt_doc {
                 Some(
@@ -519,11 +701,29 @@ impl<'a> CreationPlan<'a> {
 
             // If auto-name was deferred, run it now using the rendered prompt
             let final_branch_name = if self.deferred_auto_name {
+                let config = load_config(spec.agent.as_deref(), None, self.profile)?;
                 generate_branch_name_with_spinner(rendered_prompt.as_deref(), &config)?
             } else {
                 spec.branch_name.clone()
             };
 
+            // Load config for this specific agent and branch to ensure correct agent resolution
+            let (config, config_location) = load_config_with_location(
+                spec.agent.as_deref(),
+                Some(&final_branch_name),
+                self.profile,
+            )?;
+            let config = match self.prompt_doc {
+                Some(doc) => doc.meta.apply_to(config),
+                None => config,
+            };
+
+            // A matching override's main_branch is the default base for new branches
+            let override_base = match (self.resolved_base, self.remote_branch) {
+                (None, None) => config.override_main_branch(&final_branch_name)?,
+                _ => None,
+            };
+
             if self.specs.len() > 1 {
                 println!(
                     "\n--- [{}/{}] Creating worktree: {} ---",
@@ -540,6 +740,7 @@ impl<'a> CreationPlan<'a> {
 
             let prompt_for_spec = rendered_prompt.map(Prompt::Inline);
 
+            super::announce_hooks(&config, Some(&self.options), super::HookPhase::PreCreate);
             super::announce_hooks(&config, Some(&self.options), super::HookPhase::PostCreate);
 
             // Create a WorkflowContext for this spec's config (reuse shared mux)
@@ -562,7 +763,7 @@ impl<'a> CreationPlan<'a> {
                 workflow::CreateArgs {
                     branch_name: &final_branch_name,
                     handle: &handle,
-                    base_branch: self.resolved_base,
+                    base_branch: self.resolved_base.or(override_base.as_deref()),
                     remote_branch: self.remote_branch,
                     prompt: prompt_for_spec.as_ref(),
                     options: self.options.clone(),
@@ -576,6 +777,17 @@ impl<'a> CreationPlan<'a> {
                 )
             })?;
 
+            // Remember the profile so `workmux open` can apply it again
+            if let Some(profile) = self.profile {
+                git::set_branch_profile(&result.branch_name, profile)?;
+            }
+            // Remember the prompt's labels for the PR opened later
+            if let Some(doc) = self.prompt_doc
+                && !doc.meta.labels.is_empty()
+            {
+                git::set_branch_labels(&result.branch_name, &doc.meta.labels)?;
+            }
+
             if result.post_create_hooks_run > 0 {
                 println!("✓ Setup complete");
             }
@@ -20,6 +20,22 @@ pub struct PromptArgs {
     pub prompt_editor: bool,
 }
 
+#[derive(clap::Args, Debug)]
+pub struct PromptTemplateArgs {
+    /// Use a named prompt from the prompt library (see `workmux prompts list`)
+    #[arg(
+        short = 'T',
+        long = "template",
+        value_name = "NAME",
+        conflicts_with_all = ["prompt", "prompt_file", "prompt_editor"]
+    )]
+    pub template: Option<String>,
+
+    /// Set a variable for the prompt template. Can be repeated
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = crate::template::parse_template_var)]
+    pub vars: Vec<(String, String)>,
+}
+
 #[derive(clap::Args, Debug)]
 pub struct SetupFlags {
     /// Skip running post-create hooks
@@ -67,11 +83,9 @@ pub struct MultiArgs {
 
     /// Template for branch names in multi-worktree modes.
     /// Variables: {{ base_name }}, {{ agent }}, {{ num }}, {{ foreach_vars }}.
-    #[arg(
-        long,
-        default_value = r#"{{ base_name }}{% if agent %}-{{ agent | slugify }}{% endif %}{% for key in foreach_vars %}-{{ foreach_vars[key] | slugify }}{% endfor %}{% if num %}-{{ num }}{% endif %}"#
-    )]
-    pub branch_template: String,
+    /// Defaults to the base name followed by the agent, foreach values and number.
+    #[arg(long)]
+    pub branch_template: Option<String>,
 
     /// Maximum number of worktrees to run concurrently.
     /// When set, waits for a slot to open before creating new worktrees.
@@ -0,0 +1,43 @@
+use anyhow::{Context, Result, anyhow};
+
+use crate::multiplexer::{create_backend, detect_backend};
+use crate::workflow::feedback;
+use crate::{config, forge, git, spinner};
+
+/// Send the failed CI checks of a worktree's branch to the agent running in it
+pub fn run(name: Option<&str>, print: bool) -> Result<()> {
+    let name = super::resolve_name(name)?;
+    let (worktree_path, branch) = git::find_worktree(&name)
+        .with_context(|| format!("No worktree found with name '{}'", name))?;
+    let config = config::Config::load(None)?.apply_branch_overrides(&branch, None)?;
+
+    let forge = forge::for_repo(Some(&worktree_path), &config);
+    let checks = spinner::with_spinner("Fetching failed checks", || {
+        forge.failed_checks(&worktree_path, &branch)
+    })?;
+    let Some(prompt) = feedback::ci_failure_prompt(&branch, &checks) else {
+        println!("No failed checks on '{}'", branch);
+        return Ok(());
+    };
+
+    if print {
+        print!("{}", prompt);
+        return Ok(());
+    }
+
+    let mux = create_backend(detect_backend());
+    let pane_id = feedback::find_agent_pane(mux.as_ref(), &worktree_path)?.ok_or_else(|| {
+        anyhow!(
+            "No agent is running in '{}'. Use --print to output the prompt instead.",
+            name
+        )
+    })?;
+    feedback::send_to_agent(mux.as_ref(), &pane_id, &prompt)?;
+
+    println!(
+        "✓ Sent {} failed check(s) to the agent in '{}'",
+        checks.len(),
+        name
+    );
+    Ok(())
+}
@@ -1,6 +1,9 @@
+use crate::config::HookEntry;
 use crate::multiplexer::{create_backend, detect_backend, util};
+use crate::workflow::hooks;
 use crate::{config, git};
 use anyhow::{Context, Result, anyhow};
+use std::path::{Path, PathBuf};
 
 pub fn run(name: Option<&str>) -> Result<()> {
     let config = config::Config::load(None)?;
@@ -9,10 +12,10 @@ pub fn run(name: Option<&str>) -> Result<()> {
 
     // When no name is provided, prefer the current window name
     // This handles duplicate windows (e.g., wm:feature-2) correctly
-    let (full_window_name, is_current_window) = match name {
+    let (full_window_name, is_current_window, worktree) = match name {
         Some(name) => {
             // Explicit name provided - validate the worktree exists and get path
-            let (path, _branch) = git::find_worktree(name).with_context(|| {
+            let (path, branch) = git::find_worktree(name).with_context(|| {
                 format!(
                     "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
                     name
@@ -23,23 +26,26 @@ pub fn run(name: Option<&str>) -> Result<()> {
             let prefixed = util::prefixed(prefix, handle);
             let current_window = mux.current_window_name()?;
             let is_current = current_window.as_deref() == Some(&prefixed);
-            (prefixed, is_current)
+            (prefixed, is_current, Some((path, branch)))
         }
         None => {
             // No name provided - check if we're in a workmux window
             if let Some(current) = mux.current_window_name()? {
-                if current.starts_with(prefix) {
+                if let Some(handle) = current.strip_prefix(prefix) {
                     // We're in a workmux window, use it directly
-                    (current.clone(), true)
+                    let worktree = find_window_worktree(handle);
+                    (current.clone(), true, worktree)
                 } else {
                     // Not in a workmux window, fall back to directory name
                     let handle = super::resolve_name(None)?;
-                    (util::prefixed(prefix, &handle), false)
+                    let worktree = git::find_worktree(&handle).ok();
+                    (util::prefixed(prefix, &handle), false, worktree)
                 }
             } else {
                 // Not in tmux, use directory name
                 let handle = super::resolve_name(None)?;
-                (util::prefixed(prefix, &handle), false)
+                let worktree = git::find_worktree(&handle).ok();
+                (util::prefixed(prefix, &handle), false, worktree)
             }
         }
     };
@@ -52,6 +58,15 @@ pub fn run(name: Option<&str>) -> Result<()> {
         ));
     }
 
+    // Run on-close hooks while the window still exists; a failing hook keeps it open
+    if let Some((path, branch)) = &worktree
+        && let Some(on_close) = &config.on_close
+        && !on_close.is_empty()
+    {
+        super::announce_hooks(&config, None, super::HookPhase::OnClose);
+        run_on_close_hooks(on_close, path, branch)?;
+    }
+
     if is_current_window {
         // Schedule the window close with a small delay so the command can complete
         mux.schedule_window_close(&full_window_name, std::time::Duration::from_millis(100))?;
@@ -64,3 +79,31 @@ pub fn run(name: Option<&str>) -> Result<()> {
 
     Ok(())
 }
+
+/// Find the worktree behind a window handle, including duplicates (e.g. `feature-2`).
+fn find_window_worktree(handle: &str) -> Option<(PathBuf, String)> {
+    git::find_worktree(handle).ok().or_else(|| {
+        let (base, suffix) = handle.rsplit_once('-')?;
+        suffix.parse::<u32>().ok()?;
+        git::find_worktree(base).ok()
+    })
+}
+
+fn run_on_close_hooks(hooks: &[HookEntry], worktree_path: &Path, branch: &str) -> Result<()> {
+    let handle = worktree_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let project_root = git::get_main_worktree_root()?;
+    let hook_env = hooks::worktree_env(&handle, branch, worktree_path, &project_root);
+    hooks::run_hooks(
+        hooks,
+        &hooks::HookContext {
+            phase: "on_close",
+            handle: &handle,
+            workdir: worktree_path,
+            env: &hooks::env_refs(&hook_env),
+        },
+    )?;
+    Ok(())
+}
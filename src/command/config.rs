@@ -0,0 +1,376 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_yaml::{Mapping, Value};
+
+use crate::config::{self, Config};
+use crate::config_schema::{self, Segment};
+
+/// Top-level keys whose fields are merged one by one, so each field gets its own source.
+const PER_FIELD_KEYS: &[&str] = &[
+    "files",
+    "status_icons",
+    "dashboard",
+    "profiles",
+    "overrides",
+];
+
+/// Print the merged configuration, annotating each value with the file it came from.
+/// With `branch`, matching `overrides` entries are applied on top.
+pub fn run_show(branch: Option<&str>) -> Result<()> {
+    let mut config = Config::load(None)?;
+    let mut layers: Vec<(String, Value)> = Config::source_files()?
+        .into_iter()
+        .map(|path| {
+            let value = fs::read_to_string(&path)
+                .ok()
+                .and_then(|contents| serde_yaml::from_str(&contents).ok())
+                .unwrap_or(Value::Null);
+            (display_path(&path), value)
+        })
+        .collect();
+
+    if let Some(branch) = branch {
+        config = config.apply_branch_overrides(branch, None)?;
+        // Matching overrides rank above every file, in the order they are applied
+        let mut override_layers = Vec::new();
+        for (source, value) in &layers {
+            let Some(Value::Mapping(overrides)) = value.get("overrides") else {
+                continue;
+            };
+            for (pattern, overlay) in overrides {
+                if let Some(pattern) = pattern.as_str()
+                    && config::branch_pattern_matches(pattern, branch)?
+                {
+                    let label = format!("{} (overrides: {})", source, pattern);
+                    override_layers.push((label, overlay.clone()));
+                }
+            }
+        }
+        layers.extend(override_layers);
+    }
+
+    let Value::Mapping(merged) = serde_yaml::to_value(&config)? else {
+        return Ok(());
+    };
+
+    let defaults = serde_yaml::to_value(Config::default())?;
+    for (key, mut value) in merged {
+        let Some(name) = key.as_str() else { continue };
+        // Profiles and overrides are overlays: only show what they set, not the
+        // defaults they carry.
+        if (name == "profiles" || name == "overrides")
+            && let Value::Mapping(overlays) = &mut value
+        {
+            for (_, overlay) in overlays.iter_mut() {
+                if let Value::Mapping(fields) = overlay {
+                    fields.retain(|k, v| defaults.get(k) != Some(v));
+                }
+            }
+        }
+        let Some(value) = strip_nulls(value) else {
+            continue;
+        };
+
+        match value {
+            Value::Mapping(fields) if PER_FIELD_KEYS.contains(&name) => {
+                println!("{}:", name);
+                for (field, value) in fields {
+                    let Some(field_name) = field.as_str() else {
+                        continue;
+                    };
+                    let source = source_of(&layers, &[name, field_name]);
+                    for line in annotate(field, value, &source)?.lines() {
+                        println!("  {}", line);
+                    }
+                }
+            }
+            value => {
+                let source = source_of(&layers, &[name]);
+                print!("{}", annotate(key, value, &source)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check config files for syntax errors, unknown keys and invalid pane layouts.
+///
+/// With no paths, checks the global and project configs and the files they extend.
+pub fn run_validate(paths: Vec<PathBuf>) -> Result<()> {
+    let mut files = Vec::new();
+    if paths.is_empty() {
+        files = Config::source_files()?;
+        if files.is_empty() {
+            println!("No config files found. Run 'workmux init' to create .workmux.yaml.");
+            return Ok(());
+        }
+    } else {
+        for path in paths {
+            if !path.exists() {
+                anyhow::bail!("Config file not found: {}", path.display());
+            }
+            config::collect_extends(&path, &mut files);
+        }
+    }
+
+    let schema = config_schema::schema();
+    let mut error_count = 0;
+    for file in &files {
+        let problems = validate_file(file, &schema)?;
+        if problems.is_empty() {
+            println!("✓ {} is valid", display_path(file));
+            continue;
+        }
+        for problem in &problems {
+            println!("{}:{}", display_path(file), problem);
+        }
+        error_count += problems.len();
+    }
+
+    if error_count > 0 {
+        anyhow::bail!(
+            "Found {} problem{} in config",
+            error_count,
+            if error_count == 1 { "" } else { "s" }
+        );
+    }
+    Ok(())
+}
+
+/// Print the JSON Schema for `.workmux.yaml`.
+pub fn run_schema() -> Result<()> {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&config_schema::schema())?
+    );
+    Ok(())
+}
+
+/// A validation error, with the position it was found at when known.
+struct Problem {
+    location: Option<(usize, usize)>,
+    message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Some((line, column)) => write!(f, "{}:{}: {}", line, column, self.message),
+            None => write!(f, " {}", self.message),
+        }
+    }
+}
+
+fn validate_file(path: &Path, schema: &serde_json::Value) -> Result<Vec<Problem>> {
+    let source = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+    let document: Value = match serde_yaml::from_str(&source) {
+        Ok(document) => document,
+        Err(e) => return Ok(vec![yaml_problem(&e)]),
+    };
+
+    let mut problems: Vec<Problem> = config_schema::unknown_keys(&document, schema)
+        .into_iter()
+        .map(|key_path| Problem {
+            location: config_schema::locate(&source, &key_path),
+            message: format!("unknown key '{}'", config_schema::display_path(&key_path)),
+        })
+        .collect();
+
+    match serde_yaml::from_str::<Config>(&source) {
+        Err(e) => problems.push(yaml_problem(&e)),
+        Ok(parsed) => {
+            let at = |key_path: &[Segment]| config_schema::locate(&source, key_path);
+
+            if let Some(panes) = &parsed.panes
+                && let Err(e) = config::validate_panes_config(panes)
+            {
+                problems.push(Problem {
+                    location: at(&[Segment::Key("panes".into())]),
+                    message: e.to_string(),
+                });
+            }
+
+            for (name, profile) in parsed.profiles.iter().flatten() {
+                if let Some(panes) = &profile.panes
+                    && let Err(e) = config::validate_panes_config(panes)
+                {
+                    problems.push(Problem {
+                        location: at(&[
+                            Segment::Key("profiles".into()),
+                            Segment::Key(name.clone()),
+                            Segment::Key("panes".into()),
+                        ]),
+                        message: format!("profile '{}': {}", name, e),
+                    });
+                }
+            }
+
+            for (pattern, overlay) in parsed.overrides.iter().flat_map(|o| &o.0) {
+                let at_pattern = |key: Option<&str>| {
+                    let mut key_path = vec![
+                        Segment::Key("overrides".into()),
+                        Segment::Key(pattern.clone()),
+                    ];
+                    key_path.extend(key.map(|k| Segment::Key(k.into())));
+                    at(&key_path)
+                };
+                if let Err(e) = config::branch_pattern_matches(pattern, "") {
+                    problems.push(Problem {
+                        location: at_pattern(None),
+                        message: format!("{:#}", e),
+                    });
+                }
+                if let Some(panes) = &overlay.panes
+                    && let Err(e) = config::validate_panes_config(panes)
+                {
+                    problems.push(Problem {
+                        location: at_pattern(Some("panes")),
+                        message: format!("override '{}': {}", pattern, e),
+                    });
+                }
+            }
+
+            let dir = path.parent().unwrap_or_else(|| Path::new("."));
+            for include in parsed.extends.iter().flat_map(|e| e.paths()) {
+                if !config::resolve_include_path(dir, include).exists() {
+                    let key = if document.get("include").is_some() {
+                        "include"
+                    } else {
+                        "extends"
+                    };
+                    problems.push(Problem {
+                        location: at(&[Segment::Key(key.into())]),
+                        message: format!("extended config file not found: {}", include),
+                    });
+                }
+            }
+        }
+    }
+
+    problems.sort_by_key(|p| p.location);
+    Ok(problems)
+}
+
+fn yaml_problem(error: &serde_yaml::Error) -> Problem {
+    let message = error.to_string();
+    // serde_yaml appends " at line N column M"; the position is reported separately.
+    let message = match message.rfind(" at line ") {
+        Some(i) if error.location().is_some() => message[..i].to_string(),
+        _ => message,
+    };
+    Problem {
+        location: error.location().map(|l| (l.line(), l.column())),
+        message,
+    }
+}
+
+/// Serialize a single `key: value` entry with a trailing `# source` comment.
+fn annotate(key: Value, value: Value, source: &str) -> Result<String> {
+    let mut entry = Mapping::new();
+    entry.insert(key, value);
+    let yaml = serde_yaml::to_string(&entry)?;
+    let mut out = String::new();
+    for (i, line) in yaml.lines().enumerate() {
+        out.push_str(line);
+        if i == 0 {
+            out.push_str("  # ");
+            out.push_str(source);
+        }
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// The last config file that sets the value at `key_path`, or "default".
+fn source_of(layers: &[(String, Value)], key_path: &[&str]) -> String {
+    layers
+        .iter()
+        .rev()
+        .find(|(_, value)| {
+            key_path
+                .iter()
+                .try_fold(value, |value, key| value.get(*key))
+                .is_some_and(|v| !v.is_null())
+        })
+        .map(|(source, _)| source.clone())
+        .unwrap_or_else(|| "default".to_string())
+}
+
+/// Drop null values (unset options) recursively; returns None if nothing is left.
+fn strip_nulls(value: Value) -> Option<Value> {
+    match value {
+        Value::Null => None,
+        Value::Mapping(map) => {
+            let map: Mapping = map
+                .into_iter()
+                .filter_map(|(k, v)| strip_nulls(v).map(|v| (k, v)))
+                .collect();
+            (!map.is_empty()).then_some(Value::Mapping(map))
+        }
+        Value::Sequence(items) => Some(Value::Sequence(
+            items.into_iter().filter_map(strip_nulls).collect(),
+        )),
+        other => Some(other),
+    }
+}
+
+fn display_path(path: &Path) -> String {
+    if let Some(home) = home::home_dir()
+        && let Ok(rest) = path.strip_prefix(&home)
+    {
+        return format!("~/{}", rest.display());
+    }
+    path.display().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_of_prefers_the_last_layer_that_sets_a_key() {
+        let global: Value = serde_yaml::from_str("agent: codex\nfiles:\n  copy: [.env]\n").unwrap();
+        let project: Value = serde_yaml::from_str("agent: claude\nmain_branch:\n").unwrap();
+        let layers = vec![
+            ("/etc/global.yaml".to_string(), global),
+            ("/repo/.workmux.yaml".to_string(), project),
+        ];
+
+        assert_eq!(source_of(&layers, &["agent"]), "/repo/.workmux.yaml");
+        assert_eq!(source_of(&layers, &["files", "copy"]), "/etc/global.yaml");
+        assert_eq!(source_of(&layers, &["main_branch"]), "default");
+        assert_eq!(source_of(&layers, &["files", "symlink"]), "default");
+    }
+
+    #[test]
+    fn validate_reports_positions_for_each_kind_of_problem() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".workmux.yaml");
+        fs::write(
+            &path,
+            "panes:\n  - command: vim\n    split: horizontal\nfiles:\n  symlnk: [node_modules]\n",
+        )
+        .unwrap();
+
+        let problems = validate_file(&path, &config_schema::schema()).unwrap();
+        let rendered: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "1:1: First pane (index 0) cannot have a 'split' direction.",
+                "5:3: unknown key 'files.symlnk'",
+            ]
+        );
+
+        fs::write(&path, "panes:\n  - split: diagonal\n").unwrap();
+        let problems = validate_file(&path, &config_schema::schema()).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].location, Some((2, 12)));
+        assert!(problems[0].message.contains("unknown variant `diagonal`"));
+    }
+}
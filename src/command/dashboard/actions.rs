@@ -30,6 +30,13 @@ pub enum Action {
     LoadWipDiff,
     SendCommitDashboard,
     TriggerMergeDashboard,
+    SendCiFeedback,
+    TogglePrPanel,
+
+    // PR panel
+    OpenPr,
+    RerunFailedChecks,
+    MarkPrReady,
 
     // Input mode
     SendKey(String),
@@ -44,6 +51,7 @@ pub enum Action {
     EnterPatchMode,
     SendCommitDiff,
     TriggerMergeDiff,
+    GenerateCommitMessage,
 
     // Patch mode
     StageAndNext,
@@ -60,6 +68,11 @@ pub enum Action {
     SendComment,
     DeleteChar,
     AppendChar(char),
+
+    // Commit message input
+    CancelCommitMessage,
+    ConfirmCommitMessage,
+    InsertNewline,
 }
 
 /// Apply an action to the app state.
@@ -149,6 +162,28 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             app.trigger_merge_for_selected();
             false
         }
+        Action::SendCiFeedback => {
+            app.send_ci_feedback_to_selected();
+            false
+        }
+        Action::TogglePrPanel => {
+            app.toggle_pr_panel();
+            false
+        }
+
+        // PR panel
+        Action::OpenPr => {
+            app.open_selected_pr();
+            false
+        }
+        Action::RerunFailedChecks => {
+            app.rerun_selected_pr_checks();
+            false
+        }
+        Action::MarkPrReady => {
+            app.mark_selected_pr_ready();
+            false
+        }
 
         // Input mode
         Action::SendKey(key) => {
@@ -207,6 +242,10 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             app.trigger_merge();
             false
         }
+        Action::GenerateCommitMessage => {
+            app.start_commit_message();
+            false
+        }
 
         // Patch mode
         Action::StageAndNext => {
@@ -256,20 +295,46 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             false
         }
         Action::DeleteChar => {
-            if let ViewMode::Diff(ref mut diff) = app.view_mode
-                && let Some(ref mut input) = diff.comment_input
-            {
+            if let Some(input) = active_input(app) {
                 input.pop();
             }
             false
         }
         Action::AppendChar(c) => {
-            if let ViewMode::Diff(ref mut diff) = app.view_mode
-                && let Some(ref mut input) = diff.comment_input
-            {
+            if let Some(input) = active_input(app) {
                 input.push(c);
             }
             false
         }
+
+        // Commit message input
+        Action::CancelCommitMessage => {
+            if let ViewMode::Diff(ref mut diff) = app.view_mode {
+                diff.commit_message = None;
+            }
+            false
+        }
+        Action::ConfirmCommitMessage => {
+            app.confirm_commit_message();
+            false
+        }
+        Action::InsertNewline => {
+            if let Some(input) = active_input(app) {
+                input.push('\n');
+            }
+            false
+        }
+    }
+}
+
+/// The text buffer being typed into: the commit message (once generated) or the hunk comment.
+fn active_input(app: &mut App) -> Option<&mut String> {
+    let ViewMode::Diff(ref mut diff) = app.view_mode else {
+        return None;
+    };
+    match diff.commit_message {
+        Some(ref mut message) if message.generating => None,
+        Some(ref mut message) => Some(&mut message.text),
+        None => diff.comment_input.as_mut(),
     }
 }
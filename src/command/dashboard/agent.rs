@@ -65,6 +65,15 @@ pub fn format_duration(secs: u64) -> String {
     format!("{:02}:{:02}:{:02}", hours, mins, secs)
 }
 
+/// Format a check's run time compactly: "45s", "3m 20s", "1h 05m"
+pub fn format_short_duration(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,4 +159,11 @@ mod tests {
         assert_eq!(format_duration(61), "00:01:01");
         assert_eq!(format_duration(3661), "01:01:01");
     }
+
+    #[test]
+    fn test_format_short_duration() {
+        assert_eq!(format_short_duration(45), "45s");
+        assert_eq!(format_short_duration(200), "3m 20s");
+        assert_eq!(format_short_duration(3900), "1h 05m");
+    }
 }
@@ -21,6 +21,7 @@ use super::monitor::AgentMonitor;
 
 use super::agent;
 use super::diff::DiffView;
+use super::pr_panel::{PrPanel, PrPanelUpdate, open_in_browser};
 use super::settings::{
     load_hide_stale, load_last_pane_id, load_preview_size, save_hide_stale, save_last_pane_id,
     save_preview_size,
@@ -83,6 +84,16 @@ pub struct App {
     /// Channel for PR status updates (repo_root, prs)
     pr_rx: mpsc::Receiver<(PathBuf, HashMap<String, PrSummary>)>,
     pr_tx: mpsc::Sender<(PathBuf, HashMap<String, PrSummary>)>,
+    /// PR panel state; Some while the panel is shown in place of the preview
+    pub pr_panel: Option<PrPanel>,
+    /// Channel for PR panel overviews and action results
+    pr_panel_rx: mpsc::Receiver<PrPanelUpdate>,
+    pr_panel_tx: mpsc::Sender<PrPanelUpdate>,
+    /// Flag to prevent concurrent PR panel fetches
+    is_pr_panel_fetching: Arc<AtomicBool>,
+    /// Channel for generated commit messages (worktree path, message or error)
+    commit_msg_rx: mpsc::Receiver<(PathBuf, Result<String, String>)>,
+    commit_msg_tx: mpsc::Sender<(PathBuf, Result<String, String>)>,
     /// Last PR fetch time
     last_pr_fetch: std::time::Instant,
     /// Flag to prevent concurrent PR fetches
@@ -108,6 +119,8 @@ impl App {
         let config = Config::load(None)?;
         let (git_tx, git_rx) = mpsc::channel();
         let (pr_tx, pr_rx) = mpsc::channel();
+        let (pr_panel_tx, pr_panel_rx) = mpsc::channel();
+        let (commit_msg_tx, commit_msg_rx) = mpsc::channel();
 
         // Get the active pane's directory to indicate the active worktree.
         // Try multiplexer first (handles popup case), fall back to current_dir.
@@ -155,6 +168,12 @@ impl App {
             pr_statuses,
             pr_rx,
             pr_tx,
+            pr_panel: None,
+            pr_panel_rx,
+            pr_panel_tx,
+            is_pr_panel_fetching: Arc::new(AtomicBool::new(false)),
+            commit_msg_rx,
+            commit_msg_tx,
             // Set to past to trigger immediate fetch on first refresh
             last_pr_fetch: std::time::Instant::now() - PR_FETCH_INTERVAL,
             is_pr_fetching: Arc::new(AtomicBool::new(false)),
@@ -242,6 +261,18 @@ impl App {
 
         // Consume any pending git status updates from background thread
         while let Ok((path, status)) = self.git_rx.try_recv() {
+            // Show PRs opened with `workmux pr create` before the next PR fetch
+            if let (Some(number), Some(branch), Some(repo_root)) = (
+                status.pr_number,
+                status.branch.as_ref(),
+                self.repo_roots.get(&path),
+            ) {
+                self.pr_statuses
+                    .entry(repo_root.clone())
+                    .or_default()
+                    .entry(branch.clone())
+                    .or_insert_with(|| PrSummary::recorded(number));
+            }
             self.git_statuses.insert(path, status);
         }
 
@@ -368,6 +399,7 @@ impl App {
 
         let tx = self.pr_tx.clone();
         let is_fetching = self.is_pr_fetching.clone();
+        let config = self.config.clone();
 
         std::thread::spawn(move || {
             struct ResetFlag(Arc<AtomicBool>);
@@ -379,7 +411,8 @@ impl App {
             let _reset = ResetFlag(is_fetching);
 
             for repo_root in repo_roots {
-                match crate::github::list_prs_in_repo(&repo_root) {
+                let forge = crate::forge::for_repo(Some(&repo_root), &config);
+                match forge.list_prs(&repo_root) {
                     Ok(prs) => {
                         let _ = tx.send((repo_root, prs));
                     }
@@ -391,6 +424,266 @@ impl App {
         });
     }
 
+    /// Fetch the failed CI checks for the selected agent's branch in the
+    /// background and paste them into its pane as a prompt
+    pub fn send_ci_feedback_to_selected(&self) {
+        let Some(agent) = self
+            .table_state
+            .selected()
+            .and_then(|selected| self.agents.get(selected))
+        else {
+            return;
+        };
+        let Some(branch) = self
+            .git_statuses
+            .get(&agent.path)
+            .and_then(|status| status.branch.clone())
+        else {
+            return;
+        };
+        let mux = self.mux.clone();
+        let pane_id = agent.pane_id.clone();
+        let path = agent.path.clone();
+        let config = self.config.clone();
+
+        std::thread::spawn(move || {
+            let forge = crate::forge::for_repo(Some(&path), &config);
+            match forge.failed_checks(&path, &branch) {
+                Ok(checks) => {
+                    if let Some(prompt) =
+                        crate::workflow::feedback::ci_failure_prompt(&branch, &checks)
+                    {
+                        let _ = crate::workflow::feedback::send_to_agent(
+                            mux.as_ref(),
+                            &pane_id,
+                            &prompt,
+                        );
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to fetch failed checks for {}: {:#}", branch, e);
+                }
+            }
+        });
+    }
+
+    /// Spawn a background thread that asks the LLM for a commit message
+    /// describing the staged changes in a worktree
+    pub fn spawn_commit_message_generation(&self, worktree_path: PathBuf) {
+        let tx = self.commit_msg_tx.clone();
+        let auto_name = self.config.auto_name.clone().unwrap_or_default();
+
+        std::thread::spawn(move || {
+            let result = git::get_staged_diff(&worktree_path)
+                .and_then(|diff| crate::llm::generate_commit_message(&diff, &auto_name))
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send((worktree_path, result));
+        });
+    }
+
+    /// Fill the commit message popup with a finished suggestion, if one arrived
+    /// for the worktree whose diff is open
+    pub fn poll_commit_message(&mut self) {
+        while let Ok((path, result)) = self.commit_msg_rx.try_recv() {
+            if let ViewMode::Diff(ref mut diff) = self.view_mode
+                && diff.worktree_path == path
+                && let Some(ref mut input) = diff.commit_message
+                && input.generating
+            {
+                input.generating = false;
+                match result {
+                    Ok(message) => input.text = message,
+                    Err(e) => input.error = Some(e),
+                }
+            }
+        }
+    }
+
+    /// Show or hide the PR panel. Overviews are refetched each time it opens.
+    pub fn toggle_pr_panel(&mut self) {
+        self.pr_panel = match self.pr_panel {
+            Some(_) => None,
+            None => Some(PrPanel::default()),
+        };
+        self.poll_pr_panel();
+    }
+
+    /// Take in finished PR panel fetches and action results, and fetch the
+    /// selected agent's PR when the panel has none or it is getting old
+    pub fn poll_pr_panel(&mut self) {
+        while let Ok(update) = self.pr_panel_rx.try_recv() {
+            let Some(ref mut panel) = self.pr_panel else {
+                continue;
+            };
+            match update {
+                PrPanelUpdate::Overview(path, overview) => {
+                    panel.overviews.insert(path, overview);
+                }
+                PrPanelUpdate::Notice(path, notice) => {
+                    panel.notice = Some(notice);
+                    // Refetch to show rerun checks and the new draft state
+                    panel.requested.remove(&path);
+                }
+            }
+        }
+
+        let Some(ref panel) = self.pr_panel else {
+            return;
+        };
+        let Some((agent, branch)) = self.selected_branch() else {
+            return;
+        };
+        if panel
+            .requested
+            .get(&agent.path)
+            .is_some_and(|requested| requested.elapsed() < PR_FETCH_INTERVAL)
+        {
+            return;
+        }
+        if self
+            .is_pr_panel_fetching
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return;
+        }
+
+        let path = agent.path.clone();
+        let number = self.get_pr_for_agent(agent).map(|pr| pr.number);
+        let tx = self.pr_panel_tx.clone();
+        let is_fetching = self.is_pr_panel_fetching.clone();
+        let config = self.config.clone();
+        if let Some(ref mut panel) = self.pr_panel {
+            panel
+                .requested
+                .insert(path.clone(), std::time::Instant::now());
+        }
+
+        std::thread::spawn(move || {
+            struct ResetFlag(Arc<AtomicBool>);
+            impl Drop for ResetFlag {
+                fn drop(&mut self) {
+                    self.0.store(false, Ordering::SeqCst);
+                }
+            }
+            let _reset = ResetFlag(is_fetching);
+
+            let forge = crate::forge::for_repo(Some(&path), &config);
+            // PRs opened since the last list fetch aren't known yet
+            let number = match number {
+                Some(number) => Ok(number),
+                None => forge.pr_status(&path, &branch).and_then(|pr| {
+                    pr.map(|pr| pr.number).ok_or_else(|| {
+                        anyhow::anyhow!("No {} for branch '{}'", forge.request_label(), branch)
+                    })
+                }),
+            };
+            let overview = number
+                .and_then(|number| forge.pr_overview(&path, number))
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(PrPanelUpdate::Overview(path, overview));
+        });
+    }
+
+    /// The PR overview shown for the selected agent, once fetched
+    pub fn selected_pr_overview(&self) -> Option<&crate::forge::PrOverview> {
+        let agent = self
+            .table_state
+            .selected()
+            .and_then(|selected| self.agents.get(selected))?;
+        self.pr_panel
+            .as_ref()?
+            .overviews
+            .get(&agent.path)?
+            .as_ref()
+            .ok()
+    }
+
+    /// Open the selected agent's PR in the browser
+    pub fn open_selected_pr(&mut self) {
+        let Some(url) = self.selected_pr_overview().map(|pr| pr.url.clone()) else {
+            return;
+        };
+        let notice = match open_in_browser(&url) {
+            Ok(()) => format!("Opened {}", url),
+            Err(e) => format!("{:#}", e),
+        };
+        if let Some(ref mut panel) = self.pr_panel {
+            panel.notice = Some(notice);
+        }
+    }
+
+    /// Re-run the failed checks on the selected agent's branch in the background
+    pub fn rerun_selected_pr_checks(&mut self) {
+        let Some((agent, branch)) = self.selected_branch() else {
+            return;
+        };
+        let path = agent.path.clone();
+        let tx = self.pr_panel_tx.clone();
+        let config = self.config.clone();
+        if let Some(ref mut panel) = self.pr_panel {
+            panel.notice = Some("Re-running failed checks...".to_string());
+        }
+
+        std::thread::spawn(move || {
+            let forge = crate::forge::for_repo(Some(&path), &config);
+            let notice = match forge.rerun_failed_checks(&path, &branch) {
+                Ok(0) => "No failed checks to re-run".to_string(),
+                Ok(1) => "Re-running 1 failed run".to_string(),
+                Ok(count) => format!("Re-running {} failed runs", count),
+                Err(e) => format!("{:#}", e),
+            };
+            let _ = tx.send(PrPanelUpdate::Notice(path, notice));
+        });
+    }
+
+    /// Take the selected agent's draft PR out of draft in the background
+    pub fn mark_selected_pr_ready(&mut self) {
+        let Some((number, is_draft)) = self
+            .selected_pr_overview()
+            .map(|pr| (pr.number, pr.is_draft))
+        else {
+            return;
+        };
+        let Some((agent, _)) = self.selected_branch() else {
+            return;
+        };
+        let path = agent.path.clone();
+        if !is_draft {
+            if let Some(ref mut panel) = self.pr_panel {
+                panel.notice = Some(format!("#{} is already ready for review", number));
+            }
+            return;
+        }
+        let tx = self.pr_panel_tx.clone();
+        let config = self.config.clone();
+        if let Some(ref mut panel) = self.pr_panel {
+            panel.notice = Some(format!("Marking #{} ready for review...", number));
+        }
+
+        std::thread::spawn(move || {
+            let forge = crate::forge::for_repo(Some(&path), &config);
+            let notice = match forge.mark_ready(&path, number) {
+                Ok(()) => format!("Marked #{} ready for review", number),
+                Err(e) => format!("{:#}", e),
+            };
+            let _ = tx.send(PrPanelUpdate::Notice(path, notice));
+        });
+    }
+
+    /// The selected agent and its branch, unless it is on main/master
+    fn selected_branch(&self) -> Option<(&AgentPane, String)> {
+        let agent = self
+            .table_state
+            .selected()
+            .and_then(|selected| self.agents.get(selected))?;
+        let branch = self.git_statuses.get(&agent.path)?.branch.clone()?;
+        if branch == "main" || branch == "master" {
+            return None;
+        }
+        Some((agent, branch))
+    }
+
     /// Update the preview for the currently selected agent.
     /// Only fetches if the selection has changed or preview is stale.
     pub fn update_preview(&mut self) {
@@ -732,6 +1025,11 @@ impl App {
         self.is_pr_fetching.load(Ordering::Relaxed)
     }
 
+    /// Whether a PR panel fetch is currently in progress
+    pub fn is_pr_panel_fetching(&self) -> bool {
+        self.is_pr_panel_fetching.load(Ordering::Relaxed)
+    }
+
     /// Whether any agent has a matching PR (for column visibility)
     pub fn has_any_pr(&self) -> bool {
         self.agents
@@ -227,10 +227,23 @@ pub struct DiffView {
     pub staged_hunks: Vec<DiffHunk>,
     /// Comment input buffer (Some = comment mode active)
     pub comment_input: Option<String>,
+    /// Commit message editor (Some = commit message popup open)
+    pub commit_message: Option<CommitMessageInput>,
     /// List of files in the diff for the sidebar
     pub file_list: Vec<FileEntry>,
 }
 
+/// Commit message being generated or edited in the diff view
+#[derive(Debug, Default, PartialEq)]
+pub struct CommitMessageInput {
+    /// The message text (LLM suggestion, then user edits)
+    pub text: String,
+    /// Whether the LLM is still generating a suggestion
+    pub generating: bool,
+    /// Last generation or commit error, shown in the popup
+    pub error: Option<String>,
+}
+
 impl DiffView {
     pub fn scroll_up(&mut self) {
         self.scroll = self.scroll.saturating_sub(1);
@@ -8,11 +8,14 @@
 
 use std::io::Write;
 
+use crate::git;
+use crate::workflow::feedback;
+
 use super::ansi::parse_ansi_to_lines;
 use super::app::{App, ViewMode};
 use super::diff::{
-    DiffView, extract_file_list, get_diff_content, get_file_list_numstat, map_file_offsets,
-    parse_hunk_header,
+    CommitMessageInput, DiffView, extract_file_list, get_diff_content, get_file_list_numstat,
+    map_file_offsets, parse_hunk_header,
 };
 
 /// Extension trait for diff and patch mode operations on App.
@@ -33,6 +36,8 @@ pub trait DiffOps {
     fn trigger_merge(&mut self);
     fn send_commit_to_selected(&mut self);
     fn trigger_merge_for_selected(&mut self);
+    fn start_commit_message(&mut self);
+    fn confirm_commit_message(&mut self);
 }
 
 /// Reload diff showing only unstaged changes (for patch mode).
@@ -84,6 +89,7 @@ fn reload_unstaged_diff(app: &mut App) {
                 hunks_processed: 0,
                 staged_hunks: Vec::new(),
                 comment_input: None,
+                commit_message: None,
                 file_list,
             }));
         }
@@ -108,6 +114,7 @@ fn reload_unstaged_diff(app: &mut App) {
                 hunks_processed: 0,
                 staged_hunks: Vec::new(),
                 comment_input: None,
+                commit_message: None,
                 file_list: Vec::new(),
             }));
         }
@@ -341,11 +348,7 @@ impl DiffOps for App {
             .map(|(_, new_start)| new_start)
             .unwrap_or(1);
 
-        // Determine safe code fence (use more backticks if content contains ```)
-        let mut fence = "```".to_string();
-        while hunk.hunk_body.contains(&fence) {
-            fence.push('`');
-        }
+        let fence = feedback::code_fence(&hunk.hunk_body);
 
         // Format the message with file path, line number, hunk content, and comment
         let message = format!(
@@ -464,6 +467,7 @@ impl DiffOps for App {
                     hunks_processed: 0,
                     staged_hunks: Vec::new(),
                     comment_input: None,
+                    commit_message: None,
                     file_list,
                 }));
             }
@@ -489,6 +493,7 @@ impl DiffOps for App {
                     hunks_processed: 0,
                     staged_hunks: Vec::new(),
                     comment_input: None,
+                    commit_message: None,
                     file_list: Vec::new(),
                 }));
             }
@@ -549,4 +554,47 @@ impl DiffOps for App {
             );
         }
     }
+
+    /// Open the commit message popup and generate a suggestion for the staged changes
+    fn start_commit_message(&mut self) {
+        let ViewMode::Diff(ref mut diff) = self.view_mode else {
+            return;
+        };
+
+        let path = diff.worktree_path.clone();
+        if !git::has_staged_changes(&path).unwrap_or(false) {
+            diff.commit_message = Some(CommitMessageInput {
+                error: Some("Nothing staged. Stage hunks in patch mode [a] first.".to_string()),
+                ..Default::default()
+            });
+            return;
+        }
+
+        diff.commit_message = Some(CommitMessageInput {
+            generating: true,
+            ..Default::default()
+        });
+        self.spawn_commit_message_generation(path);
+    }
+
+    /// Commit the staged changes with the edited message and reload the diff
+    fn confirm_commit_message(&mut self) {
+        let ViewMode::Diff(ref mut diff) = self.view_mode else {
+            return;
+        };
+        let Some(ref mut input) = diff.commit_message else {
+            return;
+        };
+        if input.generating || input.text.trim().is_empty() {
+            return;
+        }
+
+        match git::commit_with_message(&diff.worktree_path, input.text.trim()) {
+            Ok(()) => {
+                let is_branch_diff = diff.is_branch_diff;
+                self.load_diff(is_branch_diff);
+            }
+            Err(e) => input.error = Some(format!("{:#}", e)),
+        }
+    }
 }
@@ -9,9 +9,11 @@ use super::actions::Action;
 pub enum Context {
     DashboardNormal,
     DashboardInput,
+    PrPanel,
     DiffNormal,
     Patch,
     Comment,
+    CommitMessage,
 }
 
 /// Map a key event to an action for the given context.
@@ -19,9 +21,11 @@ pub fn action_for_key(ctx: Context, key: KeyEvent) -> Option<Action> {
     match ctx {
         Context::DashboardNormal => dashboard_normal_key(key),
         Context::DashboardInput => dashboard_input_key(key),
+        Context::PrPanel => pr_panel_key(key),
         Context::DiffNormal => diff_normal_key(key),
         Context::Patch => patch_key(key),
         Context::Comment => comment_key(key),
+        Context::CommitMessage => commit_message_key(key),
     }
 }
 
@@ -49,6 +53,8 @@ fn dashboard_normal_key(key: KeyEvent) -> Option<Action> {
         KeyCode::Char('d') => Some(Action::LoadWipDiff),
         KeyCode::Char('c') => Some(Action::SendCommitDashboard),
         KeyCode::Char('m') => Some(Action::TriggerMergeDashboard),
+        KeyCode::Char('C') => Some(Action::SendCiFeedback),
+        KeyCode::Char('P') => Some(Action::TogglePrPanel),
         KeyCode::Char(c @ '1'..='9') => Some(Action::JumpToIndex((c as u8 - b'1') as usize)),
         _ => None,
     }
@@ -69,6 +75,17 @@ fn dashboard_input_key(key: KeyEvent) -> Option<Action> {
     }
 }
 
+/// The dashboard keys, plus actions on the selected agent's PR
+fn pr_panel_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('P') | KeyCode::Esc => Some(Action::TogglePrPanel),
+        KeyCode::Char('o') => Some(Action::OpenPr),
+        KeyCode::Char('r') => Some(Action::RerunFailedChecks),
+        KeyCode::Char('R') => Some(Action::MarkPrReady),
+        _ => dashboard_normal_key(key),
+    }
+}
+
 fn diff_normal_key(key: KeyEvent) -> Option<Action> {
     match key.code {
         KeyCode::Char('?') => Some(Action::ShowHelp),
@@ -87,6 +104,7 @@ fn diff_normal_key(key: KeyEvent) -> Option<Action> {
         KeyCode::Tab => Some(Action::ToggleDiffType),
         KeyCode::Char('a') => Some(Action::EnterPatchMode),
         KeyCode::Char('c') => Some(Action::SendCommitDiff),
+        KeyCode::Char('g') => Some(Action::GenerateCommitMessage),
         KeyCode::Char('m') => Some(Action::TriggerMergeDiff),
         _ => None,
     }
@@ -110,6 +128,7 @@ fn patch_key(key: KeyEvent) -> Option<Action> {
         KeyCode::Char('k') | KeyCode::Up => Some(Action::PrevHunk),
         KeyCode::Char('j') | KeyCode::Down => Some(Action::NextHunk),
         KeyCode::Char('c') => Some(Action::SendCommitDiff),
+        KeyCode::Char('g') => Some(Action::GenerateCommitMessage),
         KeyCode::Char('m') => Some(Action::TriggerMergeDiff),
         KeyCode::Esc | KeyCode::Char('q') => Some(Action::ExitPatchMode),
         _ => None,
@@ -126,6 +145,20 @@ fn comment_key(key: KeyEvent) -> Option<Action> {
     }
 }
 
+fn commit_message_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::CancelCommitMessage),
+        KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => Some(Action::InsertNewline),
+        KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Action::InsertNewline)
+        }
+        KeyCode::Enter => Some(Action::ConfirmCommitMessage),
+        KeyCode::Backspace => Some(Action::DeleteChar),
+        KeyCode::Char(c) => Some(Action::AppendChar(c)),
+        _ => None,
+    }
+}
+
 /// Get help rows for a context: (key, description) pairs.
 pub fn help_rows(ctx: Context) -> Vec<(&'static str, &'static str)> {
     match ctx {
@@ -144,9 +177,22 @@ pub fn help_rows(ctx: Context) -> Vec<(&'static str, &'static str)> {
             ("d", "View diff"),
             ("c", "Commit changes"),
             ("m", "Merge branch"),
+            ("C", "Send CI failures to agent"),
+            ("P", "Show PR panel"),
             ("1-9", "Quick jump"),
         ],
         Context::DashboardInput => vec![("Esc", "Exit input mode"), ("<keys>", "Send to agent")],
+        Context::PrPanel => vec![
+            ("?", "Show help"),
+            ("P/Esc", "Close PR panel"),
+            ("j/k", "Navigate up/down"),
+            ("Enter", "Jump to agent"),
+            ("o", "Open PR in browser"),
+            ("r", "Re-run failed checks"),
+            ("R", "Mark ready for review"),
+            ("C", "Send CI failures to agent"),
+            ("q", "Quit"),
+        ],
         Context::DiffNormal => vec![
             ("?", "Show help"),
             ("q/Esc", "Close diff"),
@@ -155,6 +201,7 @@ pub fn help_rows(ctx: Context) -> Vec<(&'static str, &'static str)> {
             ("Tab", "Toggle WIP/Review"),
             ("a", "Enter patch mode (WIP only)"),
             ("c", "Commit changes"),
+            ("g", "Generate commit message"),
             ("m", "Merge branch"),
         ],
         Context::Patch => vec![
@@ -167,6 +214,7 @@ pub fn help_rows(ctx: Context) -> Vec<(&'static str, &'static str)> {
             ("j/k", "Next/prev hunk"),
             ("Ctrl+d/u", "Scroll hunk"),
             ("c", "Commit changes"),
+            ("g", "Generate commit message"),
             ("m", "Merge branch"),
             ("q/Esc", "Exit patch mode"),
         ],
@@ -175,6 +223,12 @@ pub fn help_rows(ctx: Context) -> Vec<(&'static str, &'static str)> {
             ("Enter", "Send comment"),
             ("<type>", "Input text"),
         ],
+        Context::CommitMessage => vec![
+            ("Esc", "Cancel"),
+            ("Enter", "Commit staged changes"),
+            ("Alt+Enter", "New line"),
+            ("<type>", "Edit message"),
+        ],
     }
 }
 
@@ -186,9 +240,11 @@ mod tests {
     fn test_each_context_has_help_rows() {
         assert!(!help_rows(Context::DashboardNormal).is_empty());
         assert!(!help_rows(Context::DashboardInput).is_empty());
+        assert!(!help_rows(Context::PrPanel).is_empty());
         assert!(!help_rows(Context::DiffNormal).is_empty());
         assert!(!help_rows(Context::Patch).is_empty());
         assert!(!help_rows(Context::Comment).is_empty());
+        assert!(!help_rows(Context::CommitMessage).is_empty());
     }
 
     #[test]
@@ -196,9 +252,11 @@ mod tests {
         for ctx in [
             Context::DashboardNormal,
             Context::DashboardInput,
+            Context::PrPanel,
             Context::DiffNormal,
             Context::Patch,
             Context::Comment,
+            Context::CommitMessage,
         ] {
             let rows = help_rows(ctx);
             let keys: Vec<_> = rows.iter().map(|(k, _)| *k).collect();
@@ -232,6 +290,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pr_panel_keys() {
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        let r = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE);
+        let j = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+
+        assert_eq!(
+            action_for_key(Context::PrPanel, esc),
+            Some(Action::TogglePrPanel)
+        );
+        assert_eq!(
+            action_for_key(Context::PrPanel, r),
+            Some(Action::RerunFailedChecks)
+        );
+        assert_eq!(action_for_key(Context::DashboardNormal, r), None);
+        // Navigation keeps working with the panel open
+        assert_eq!(action_for_key(Context::PrPanel, j), Some(Action::Next));
+    }
+
     #[test]
     fn test_diff_close_keys() {
         let q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
@@ -255,4 +332,28 @@ mod tests {
             Some(Action::StageAndNext)
         );
     }
+
+    #[test]
+    fn test_commit_message_keys() {
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let alt_enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT);
+        let g = KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE);
+
+        assert_eq!(
+            action_for_key(Context::CommitMessage, enter),
+            Some(Action::ConfirmCommitMessage)
+        );
+        assert_eq!(
+            action_for_key(Context::CommitMessage, alt_enter),
+            Some(Action::InsertNewline)
+        );
+        assert_eq!(
+            action_for_key(Context::CommitMessage, g),
+            Some(Action::AppendChar('g'))
+        );
+        assert_eq!(
+            action_for_key(Context::DiffNormal, g),
+            Some(Action::GenerateCommitMessage)
+        );
+    }
 }
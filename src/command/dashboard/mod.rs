@@ -15,6 +15,7 @@
 //! - `diff`: Diff domain types and helper functions
 //! - `keymap`: Key-to-action mapping per context with help text
 //! - `monitor`: Agent stall detection and interrupt handling
+//! - `pr_panel`: PR panel state and browser opening
 //! - `settings`: Tmux-persisted dashboard settings
 //! - `sort`: Sort mode enum and tmux persistence
 //! - `spinner`: Spinner animation constants
@@ -23,6 +24,7 @@
 //!   - `diff`: Normal diff, patch mode, file list
 //!   - `format`: Git status formatting
 //!   - `help`: Help overlay
+//!   - `pr_panel`: PR details shown in place of the preview
 
 mod actions;
 mod agent;
@@ -32,6 +34,7 @@ mod diff;
 mod diff_ops;
 mod keymap;
 mod monitor;
+mod pr_panel;
 mod settings;
 mod sort;
 mod spinner;
@@ -64,12 +67,16 @@ fn get_context(app: &App) -> Context {
         ViewMode::Dashboard => {
             if app.input_mode {
                 Context::DashboardInput
+            } else if app.pr_panel.is_some() {
+                Context::PrPanel
             } else {
                 Context::DashboardNormal
             }
         }
         ViewMode::Diff(diff) => {
-            if diff.patch_mode {
+            if diff.commit_message.is_some() {
+                Context::CommitMessage
+            } else if diff.patch_mode {
                 if diff.comment_input.is_some() {
                     Context::Comment
                 } else {
@@ -209,6 +216,10 @@ pub fn run(cli_preview_size: Option<u8>, open_diff: bool) -> Result<()> {
             }
         }
 
+        // Pick up a generated commit message as soon as it is ready
+        app.poll_commit_message();
+        app.poll_pr_panel();
+
         if last_tick.elapsed() >= tick_rate {
             last_tick = std::time::Instant::now();
             // Advance spinner animation frame (wrap at frame count to avoid skip artifact)
@@ -0,0 +1,47 @@
+//! PR panel types: the selected agent's PR with its reviews and checks.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::Instant;
+
+use crate::forge::PrOverview;
+
+/// Results sent back by the PR panel's background threads
+pub enum PrPanelUpdate {
+    /// A fetched overview, or why it couldn't be fetched, for a worktree
+    Overview(PathBuf, Result<PrOverview, String>),
+    /// The outcome of an action on a worktree's PR, which is then refetched
+    Notice(PathBuf, String),
+}
+
+/// State of the PR panel, shown in place of the preview while open
+#[derive(Debug, Default)]
+pub struct PrPanel {
+    /// Last fetched overview per worktree path, or the fetch error
+    pub overviews: HashMap<PathBuf, Result<PrOverview, String>>,
+    /// When each worktree's overview was last requested
+    pub requested: HashMap<PathBuf, Instant>,
+    /// Outcome of the last action, shown at the bottom of the panel
+    pub notice: Option<String>,
+}
+
+/// Open a URL in the default browser without blocking the dashboard
+pub fn open_in_browser(url: &str) -> Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    let mut child = Command::new(opener)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", opener))?;
+    // Reap the opener once it exits
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
@@ -1,4 +1,4 @@
-//! Dashboard view rendering (table, preview, footer).
+//! Dashboard view rendering (table, preview or PR panel, footer).
 
 use ansi_to_tui::IntoText;
 use ratatui::{
@@ -13,6 +13,7 @@ use std::collections::{BTreeMap, HashSet};
 use super::super::app::App;
 use super::super::spinner::SPINNER_FRAMES;
 use super::format::{format_git_status, format_pr_status};
+use super::pr_panel::render_pr_panel;
 
 /// Render the dashboard view (table + preview + footer).
 pub fn render_dashboard(f: &mut Frame, app: &mut App) {
@@ -31,8 +32,12 @@ pub fn render_dashboard(f: &mut Frame, app: &mut App) {
     // Table
     render_table(f, app, chunks[0]);
 
-    // Preview
-    render_preview(f, app, chunks[1]);
+    // Preview, or the PR panel in its place (input mode always shows the preview)
+    if app.pr_panel.is_some() && !app.input_mode {
+        render_pr_panel(f, app, chunks[1]);
+    } else {
+        render_preview(f, app, chunks[1]);
+    }
 
     // Footer - show different help based on mode
     let footer_text = if app.input_mode {
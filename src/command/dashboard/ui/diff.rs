@@ -5,10 +5,10 @@ use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, List, ListItem, Paragraph},
+    widgets::{Block, BorderType, Clear, List, ListItem, Paragraph, Wrap},
 };
 
-use super::super::diff::DiffView;
+use super::super::diff::{CommitMessageInput, DiffView};
 
 /// Render the diff view (replaces the entire dashboard).
 pub fn render_diff_view(f: &mut Frame, diff: &mut DiffView) {
@@ -58,6 +58,69 @@ pub fn render_diff_view(f: &mut Frame, diff: &mut DiffView) {
             render_file_list(f, diff, file_area);
         }
     }
+
+    if let Some(ref input) = diff.commit_message {
+        render_commit_message(f, input, area);
+    }
+}
+
+/// Render the commit message popup (LLM suggestion the user can edit before committing).
+fn render_commit_message(f: &mut Frame, input: &CommitMessageInput, area: Rect) {
+    let mut lines: Vec<Line> = Vec::new();
+    if let Some(ref error) = input.error {
+        lines.push(Line::styled(error.clone(), Style::default().fg(Color::Red)));
+        lines.push(Line::raw(""));
+    }
+
+    if input.generating {
+        lines.push(Line::styled(
+            "Generating commit message...",
+            Style::default().fg(Color::DarkGray),
+        ));
+    } else {
+        let mut text_lines: Vec<&str> = input.text.split('\n').collect();
+        let last = text_lines.pop().unwrap_or_default();
+        lines.extend(text_lines.into_iter().map(|l| Line::raw(l.to_string())));
+        // Cursor sits at the end of the last line
+        lines.push(Line::from(vec![
+            Span::raw(last.to_string()),
+            Span::styled("|", Style::default().fg(Color::White)),
+        ]));
+    }
+
+    let width = area.width.saturating_sub(4).min(80);
+    let height = (lines.len() as u16 + 4).clamp(8, area.height);
+    let popup_area = Rect {
+        x: area.width.saturating_sub(width) / 2,
+        y: area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Green))
+        .title(Span::styled(
+            " Commit message ",
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Line::from(vec![
+            Span::styled(" [Enter]", Style::default().fg(Color::Green)),
+            Span::raw(" commit  "),
+            Span::styled("[Alt+Enter]", Style::default().fg(Color::Cyan)),
+            Span::raw(" new line  "),
+            Span::styled("[Esc]", Style::default().fg(Color::Red)),
+            Span::raw(" cancel "),
+        ]));
+
+    let paragraph = Paragraph::new(Text::from(lines))
+        .block(block)
+        .wrap(Wrap { trim: false });
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
 }
 
 /// Determine which file is currently visible based on scroll position or current hunk.
@@ -303,6 +366,8 @@ fn render_normal_diff(f: &mut Frame, diff: &DiffView, content_area: Rect, footer
         Span::raw(" scroll  "),
         Span::styled("[c]", Style::default().fg(Color::Green)),
         Span::raw(" commit  "),
+        Span::styled("[g]", Style::default().fg(Color::Green)),
+        Span::raw(" commit msg  "),
         Span::styled("[m]", Style::default().fg(Color::Yellow)),
         Span::raw(" merge  "),
         Span::styled("[q]", Style::default().fg(Color::Cyan)),
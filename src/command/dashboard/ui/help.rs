@@ -17,12 +17,16 @@ fn get_help_context(app: &App) -> Context {
         ViewMode::Dashboard => {
             if app.input_mode {
                 Context::DashboardInput
+            } else if app.pr_panel.is_some() {
+                Context::PrPanel
             } else {
                 Context::DashboardNormal
             }
         }
         ViewMode::Diff(diff) => {
-            if diff.patch_mode {
+            if diff.commit_message.is_some() {
+                Context::CommitMessage
+            } else if diff.patch_mode {
                 if diff.comment_input.is_some() {
                     Context::Comment
                 } else {
@@ -40,9 +44,11 @@ fn context_title(ctx: Context) -> &'static str {
     match ctx {
         Context::DashboardNormal => "Dashboard",
         Context::DashboardInput => "Input Mode",
+        Context::PrPanel => "PR Panel",
         Context::DiffNormal => "Diff View",
         Context::Patch => "Patch Mode",
         Context::Comment => "Comment",
+        Context::CommitMessage => "Commit Message",
     }
 }
 
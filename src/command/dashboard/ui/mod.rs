@@ -4,6 +4,7 @@ mod dashboard;
 mod diff;
 mod format;
 mod help;
+mod pr_panel;
 
 use ratatui::Frame;
 
@@ -0,0 +1,164 @@
+//! PR panel rendering (title, reviews, checks, mergeability).
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::forge::{CheckStatus, PrOverview, ReviewDecision};
+use crate::nerdfont;
+
+use super::super::agent::format_short_duration;
+use super::super::app::App;
+use super::super::spinner::SPINNER_FRAMES;
+
+/// Render the selected agent's PR in place of the preview.
+pub fn render_pr_panel(f: &mut Frame, app: &App, area: Rect) {
+    let selected_agent = app
+        .table_state
+        .selected()
+        .and_then(|idx| app.agents.get(idx));
+    let panel = app.pr_panel.as_ref();
+    let fetched = selected_agent
+        .zip(panel)
+        .and_then(|(agent, panel)| panel.overviews.get(&agent.path));
+
+    let title = match fetched {
+        Some(Ok(pr)) => format!(" PR #{}: {} ", pr.number, pr.title),
+        _ => " PR ".to_string(),
+    };
+    let block = Block::bordered()
+        .title(title)
+        .title_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title_bottom(Line::from(vec![
+            Span::styled(" [o]", Style::default().fg(Color::Cyan)),
+            Span::raw(" open  "),
+            Span::styled("[r]", Style::default().fg(Color::Yellow)),
+            Span::raw(" rerun failed  "),
+            Span::styled("[R]", Style::default().fg(Color::Green)),
+            Span::raw(" ready  "),
+            Span::styled("[P]", Style::default().fg(Color::Cyan)),
+            Span::raw(" close "),
+        ]));
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = match (selected_agent, fetched) {
+        (None, _) => vec![Line::styled("(no agent selected)", dim)],
+        (Some(_), Some(Ok(pr))) => overview_lines(pr),
+        (Some(_), Some(Err(e))) => vec![Line::styled(e.clone(), dim)],
+        (Some(_), None) if app.is_pr_panel_fetching() => {
+            let spinner = SPINNER_FRAMES[app.spinner_frame as usize % SPINNER_FRAMES.len()];
+            vec![Line::styled(format!("{} Loading PR...", spinner), dim)]
+        }
+        (Some(_), None) => vec![Line::styled("(no PR for this branch)", dim)],
+    };
+
+    if let Some(notice) = panel.and_then(|panel| panel.notice.as_ref()) {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            notice.clone(),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn overview_lines(pr: &PrOverview) -> Vec<Line<'static>> {
+    let pr_icons = nerdfont::pr_icons();
+    let (icon, state, color) = if pr.is_draft {
+        (pr_icons.draft, "Draft", Color::DarkGray)
+    } else {
+        match pr.state.as_str() {
+            "OPEN" => (pr_icons.open, "Open", Color::Green),
+            "MERGED" => (pr_icons.merged, "Merged", Color::Magenta),
+            _ => (pr_icons.closed, "Closed", Color::Red),
+        }
+    };
+    let separator = Span::styled("  ·  ", Style::default().fg(Color::DarkGray));
+
+    let mut status = vec![Span::styled(
+        format!("{} {}", icon, state),
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    )];
+    if let Some(decision) = pr.review_decision {
+        let (label, color) = match decision {
+            ReviewDecision::Approved => ("Approved", Color::Green),
+            ReviewDecision::ChangesRequested => ("Changes requested", Color::Red),
+            ReviewDecision::ReviewRequired => ("Review required", Color::Yellow),
+        };
+        status.push(separator.clone());
+        status.push(Span::styled(label, Style::default().fg(color)));
+    }
+    if pr.state == "OPEN" {
+        let (label, color) = match pr.mergeable {
+            Some(true) => ("No conflicts", Color::Green),
+            Some(false) => ("Has conflicts", Color::Red),
+            None => ("Checking mergeability", Color::DarkGray),
+        };
+        status.push(separator);
+        status.push(Span::styled(label, Style::default().fg(color)));
+    }
+
+    let mut lines = vec![Line::from(status)];
+    if !pr.requested_reviewers.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("Waiting on ", Style::default().fg(Color::DarkGray)),
+            Span::raw(pr.requested_reviewers.join(", ")),
+        ]));
+    }
+    lines.push(Line::raw(""));
+
+    if pr.checks.is_empty() {
+        lines.push(Line::styled(
+            "No checks",
+            Style::default().fg(Color::DarkGray),
+        ));
+        return lines;
+    }
+
+    let passed = pr
+        .checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Success)
+        .count();
+    lines.push(Line::styled(
+        format!("Checks ({}/{} passed)", passed, pr.checks.len()),
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+
+    let check_icons = nerdfont::check_icons();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let name_width = pr
+        .checks
+        .iter()
+        .map(|check| check.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for check in &pr.checks {
+        let (icon, color) = match check.status {
+            CheckStatus::Success => (check_icons.success, Color::Green),
+            CheckStatus::Failure => (check_icons.failure, Color::Red),
+            CheckStatus::Pending => (check_icons.pending, Color::Yellow),
+            CheckStatus::Skipped => ("-", Color::DarkGray),
+        };
+        let duration = check
+            .duration(now)
+            .map(format_short_duration)
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {} ", icon), Style::default().fg(color)),
+            Span::raw(format!("{:<width$}  ", check.name, width = name_width)),
+            Span::styled(duration, Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines
+}
@@ -0,0 +1,137 @@
+use crate::multiplexer::{create_backend, detect_backend};
+use crate::workflow::du::{WorktreeUsage, disk_usage, format_size, remove_ignored_dir};
+use crate::{config, spinner};
+use anyhow::{Context, Result};
+use std::io::{self, Write};
+use tabled::{
+    Table, Tabled,
+    settings::{Alignment, Padding, Style, object::Columns},
+};
+
+#[derive(Tabled)]
+struct UsageRow {
+    #[tabled(rename = "BRANCH")]
+    branch: String,
+    #[tabled(rename = "TRACKED")]
+    tracked: String,
+    #[tabled(rename = "IGNORED")]
+    ignored: String,
+    #[tabled(rename = "COPIED")]
+    copied: String,
+    #[tabled(rename = "TOTAL")]
+    total: String,
+    #[tabled(rename = "MUX")]
+    mux_status: String,
+}
+
+pub fn run(clean: bool, include_open: bool, force: bool) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let mux = create_backend(detect_backend());
+
+    let mut worktrees =
+        spinner::with_spinner("Measuring worktrees", || disk_usage(&config, mux.as_ref()))?;
+
+    if worktrees.is_empty() {
+        println!("No worktrees found");
+        return Ok(());
+    }
+
+    worktrees.sort_by_key(|wt| std::cmp::Reverse(wt.usage.total()));
+    print_table(&worktrees);
+
+    let cleanable: Vec<&WorktreeUsage> = worktrees
+        .iter()
+        .filter(|wt| !wt.is_main && (include_open || !wt.has_mux_window))
+        .filter(|wt| !wt.ignored_dirs.is_empty())
+        .collect();
+    let reclaimable: u64 = cleanable.iter().map(|wt| wt.reclaimable()).sum();
+
+    if cleanable.is_empty() {
+        return Ok(());
+    }
+
+    if !clean {
+        println!(
+            "\n{} reclaimable from build directories (du.clean) in {} worktree(s){}. Run `workmux du --clean` to delete them.",
+            format_size(reclaimable),
+            cleanable.len(),
+            if include_open {
+                ""
+            } else {
+                " without a window"
+            }
+        );
+        return Ok(());
+    }
+
+    println!("\nThe following ignored directories will be deleted:");
+    for wt in &cleanable {
+        for dir in &wt.ignored_dirs {
+            println!(
+                "  - {}/{} ({})",
+                wt.handle,
+                dir.rel_path.display(),
+                format_size(dir.size)
+            );
+        }
+    }
+
+    if !force {
+        print!("\nDelete and reclaim {}? [y/N] ", format_size(reclaimable));
+        io::stdout().flush().context("Failed to flush stdout")?;
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read user input")?;
+
+        if input.trim().to_lowercase() != "y" {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let mut reclaimed = 0;
+    for wt in cleanable {
+        for dir in &wt.ignored_dirs {
+            match remove_ignored_dir(&wt.path, dir) {
+                Ok(()) => reclaimed += dir.size,
+                Err(e) => eprintln!(
+                    "Failed to delete {}/{}: {}",
+                    wt.handle,
+                    dir.rel_path.display(),
+                    e
+                ),
+            }
+        }
+    }
+
+    println!("\n✓ Reclaimed {}", format_size(reclaimed));
+    Ok(())
+}
+
+fn print_table(worktrees: &[WorktreeUsage]) {
+    let rows: Vec<UsageRow> = worktrees
+        .iter()
+        .map(|wt| UsageRow {
+            branch: wt.branch.clone(),
+            tracked: format_size(wt.usage.tracked),
+            ignored: format_size(wt.usage.ignored),
+            copied: format_size(wt.usage.copied),
+            total: format_size(wt.usage.total()),
+            mux_status: if wt.is_main || wt.has_mux_window {
+                "✓".to_string()
+            } else {
+                "closed".to_string()
+            },
+        })
+        .collect();
+
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(0..6), Padding::new(0, 1, 0, 0))
+        .modify(Columns::new(1..5), Alignment::right());
+
+    println!("{table}");
+}
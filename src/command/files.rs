@@ -0,0 +1,95 @@
+use crate::workflow::files_sync::{self, FileChange, FileDiff, SyncDirection};
+use crate::{config, git};
+use anyhow::{Context, Result, anyhow};
+use std::io::{self, Write};
+use std::path::Path;
+
+pub fn run(
+    direction: SyncDirection,
+    name: &str,
+    paths: &[String],
+    dry_run: bool,
+    show_diff: bool,
+    force: bool,
+) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let patterns: Vec<String> = config
+        .files
+        .copy
+        .iter()
+        .flatten()
+        .map(|entry| entry.pattern().to_string())
+        .collect();
+    if patterns.is_empty() {
+        return Err(anyhow!(
+            "No 'files.copy' patterns configured in .workmux.yaml"
+        ));
+    }
+
+    let (worktree_root, _branch) = git::find_worktree(name).with_context(|| {
+        format!(
+            "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+            name
+        )
+    })?;
+    let main_root = git::get_main_worktree_root()?;
+    if worktree_root == main_root {
+        return Err(anyhow!("'{}' is the main worktree", name));
+    }
+    let (source, dest) = direction.roots(&main_root, &worktree_root);
+
+    let diffs: Vec<FileDiff> = files_sync::diff(source, dest, &patterns)?
+        .into_iter()
+        .filter(|d| paths.is_empty() || paths.iter().any(|p| d.rel_path.starts_with(p)))
+        .collect();
+
+    if diffs.is_empty() {
+        println!("Everything is up to date.");
+        return Ok(());
+    }
+
+    let (from_label, to_label) = match direction {
+        SyncDirection::Pull => (name, "main worktree"),
+        SyncDirection::Push => ("main worktree", name),
+    };
+    println!("Changes from {} to {}:", from_label, to_label);
+    for d in &diffs {
+        println!("  {} {}", d.change.marker(), d.rel_path.display());
+        if show_diff && d.change == FileChange::Modified {
+            print_diff(&dest.join(&d.rel_path), &source.join(&d.rel_path));
+        }
+    }
+
+    if dry_run {
+        println!("\nDry run: {} file(s) would be copied.", diffs.len());
+        return Ok(());
+    }
+
+    if !force {
+        print!("\nCopy {} file(s) to {}? [y/N] ", diffs.len(), to_label);
+        io::stdout().flush().context("Failed to flush stdout")?;
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read user input")?;
+
+        if input.trim().to_lowercase() != "y" {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    files_sync::apply(source, dest, &diffs)?;
+    println!("✓ Copied {} file(s) to {}", diffs.len(), to_label);
+    Ok(())
+}
+
+/// Show a unified diff via `git diff --no-index`. Best-effort: failures are ignored.
+fn print_diff(old: &Path, new: &Path) {
+    let _ = std::process::Command::new("git")
+        .args(["--no-pager", "diff", "--no-index", "--"])
+        .arg(old)
+        .arg(new)
+        .status();
+}
@@ -0,0 +1,235 @@
+use crate::multiplexer::{create_backend, detect_backend};
+use crate::workflow::WorkflowContext;
+use crate::workflow::gc::{GcCandidate, GcPolicy, find_candidates};
+use crate::{config, git, spinner, workflow};
+use anyhow::{Context, Result, anyhow};
+use pathdiff::diff_paths;
+use std::io::{self, Write};
+use tabled::{
+    Table, Tabled,
+    settings::{Padding, Style, object::Columns},
+};
+
+#[derive(Tabled)]
+struct GcRow {
+    #[tabled(rename = "BRANCH")]
+    branch: String,
+    #[tabled(rename = "REASON")]
+    reason: String,
+    #[tabled(rename = "ACTION")]
+    action: String,
+    #[tabled(rename = "PATH")]
+    path: String,
+}
+
+/// Policy flags from the command line. When none are given, the `gc` config block is used.
+pub struct GcArgs {
+    pub older_than: Option<u64>,
+    pub merged: bool,
+    pub gone: bool,
+    pub pr_closed: bool,
+}
+
+impl GcArgs {
+    fn to_policy(&self) -> GcPolicy {
+        GcPolicy {
+            older_than_days: self.older_than,
+            merged: self.merged,
+            gone: self.gone,
+            pr_closed: self.pr_closed,
+        }
+    }
+}
+
+pub fn run(
+    args: GcArgs,
+    include_open: bool,
+    dry_run: bool,
+    force: bool,
+    keep_branch: bool,
+) -> Result<()> {
+    let config = config::Config::load(None)?;
+
+    let mut policy = args.to_policy();
+    if policy.is_empty()
+        && let Some(gc) = &config.gc
+    {
+        policy = GcPolicy::from_config(gc);
+    }
+    if policy.is_empty() {
+        return Err(anyhow!(
+            "No gc policy given. Pass --older-than, --merged, --gone or --pr-closed, \
+            or configure a 'gc' block in .workmux.yaml."
+        ));
+    }
+
+    if policy.gone
+        && let Err(e) = spinner::with_spinner("Fetching from remote", git::fetch_prune)
+    {
+        eprintln!("Warning: failed to fetch from remote: {}", e);
+    }
+
+    let mux = create_backend(detect_backend());
+    let candidates = spinner::with_spinner("Scanning worktrees", || {
+        find_candidates(&config, mux.as_ref(), &policy, include_open)
+    })?;
+
+    if candidates.is_empty() {
+        println!("No stale worktrees found.");
+        return Ok(());
+    }
+
+    print_table(&candidates)?;
+
+    let to_remove: Vec<&GcCandidate> = candidates.iter().filter(|c| c.skip.is_none()).collect();
+
+    if to_remove.is_empty() {
+        println!("\nNo removable worktrees found.");
+        return Ok(());
+    }
+
+    if dry_run {
+        println!(
+            "\nDry run: {} worktree(s) would be removed.",
+            to_remove.len()
+        );
+        return Ok(());
+    }
+
+    if !force {
+        print!("\nRemove {} worktree(s)? [y/N] ", to_remove.len());
+        io::stdout().flush().context("Failed to flush stdout")?;
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read user input")?;
+
+        if input.trim().to_lowercase() != "y" {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let context = WorkflowContext::new(config, mux, None)?;
+    let mut success_count = 0;
+    let mut failed: Vec<(String, String)> = Vec::new();
+
+    for candidate in to_remove {
+        // Confirmed by the user (or --force), so unmerged branches are deleted too
+        match remove_candidate(candidate, true, keep_branch, &context) {
+            Ok(()) => success_count += 1,
+            Err(e) => failed.push((candidate.branch.clone(), e.to_string())),
+        }
+    }
+
+    if success_count > 0 {
+        println!("\n✓ Removed {} stale worktree(s)", success_count);
+    }
+
+    if !failed.is_empty() {
+        eprintln!("\nFailed to remove {} worktree(s):", failed.len());
+        for (branch, error) in &failed {
+            eprintln!("  - {}: {}", branch, error);
+        }
+        return Err(anyhow!("Some worktrees could not be removed"));
+    }
+
+    Ok(())
+}
+
+/// Run gc with the configured policies before `workmux add`, if enabled.
+///
+/// Never prompts and never fails the caller. Only worktrees whose branch is fully
+/// merged and that have no uncommitted changes or open window are removed, and
+/// errors are reported as warnings.
+pub fn run_on_add(config: &config::Config) {
+    let Some(gc) = config.gc.as_ref().filter(|gc| gc.on_add()) else {
+        return;
+    };
+    let policy = GcPolicy::from_config(gc);
+    if policy.is_empty() {
+        return;
+    }
+
+    if let Err(e) = collect_without_prompt(config, &policy) {
+        eprintln!("Warning: gc failed: {}", e);
+    }
+}
+
+fn collect_without_prompt(config: &config::Config, policy: &GcPolicy) -> Result<()> {
+    let mux = create_backend(detect_backend());
+    let candidates = find_candidates(config, mux.as_ref(), policy, false)?;
+    let to_remove: Vec<&GcCandidate> = candidates
+        .iter()
+        .filter(|c| c.removable_without_prompt())
+        .collect();
+    if to_remove.is_empty() {
+        return Ok(());
+    }
+
+    let context = WorkflowContext::new(config.clone(), mux, None)?;
+    for candidate in to_remove {
+        if let Err(e) = remove_candidate(candidate, false, false, &context) {
+            eprintln!("Warning: gc could not remove '{}': {}", candidate.handle, e);
+        }
+    }
+    Ok(())
+}
+
+/// Remove a candidate's worktree. `force` deletes unmerged branches and must only be
+/// set once the user has confirmed the removal.
+fn remove_candidate(
+    candidate: &GcCandidate,
+    force: bool,
+    keep_branch: bool,
+    context: &WorkflowContext,
+) -> Result<()> {
+    super::announce_hooks(&context.config, None, super::HookPhase::PreRemove);
+
+    workflow::remove(&candidate.handle, force, keep_branch, context)
+        .context("Failed to remove worktree")?;
+
+    println!(
+        "✓ Removed worktree '{}' ({})",
+        candidate.handle,
+        format_reasons(candidate)
+    );
+    Ok(())
+}
+
+fn format_reasons(candidate: &GcCandidate) -> String {
+    candidate
+        .reasons
+        .iter()
+        .map(|r| r.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn print_table(candidates: &[GcCandidate]) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+
+    let rows: Vec<GcRow> = candidates
+        .iter()
+        .map(|c| GcRow {
+            branch: c.branch.clone(),
+            reason: format_reasons(c),
+            action: match c.skip {
+                Some(skip) => format!("skip: {}", skip),
+                None => "remove".to_string(),
+            },
+            path: diff_paths(&c.path, &current_dir)
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| c.path.display().to_string()),
+        })
+        .collect();
+
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(0..4), Padding::new(0, 1, 0, 0));
+
+    println!("{table}");
+    Ok(())
+}
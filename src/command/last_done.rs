@@ -37,6 +37,7 @@ pub fn run() -> Result<()> {
     }
 
     // Sort by timestamp descending (most recent first)
+    #[allow(clippy::unnecessary_sort_by)]
     done_agents.sort_by(|a, b| b.status_ts.cmp(&a.status_ts));
 
     // Get current pane to determine where we are in the cycle
@@ -1,8 +1,19 @@
 use crate::config::MergeStrategy;
+use crate::forge::MergeMethod;
 use crate::multiplexer::{create_backend, detect_backend};
 use crate::workflow::WorkflowContext;
-use crate::{config, workflow};
+use crate::workflow::types::PrMergeOptions;
+use crate::{config, git, workflow};
 use anyhow::{Context, Result};
+use std::time::Duration;
+
+/// `--via-pr`, `--wait`, `--auto` and `--timeout`
+pub struct PrMergeFlags {
+    pub via_pr: bool,
+    pub wait: bool,
+    pub auto: bool,
+    pub timeout: u64,
+}
 
 #[allow(clippy::too_many_arguments)]
 pub fn run(
@@ -11,11 +22,20 @@ pub fn run(
     ignore_uncommitted: bool,
     mut rebase: bool,
     mut squash: bool,
+    generate_message: bool,
     keep: bool,
     no_verify: bool,
     notification: bool,
+    pr_flags: PrMergeFlags,
 ) -> Result<()> {
-    let config = config::Config::load(None)?;
+    // Resolve name from argument or current directory
+    // Note: Must be done BEFORE creating WorkflowContext (which may change CWD)
+    let name_to_merge = super::resolve_name(name)?;
+
+    let mut config = config::Config::load(None)?;
+    if let Ok((_, branch)) = git::find_worktree(&name_to_merge) {
+        config = config.apply_branch_overrides(&branch, None)?;
+    }
 
     // Apply default strategy from config if no CLI flags are provided
     if !rebase
@@ -25,13 +45,25 @@ pub fn run(
         match strategy {
             MergeStrategy::Rebase => rebase = true,
             MergeStrategy::Squash => squash = true,
-            MergeStrategy::Merge => {}
+            MergeStrategy::Merge | MergeStrategy::Pr => {}
         }
     }
 
-    // Resolve name from argument or current directory
-    // Note: Must be done BEFORE creating WorkflowContext (which may change CWD)
-    let name_to_merge = super::resolve_name(name)?;
+    let via_pr = pr_flags.via_pr || config.merge_strategy == Some(MergeStrategy::Pr);
+    if via_pr {
+        if into_branch.is_some() {
+            anyhow::bail!("--into doesn't apply to PR merges; the PR's base branch is used");
+        }
+        if generate_message {
+            anyhow::bail!("--generate-message doesn't apply to PR merges");
+        }
+    } else if pr_flags.wait || pr_flags.auto {
+        anyhow::bail!("--wait and --auto only apply to PR merges (use --via-pr)");
+    }
+
+    if generate_message && !squash {
+        anyhow::bail!("--generate-message only applies to squash merges (use --squash)");
+    }
 
     let mux = create_backend(detect_backend());
     let context = WorkflowContext::new(config, mux, None)?;
@@ -46,17 +78,42 @@ pub fn run(
         super::announce_hooks(&context.config, None, super::HookPhase::PreRemove);
     }
 
-    let result = workflow::merge(
-        &name_to_merge,
-        into_branch,
-        ignore_uncommitted,
-        rebase,
-        squash,
-        keep,
-        no_verify,
-        notification,
-        &context,
-    )
+    let result = if via_pr {
+        let options = PrMergeOptions {
+            method: if rebase {
+                MergeMethod::Rebase
+            } else if squash {
+                MergeMethod::Squash
+            } else {
+                MergeMethod::Merge
+            },
+            wait: pr_flags.wait,
+            auto: pr_flags.auto,
+            timeout: Duration::from_secs(pr_flags.timeout),
+        };
+        workflow::merge_via_pr(
+            &name_to_merge,
+            ignore_uncommitted,
+            &options,
+            keep,
+            no_verify,
+            notification,
+            &context,
+        )
+    } else {
+        workflow::merge(
+            &name_to_merge,
+            into_branch,
+            ignore_uncommitted,
+            rebase,
+            squash,
+            generate_message,
+            keep,
+            no_verify,
+            notification,
+            &context,
+        )
+    }
     .context("Failed to merge worktree")?;
 
     if result.had_staged_changes {
@@ -1,16 +1,24 @@
 pub mod add;
 pub mod args;
 pub mod changelog;
+pub mod ci_feedback;
 pub mod close;
+pub mod config;
 pub mod dashboard;
 pub mod docs;
+pub mod du;
+pub mod files;
+pub mod gc;
 pub mod last_agent;
 pub mod last_done;
 pub mod list;
 pub mod merge;
 pub mod open;
 pub mod path;
+pub mod pr;
+pub mod prompts;
 pub mod remove;
+pub mod review_feedback;
 pub mod set_base;
 pub mod set_window_status;
 
@@ -20,15 +28,27 @@ use crate::{config::Config, workflow::SetupOptions};
 
 /// Represents the different phases where hooks can be executed
 pub enum HookPhase {
+    PreCreate,
     PostCreate,
     PreMerge,
     PreRemove,
+    OnOpen,
+    OnClose,
 }
 
 /// Announce that hooks are about to run, if applicable.
 /// Returns true if the announcement was printed (hooks will run).
 pub fn announce_hooks(config: &Config, options: Option<&SetupOptions>, phase: HookPhase) -> bool {
     match phase {
+        HookPhase::PreCreate => {
+            let should_run = options.is_some_and(|opts| opts.run_hooks)
+                && config.pre_create.as_ref().is_some_and(|v| !v.is_empty());
+
+            if should_run {
+                println!("Running pre-create commands...");
+            }
+            should_run
+        }
         HookPhase::PostCreate => {
             let should_run = options.is_some_and(|opts| opts.run_hooks)
                 && config.post_create.as_ref().is_some_and(|v| !v.is_empty());
@@ -54,6 +74,22 @@ pub fn announce_hooks(config: &Config, options: Option<&SetupOptions>, phase: Ho
             }
             should_run
         }
+        HookPhase::OnOpen => {
+            let should_run = config.on_open.as_ref().is_some_and(|v| !v.is_empty());
+
+            if should_run {
+                println!("Running on-open commands...");
+            }
+            should_run
+        }
+        HookPhase::OnClose => {
+            let should_run = config.on_close.as_ref().is_some_and(|v| !v.is_empty());
+
+            if should_run {
+                println!("Running on-close commands...");
+            }
+            should_run
+        }
     }
 }
 
@@ -2,7 +2,7 @@ use crate::command::args::PromptArgs;
 use crate::multiplexer::{create_backend, detect_backend};
 use crate::workflow::prompt_loader::{PromptLoadArgs, load_prompt};
 use crate::workflow::{SetupOptions, WorkflowContext};
-use crate::{config, workflow};
+use crate::{config, git, workflow};
 use anyhow::{Context, Result, bail};
 
 pub fn run(
@@ -21,7 +21,13 @@ pub fn run(
         (None, false) => bail!("Worktree name is required unless --new is provided"),
     };
 
-    let (config, config_location) = config::Config::load_with_location(None)?;
+    let (mut config, config_location) = config::Config::load_with_location(None)?;
+    let worktree = git::find_worktree(&resolved_name).ok();
+    // Apply branch overrides and re-apply the profile the worktree was created with
+    if let Some((_, branch)) = &worktree {
+        let profile = git::get_branch_profile(branch);
+        config = config.for_branch(Some(branch), profile.as_deref(), None)?;
+    }
     let mux = create_backend(detect_backend());
     let context = WorkflowContext::new(config, mux, config_location)?;
 
@@ -52,8 +58,20 @@ pub fn run(
     let mut options = SetupOptions::new(run_hooks, force_files, true);
     options.prompt_file_path = prompt_file_path;
 
-    // Only announce hooks if we're forcing a new window (otherwise we might just switch)
-    if new_window {
+    // Only announce hooks if a window will be created (otherwise we just switch).
+    // on_open hooks run on every such open; --run-hooks only affects post_create.
+    let creates_window = new_window
+        || worktree
+            .as_ref()
+            .and_then(|(path, _)| path.file_name())
+            .is_none_or(|handle| {
+                !context
+                    .mux
+                    .window_exists(&context.prefix, &handle.to_string_lossy())
+                    .unwrap_or(false)
+            });
+    if creates_window {
+        super::announce_hooks(&context.config, None, super::HookPhase::OnOpen);
         super::announce_hooks(
             &context.config,
             Some(&options),
@@ -0,0 +1,232 @@
+use anyhow::{Context, Result, anyhow};
+use edit::Builder;
+use serde_json::json;
+use std::fs;
+
+use crate::config::PrConfig;
+use crate::forge::{self, NewPr};
+use crate::github::{self, PrSummary};
+use crate::template::create_template_env;
+use crate::{config, git, llm, spinner, workflow};
+
+/// The only commit's subject, or the branch name.
+const DEFAULT_TITLE_TEMPLATE: &str =
+    "{% if commits | length == 1 %}{{ commits[0] }}{% else %}{{ branch }}{% endif %}";
+
+const DEFAULT_BODY_TEMPLATE: &str = r#"{% if prompt %}## Task
+
+{{ prompt | trim }}
+
+{% endif %}## Changes
+
+{% for commit in commits %}- {{ commit }}
+{% endfor %}
+{{ lines_added }} lines added, {{ lines_removed }} lines removed
+"#;
+
+/// A PR title and body before it is submitted.
+#[derive(Debug, PartialEq, Eq)]
+struct Description {
+    title: String,
+    body: String,
+}
+
+/// Push a worktree's branch and open a pull request for it against its base branch.
+pub fn run_create(name: Option<&str>, generate: bool, draft: bool) -> Result<()> {
+    let name = super::resolve_name(name)?;
+    let (worktree_path, branch) = git::find_worktree(&name)
+        .with_context(|| format!("No worktree found with name '{}'", name))?;
+    let config = config::Config::load(None)?.apply_branch_overrides(&branch, None)?;
+
+    let base = match git::get_branch_base_in(&branch, Some(&worktree_path)) {
+        Ok(base) => base,
+        Err(_) => match &config.main_branch {
+            Some(main) => main.clone(),
+            None => git::get_default_branch_in(Some(&worktree_path))?,
+        },
+    };
+    let target = remote_branch_name(&base);
+
+    let commits = git::get_commit_subjects(&worktree_path, &base)?;
+    if commits.is_empty() {
+        return Err(anyhow!(
+            "Branch '{}' has no commits ahead of '{}'",
+            branch,
+            base
+        ));
+    }
+
+    let draft_description = if generate {
+        let diff = git::get_branch_diff(&worktree_path, &base)?;
+        let log = commits
+            .iter()
+            .map(|subject| format!("- {}", subject))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let auto_name = config.auto_name.clone().unwrap_or_default();
+        let generated = spinner::with_spinner("Generating PR description", || {
+            llm::generate_pr_description(&log, &diff, &auto_name)
+        })?;
+        Description {
+            title: generated.title,
+            body: generated.body,
+        }
+    } else {
+        let status = git::get_git_status(&worktree_path);
+        let prompt = fs::read_to_string(workflow::prompt_file_path(&branch)).ok();
+        let context = json!({
+            "branch": branch,
+            "base": target,
+            "commits": commits,
+            "prompt": prompt,
+            "lines_added": status.lines_added,
+            "lines_removed": status.lines_removed,
+        });
+        render_description(&config.pr.clone().unwrap_or_default(), &context)?
+    };
+
+    let description = edit_description(&draft_description)?;
+
+    println!("Pushing '{}'...", branch);
+    git::push_branch(&worktree_path, "origin", &branch)?;
+
+    let labels = git::get_branch_labels_in(&branch, &worktree_path);
+    let forge = forge::for_repo(Some(&worktree_path), &config);
+    let created = forge.create_pr(
+        &worktree_path,
+        &NewPr {
+            head: &branch,
+            base: target,
+            title: &description.title,
+            body: &description.body,
+            draft,
+            labels: &labels,
+        },
+    )?;
+
+    // Record the PR so `list --pr` and the dashboard show it before the
+    // forge's PR list catches up
+    git::set_branch_pr(&worktree_path, &branch, created.number)?;
+    github::cache_pr(
+        &worktree_path,
+        &branch,
+        PrSummary {
+            title: description.title.clone(),
+            is_draft: draft,
+            ..PrSummary::recorded(created.number)
+        },
+    );
+
+    println!(
+        "✓ Created {} #{} into '{}': {}",
+        forge.request_label(),
+        created.number,
+        target,
+        created.url
+    );
+    Ok(())
+}
+
+/// Render the configured (or default) title and body templates.
+fn render_description(config: &PrConfig, context: &serde_json::Value) -> Result<Description> {
+    let env = create_template_env();
+    let title_template = config.title.as_deref().unwrap_or(DEFAULT_TITLE_TEMPLATE);
+    let body_template = config.body.as_deref().unwrap_or(DEFAULT_BODY_TEMPLATE);
+    let title = env
+        .render_str(title_template, context)
+        .context("Failed to render pr.title template")?;
+    let body = env
+        .render_str(body_template, context)
+        .context("Failed to render pr.body template")?;
+    Ok(Description {
+        title: title.trim().to_string(),
+        body: body.trim().to_string(),
+    })
+}
+
+/// Let the user review the title (first line) and body in their editor.
+fn edit_description(draft: &Description) -> Result<Description> {
+    let mut builder = Builder::new();
+    builder.suffix(".md");
+    let initial = format!("{}\n\n{}\n", draft.title, draft.body);
+    let edited = edit::edit_with_builder(initial, &builder)
+        .context("Failed to open editor or read content")?;
+    parse_description(&edited).ok_or_else(|| anyhow!("Aborting: PR title is empty"))
+}
+
+fn parse_description(text: &str) -> Option<Description> {
+    let text = text.trim();
+    let (title, body) = text.split_once('\n').unwrap_or((text, ""));
+    let title = title.trim();
+    (!title.is_empty()).then(|| Description {
+        title: title.to_string(),
+        body: body.trim().to_string(),
+    })
+}
+
+/// The branch name to target on the forge: a stored base like "origin/main" becomes "main".
+fn remote_branch_name(base: &str) -> &str {
+    base.strip_prefix("origin/").unwrap_or(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(commits: &[&str], prompt: Option<&str>) -> serde_json::Value {
+        json!({
+            "branch": "auth",
+            "base": "main",
+            "commits": commits,
+            "prompt": prompt,
+            "lines_added": 120,
+            "lines_removed": 8,
+        })
+    }
+
+    #[test]
+    fn default_templates_use_single_commit_subject_as_title() {
+        let single =
+            render_description(&PrConfig::default(), &context(&["Fix login"], None)).unwrap();
+        assert_eq!(single.title, "Fix login");
+        assert_eq!(
+            single.body,
+            "## Changes\n\n- Fix login\n\n120 lines added, 8 lines removed"
+        );
+
+        let several = render_description(
+            &PrConfig::default(),
+            &context(
+                &["Add OAuth provider", "Add tests"],
+                Some("Add OAuth login\n"),
+            ),
+        )
+        .unwrap();
+        assert_eq!(several.title, "auth");
+        assert_eq!(
+            several.body,
+            "## Task\n\nAdd OAuth login\n\n## Changes\n\n- Add OAuth provider\n- Add tests\n\n120 lines added, 8 lines removed"
+        );
+    }
+
+    #[test]
+    fn configured_templates_override_defaults() {
+        let config = PrConfig {
+            title: Some("[{{ base }}] {{ commits | last }}".to_string()),
+            body: Some("+{{ lines_added }}/-{{ lines_removed }}".to_string()),
+        };
+        let description = render_description(&config, &context(&["One", "Two"], None)).unwrap();
+        assert_eq!(description.title, "[main] Two");
+        assert_eq!(description.body, "+120/-8");
+    }
+
+    #[test]
+    fn parse_description_splits_first_line_from_body() {
+        let parsed = parse_description("\nAdd OAuth\n\nBody line\n").unwrap();
+        assert_eq!(parsed.title, "Add OAuth");
+        assert_eq!(parsed.body, "Body line");
+        assert!(parse_description("  \n ").is_none());
+        assert_eq!(remote_branch_name("origin/main"), "main");
+        assert_eq!(remote_branch_name("develop"), "develop");
+    }
+}
@@ -0,0 +1,56 @@
+use anyhow::Result;
+use tabled::{
+    Table, Tabled,
+    settings::{Padding, Style, object::Columns},
+};
+
+use crate::prompt::Prompt;
+use crate::prompt_library::{self, PROJECT_PROMPTS_DIR};
+
+#[derive(Tabled)]
+struct PromptRow {
+    #[tabled(rename = "NAME")]
+    name: String,
+    #[tabled(rename = "SCOPE")]
+    scope: &'static str,
+    #[tabled(rename = "DESCRIPTION")]
+    description: String,
+}
+
+/// List the prompts in the prompt library.
+pub fn run_list() -> Result<()> {
+    let prompts = prompt_library::list();
+    if prompts.is_empty() {
+        println!(
+            "No prompts found. Add Markdown files to {}/ or ~/.config/workmux/prompts/",
+            PROJECT_PROMPTS_DIR
+        );
+        return Ok(());
+    }
+
+    let rows: Vec<PromptRow> = prompts
+        .iter()
+        .map(|prompt| PromptRow {
+            name: prompt.name.clone(),
+            scope: prompt.scope.label(),
+            description: prompt.description(),
+        })
+        .collect();
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(0..3), Padding::new(0, 1, 0, 0));
+    println!("{table}");
+    Ok(())
+}
+
+/// Print a prompt from the prompt library as written.
+pub fn run_show(name: &str) -> Result<()> {
+    let prompt = prompt_library::find(name)?;
+    let content = Prompt::FromFile(prompt.path).read_content()?;
+    print!("{}", content);
+    if !content.ends_with('\n') {
+        println!();
+    }
+    Ok(())
+}
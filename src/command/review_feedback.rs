@@ -0,0 +1,71 @@
+use anyhow::{Context, Result};
+use std::fs;
+
+use crate::multiplexer::{create_backend, detect_backend};
+use crate::workflow::{self, feedback};
+use crate::{config, forge, git, spinner};
+
+/// Send the unresolved review threads on a worktree's PR to the agent running
+/// in it, or write them to the worktree's prompt file when no agent is running
+pub fn run(name: Option<&str>, print: bool) -> Result<()> {
+    let name = super::resolve_name(name)?;
+    let (worktree_path, branch) = git::find_worktree(&name)
+        .with_context(|| format!("No worktree found with name '{}'", name))?;
+    let config = config::Config::load(None)?.apply_branch_overrides(&branch, None)?;
+
+    let forge = forge::for_repo(Some(&worktree_path), &config);
+    let label = forge.request_label();
+    let (number, threads) = spinner::with_spinner("Fetching review comments", || {
+        let found = match git::get_repo_owner() {
+            Ok(owner) => forge.find_pr_by_head_ref(&owner, &branch)?,
+            Err(_) => None,
+        };
+        // Nested GitLab groups don't match the owner parsed from origin; fall
+        // back to the latest PR from the branch
+        let pr = match found {
+            Some(pr) => pr,
+            None => forge
+                .pr_status(&worktree_path, &branch)?
+                .with_context(|| format!("No {} found for branch '{}'", label, branch))?,
+        };
+        Ok((pr.number, forge.review_threads(&worktree_path, pr.number)?))
+    })?;
+    let request = format!("{} #{}", label, number);
+    let Some(prompt) = feedback::review_prompt(&request, &threads) else {
+        println!("No unresolved review comments on {}", request);
+        return Ok(());
+    };
+
+    if print {
+        print!("{}", prompt);
+        return Ok(());
+    }
+
+    let mux = create_backend(detect_backend());
+    if let Some(pane_id) = feedback::find_agent_pane(mux.as_ref(), &worktree_path)? {
+        feedback::send_to_agent(mux.as_ref(), &pane_id, &prompt)?;
+        println!(
+            "✓ Sent {} review thread(s) from {} to the agent in '{}'",
+            threads.len(),
+            request,
+            name
+        );
+        return Ok(());
+    }
+
+    let prompt_path = workflow::prompt_file_path(&branch);
+    fs::write(&prompt_path, &prompt)
+        .with_context(|| format!("Failed to write prompt file '{}'", prompt_path.display()))?;
+    println!(
+        "No agent is running in '{}'. Wrote {} review thread(s) to {}",
+        name,
+        threads.len(),
+        prompt_path.display()
+    );
+    println!(
+        "  Start an agent with it: workmux open {} --new --prompt-file {}",
+        name,
+        prompt_path.display()
+    );
+    Ok(())
+}
@@ -1,4 +1,6 @@
+use anyhow::Context;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -17,11 +19,87 @@ const NODE_MODULES_CLEANUP_SCRIPT: &str = include_str!("scripts/cleanup_node_mod
 pub struct FileConfig {
     /// Glob patterns for files to copy from the repo root to the new worktree
     #[serde(default)]
-    pub copy: Option<Vec<String>>,
+    pub copy: Option<Vec<CopyEntry>>,
 
     /// Glob patterns for files to symlink from the repo root into the new worktree
     #[serde(default)]
     pub symlink: Option<Vec<String>>,
+
+    /// Files rendered through minijinja from the repo root into the new worktree
+    #[serde(default)]
+    pub template: Option<Vec<TemplateFile>>,
+}
+
+/// A `files.copy` entry: either a bare glob or a glob with an explicit copy mode
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum CopyEntry {
+    Pattern(String),
+    WithMode {
+        path: String,
+        #[serde(default)]
+        mode: CopyMode,
+    },
+}
+
+impl CopyEntry {
+    pub fn pattern(&self) -> &str {
+        match self {
+            CopyEntry::Pattern(p) => p,
+            CopyEntry::WithMode { path, .. } => path,
+        }
+    }
+
+    pub fn mode(&self) -> CopyMode {
+        match self {
+            CopyEntry::Pattern(_) => CopyMode::default(),
+            CopyEntry::WithMode { mode, .. } => *mode,
+        }
+    }
+}
+
+/// How `files.copy` materialises files in the new worktree
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CopyMode {
+    /// Copy-on-write clone where the filesystem supports it, otherwise a regular copy
+    #[default]
+    Reflink,
+    /// Hard link to the source file (edits are shared), otherwise a regular copy
+    Hardlink,
+    /// Regular byte-for-byte copy
+    Copy,
+    /// Only copy files whose size or mtime differ from the destination
+    Incremental,
+}
+
+impl std::fmt::Display for CopyMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            CopyMode::Reflink => "reflink",
+            CopyMode::Hardlink => "hardlink",
+            CopyMode::Copy => "copy",
+            CopyMode::Incremental => "incremental",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A file rendered with per-worktree variables (handle, branch, ports, ...)
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TemplateFile {
+    /// Template path, relative to the repo root
+    pub source: String,
+
+    /// Output path, relative to the worktree. Defaults to `source`.
+    #[serde(default)]
+    pub dest: Option<String>,
+}
+
+impl TemplateFile {
+    pub fn dest(&self) -> &str {
+        self.dest.as_deref().unwrap_or(&self.source)
+    }
 }
 
 /// Configuration for agent status icons displayed in tmux window bar
@@ -33,6 +111,8 @@ pub struct StatusIcons {
     pub waiting: Option<String>,
     /// Icon shown when agent is done. Default: ✅
     pub done: Option<String>,
+    /// Icon shown while background setup hooks run. Default: ⏳
+    pub setup: Option<String>,
 }
 
 impl StatusIcons {
@@ -47,9 +127,13 @@ impl StatusIcons {
     pub fn done(&self) -> &str {
         self.done.as_deref().unwrap_or("✅")
     }
+
+    pub fn setup(&self) -> &str {
+        self.setup.as_deref().unwrap_or("⏳")
+    }
 }
 
-/// Configuration for LLM-based branch name generation
+/// Configuration for LLM-generated text: branch names, commit messages and PR descriptions
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
 pub struct AutoNameConfig {
     /// Model to use with llm CLI (e.g., "gpt-4o-mini", "claude-3-5-sonnet").
@@ -63,6 +147,53 @@ pub struct AutoNameConfig {
     /// Whether to always run in background mode when using --auto-name.
     /// If true, the window will be created but not focused.
     pub background: Option<bool>,
+
+    /// Which backend generates the name: the `llm` CLI (default) or an
+    /// OpenAI-compatible HTTP endpoint.
+    pub provider: Option<LlmProvider>,
+
+    /// Base URL of the OpenAI-compatible API (openai provider only).
+    /// Default: https://api.openai.com/v1
+    pub base_url: Option<String>,
+
+    /// Environment variable holding the API key (openai provider only).
+    /// Default: OPENAI_API_KEY. No key is sent if the variable is unset.
+    pub api_key_env: Option<String>,
+
+    /// Custom system prompt for commit message generation.
+    /// The staged diff is sent as the user input.
+    pub commit_prompt: Option<String>,
+
+    /// Custom system prompt for PR title/body generation.
+    /// The reply's first line is used as the title, the rest as the body.
+    pub pr_prompt: Option<String>,
+}
+
+impl AutoNameConfig {
+    pub fn provider(&self) -> LlmProvider {
+        self.provider.unwrap_or_default()
+    }
+
+    pub fn base_url(&self) -> &str {
+        self.base_url
+            .as_deref()
+            .unwrap_or("https://api.openai.com/v1")
+    }
+
+    pub fn api_key_env(&self) -> &str {
+        self.api_key_env.as_deref().unwrap_or("OPENAI_API_KEY")
+    }
+}
+
+/// Backend used for LLM-generated text
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmProvider {
+    /// The `llm` CLI (https://llm.datasette.io)
+    #[default]
+    Llm,
+    /// Any OpenAI-compatible `/chat/completions` endpoint (OpenAI, Ollama, llama.cpp, ...)
+    Openai,
 }
 
 /// Configuration for dashboard actions (commit, merge keybindings)
@@ -109,6 +240,180 @@ impl DashboardConfig {
     }
 }
 
+/// Configuration for `workmux gc` stale worktree policies
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct GcConfig {
+    /// Collect worktrees whose last commit is older than this many days
+    pub older_than_days: Option<u64>,
+
+    /// Collect worktrees whose branch is fully merged into the main branch
+    pub merged: Option<bool>,
+
+    /// Collect worktrees whose upstream remote branch has been deleted
+    pub gone: Option<bool>,
+
+    /// Collect worktrees whose pull request is closed or merged (requires gh CLI)
+    pub pr_closed: Option<bool>,
+
+    /// Run gc automatically (without prompting) before `workmux add`.
+    /// Default: false
+    pub on_add: Option<bool>,
+}
+
+impl GcConfig {
+    pub fn merged(&self) -> bool {
+        self.merged.unwrap_or(false)
+    }
+
+    pub fn gone(&self) -> bool {
+        self.gone.unwrap_or(false)
+    }
+
+    pub fn pr_closed(&self) -> bool {
+        self.pr_closed.unwrap_or(false)
+    }
+
+    pub fn on_add(&self) -> bool {
+        self.on_add.unwrap_or(false)
+    }
+}
+
+/// Build output directories removed by `workmux du --clean` when `du.clean` is unset
+pub const DEFAULT_DU_CLEAN: &[&str] = &[
+    "target",
+    "node_modules",
+    "dist",
+    "build",
+    ".next",
+    "__pycache__",
+];
+
+/// Configuration for `workmux du`
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct DuConfig {
+    /// Ignored directories that `workmux du --clean` may delete. A name without
+    /// `/` matches a directory of that name at any depth; other entries are
+    /// globs matched against the path from the worktree root.
+    pub clean: Option<Vec<String>>,
+}
+
+impl DuConfig {
+    pub fn clean(&self) -> Vec<String> {
+        match &self.clean {
+            Some(dirs) => dirs.clone(),
+            None => DEFAULT_DU_CLEAN.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+}
+
+/// Configuration for per-worktree port allocation
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct PortsConfig {
+    /// Number of ports to reserve for each worktree
+    pub count: Option<u16>,
+
+    /// First port of the allocation range (inclusive). Default: 4000
+    pub range_start: Option<u16>,
+
+    /// Last port of the allocation range (inclusive). Default: 4999
+    pub range_end: Option<u16>,
+}
+
+impl PortsConfig {
+    pub fn count(&self) -> u16 {
+        self.count.unwrap_or(0)
+    }
+
+    pub fn range(&self) -> std::ops::RangeInclusive<u16> {
+        self.range_start.unwrap_or(4000)..=self.range_end.unwrap_or(4999)
+    }
+}
+
+/// A lifecycle hook: a plain shell command, or a command with execution options
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum HookEntry {
+    Command(String),
+    Detailed(HookSpec),
+}
+
+/// A hook command with execution options
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct HookSpec {
+    /// Shell command to run
+    pub command: String,
+
+    /// Display name used in output and log file names. Defaults to the command.
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Kill the hook after this many seconds
+    #[serde(default)]
+    pub timeout: Option<u64>,
+
+    /// Consecutive hooks with the same group name run concurrently
+    #[serde(default)]
+    pub parallel: Option<String>,
+
+    /// Keep going if this hook fails
+    #[serde(default)]
+    pub continue_on_error: bool,
+
+    /// Extra environment variables for this hook
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+
+    /// Shell condition; the hook only runs if it exits successfully
+    #[serde(default)]
+    pub when: Option<String>,
+
+    /// post_create only: run after the window opens instead of blocking it.
+    /// Pane commands wait until all background hooks have succeeded.
+    #[serde(default)]
+    pub background: bool,
+}
+
+impl HookEntry {
+    pub fn to_spec(&self) -> HookSpec {
+        match self {
+            HookEntry::Command(command) => HookSpec {
+                command: command.clone(),
+                ..Default::default()
+            },
+            HookEntry::Detailed(spec) => spec.clone(),
+        }
+    }
+}
+
+impl From<&str> for HookEntry {
+    fn from(command: &str) -> Self {
+        HookEntry::Command(command.to_string())
+    }
+}
+
+/// List items that can be the `"<global>"` placeholder in a project config.
+trait GlobalPlaceholder: Clone {
+    fn is_global_placeholder(&self) -> bool;
+}
+
+impl GlobalPlaceholder for String {
+    fn is_global_placeholder(&self) -> bool {
+        self == "<global>"
+    }
+}
+
+impl GlobalPlaceholder for HookEntry {
+    fn is_global_placeholder(&self) -> bool {
+        matches!(self, HookEntry::Command(c) if c == "<global>")
+    }
+}
+
+impl GlobalPlaceholder for CopyEntry {
+    fn is_global_placeholder(&self) -> bool {
+        matches!(self, CopyEntry::Pattern(p) if p == "<global>")
+    }
+}
+
 /// Configuration for the workmux tool, read from .workmux.yaml
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
 pub struct Config {
@@ -129,17 +434,37 @@ pub struct Config {
     #[serde(default)]
     pub panes: Option<Vec<PaneConfig>>,
 
+    /// Commands to run before creating the worktree (e.g., validating the branch name)
+    #[serde(default)]
+    pub pre_create: Option<Vec<HookEntry>>,
+
     /// Commands to run after creating the worktree
     #[serde(default)]
-    pub post_create: Option<Vec<String>>,
+    pub post_create: Option<Vec<HookEntry>>,
 
     /// Commands to run before merging (e.g., linting, tests)
     #[serde(default)]
-    pub pre_merge: Option<Vec<String>>,
+    pub pre_merge: Option<Vec<HookEntry>>,
+
+    /// Commands to run after a successful merge (e.g., notifying a tracker)
+    #[serde(default)]
+    pub post_merge: Option<Vec<HookEntry>>,
 
     /// Commands to run before removing the worktree (e.g., for backups)
     #[serde(default)]
-    pub pre_remove: Option<Vec<String>>,
+    pub pre_remove: Option<Vec<HookEntry>>,
+
+    /// Commands to run after the worktree has been removed (e.g., dropping a database)
+    #[serde(default)]
+    pub post_remove: Option<Vec<HookEntry>>,
+
+    /// Commands to run when `workmux open` opens a window for an existing worktree
+    #[serde(default)]
+    pub on_open: Option<Vec<HookEntry>>,
+
+    /// Commands to run when `workmux close` closes a worktree's window
+    #[serde(default)]
+    pub on_close: Option<Vec<HookEntry>>,
 
     /// The agent command to use (e.g., "claude", "gemini")
     #[serde(default)]
@@ -149,6 +474,19 @@ pub struct Config {
     #[serde(default)]
     pub merge_strategy: Option<MergeStrategy>,
 
+    /// Code forge hosting the repository (PR lookups, `add --pr`).
+    /// Default: detected from the origin remote URL, falling back to GitHub.
+    #[serde(default)]
+    pub forge: Option<ForgeKind>,
+
+    /// Gitea/Forgejo API settings
+    #[serde(default)]
+    pub gitea: Option<GiteaConfig>,
+
+    /// Templates for `workmux pr create`
+    #[serde(default)]
+    pub pr: Option<PrConfig>,
+
     /// Strategy for deriving worktree/window names from branch names
     #[serde(default)]
     pub worktree_naming: WorktreeNaming,
@@ -170,7 +508,7 @@ pub struct Config {
     #[serde(default)]
     pub status_icons: StatusIcons,
 
-    /// Configuration for LLM-based branch name generation
+    /// LLM settings for branch names (--auto-name), commit messages and PR descriptions
     #[serde(default)]
     pub auto_name: Option<AutoNameConfig>,
 
@@ -181,6 +519,161 @@ pub struct Config {
     /// Whether to use nerdfont icons (None = prompt user on first run)
     #[serde(default)]
     pub nerdfont: Option<bool>,
+
+    /// Policies for garbage-collecting stale worktrees
+    #[serde(default)]
+    pub gc: Option<GcConfig>,
+
+    /// Directories that `workmux du --clean` may delete
+    #[serde(default)]
+    pub du: Option<DuConfig>,
+
+    /// Ports reserved for each worktree and exposed as WM_PORT, WM_PORT_1, ...
+    #[serde(default)]
+    pub ports: Option<PortsConfig>,
+
+    /// Config files this one builds on, relative to this file. Values in this
+    /// file override them. Resolved (and cleared) when the file is loaded.
+    #[serde(default, alias = "include", skip_serializing_if = "Option::is_none")]
+    pub extends: Option<ConfigIncludes>,
+
+    /// Named config overlays, selected with `workmux add --profile <name>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profiles: Option<BTreeMap<String, Config>>,
+
+    /// Config overlays for branches matching a glob (or `re:` regex) pattern
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overrides: Option<BranchOverrides>,
+}
+
+/// `overrides:` entries in the order they are written, so later matches win.
+#[derive(Debug, Clone, Default)]
+pub struct BranchOverrides(pub Vec<(String, Config)>);
+
+impl BranchOverrides {
+    /// Overlays whose pattern matches `branch`, in order.
+    pub fn matching(&self, branch: &str) -> anyhow::Result<Vec<(&str, &Config)>> {
+        let mut matched = Vec::new();
+        for (pattern, overlay) in &self.0 {
+            if branch_pattern_matches(pattern, branch)? {
+                matched.push((pattern.as_str(), overlay));
+            }
+        }
+        Ok(matched)
+    }
+}
+
+impl Serialize for BranchOverrides {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (pattern, overlay) in &self.0 {
+            map.serialize_entry(pattern, overlay)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for BranchOverrides {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OverridesVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for OverridesVisitor {
+            type Value = BranchOverrides;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a map of branch patterns to config")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut access: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = access.next_entry::<String, Config>()? {
+                    entries.push(entry);
+                }
+                Ok(BranchOverrides(entries))
+            }
+        }
+
+        deserializer.deserialize_map(OverridesVisitor)
+    }
+}
+
+/// Match a branch against an `overrides:` pattern: a glob such as `hotfix/*`, or a
+/// regular expression prefixed with `re:`.
+pub fn branch_pattern_matches(pattern: &str, branch: &str) -> anyhow::Result<bool> {
+    if let Some(re) = pattern.strip_prefix("re:") {
+        let re = regex::Regex::new(re)
+            .with_context(|| format!("Invalid regex in overrides pattern '{}'", pattern))?;
+        return Ok(re.is_match(branch));
+    }
+    let glob = glob::Pattern::new(pattern)
+        .with_context(|| format!("Invalid glob in overrides pattern '{}'", pattern))?;
+    Ok(glob.matches(branch))
+}
+
+/// One or more paths in `extends:` / `include:`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ConfigIncludes {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl ConfigIncludes {
+    pub fn paths(&self) -> &[String] {
+        match self {
+            ConfigIncludes::One(path) => std::slice::from_ref(path),
+            ConfigIncludes::Many(paths) => paths,
+        }
+    }
+}
+
+/// Path of the global config file (`~/.config/workmux/config.yaml` or `.yml`), if one exists.
+pub fn global_config_path() -> Option<PathBuf> {
+    // Check ~/.config/workmux (XDG convention, works cross-platform)
+    let dir = home::home_dir()?.join(".config/workmux");
+    ["config.yaml", "config.yml"]
+        .into_iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+}
+
+/// Append `path` to `files` after the files it extends (depth first), skipping
+/// files that are already listed. Unreadable files are left to the loader to report.
+pub fn collect_extends(path: &Path, files: &mut Vec<PathBuf>) {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if files.contains(&path) {
+        return;
+    }
+    let extends = fs::read_to_string(&path)
+        .ok()
+        .and_then(|contents| serde_yaml::from_str::<Config>(&contents).ok())
+        .and_then(|config| config.extends);
+    if let Some(extends) = extends {
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        for include in extends.paths() {
+            let include_path = resolve_include_path(dir, include);
+            if include_path.exists() {
+                collect_extends(&include_path, files);
+            }
+        }
+    }
+    if !files.contains(&path) {
+        files.push(path);
+    }
+}
+
+/// Resolve an `extends:` entry relative to the directory of the file that lists it.
+pub fn resolve_include_path(dir: &Path, include: &str) -> PathBuf {
+    if let Some(rest) = include.strip_prefix("~/")
+        && let Some(home_dir) = home::home_dir()
+    {
+        return home_dir.join(rest);
+    }
+    dir.join(include)
 }
 
 /// Configuration for a single tmux pane
@@ -231,6 +724,64 @@ pub enum MergeStrategy {
     Merge,
     Rebase,
     Squash,
+    /// Merge the branch's PR through the forge (`merge --via-pr`)
+    Pr,
+}
+
+/// Code forge that pull/merge requests are fetched from
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeKind {
+    /// GitHub, through the `gh` CLI
+    Github,
+    /// GitLab, through the `glab` CLI
+    Gitlab,
+    /// Gitea or Forgejo, through the REST API
+    #[serde(alias = "forgejo")]
+    Gitea,
+}
+
+/// Settings for Gitea/Forgejo instances, used when `forge` is `gitea`
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct GiteaConfig {
+    /// Base URL of the instance, e.g. https://git.example.com.
+    /// Default: https:// plus the origin remote's host
+    pub base_url: Option<String>,
+
+    /// API token. Prefer `token_env` in project configs.
+    pub token: Option<String>,
+
+    /// Environment variable holding the API token.
+    /// Default: GITEA_TOKEN, then FORGEJO_TOKEN
+    pub token_env: Option<String>,
+}
+
+impl GiteaConfig {
+    /// The API token from config or the environment, if any
+    pub fn token(&self) -> Option<String> {
+        if let Some(token) = &self.token {
+            return Some(token.clone());
+        }
+        let vars: Vec<&str> = match &self.token_env {
+            Some(var) => vec![var],
+            None => vec!["GITEA_TOKEN", "FORGEJO_TOKEN"],
+        };
+        vars.into_iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|token| !token.is_empty())
+    }
+}
+
+/// Title and body templates for `workmux pr create`
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct PrConfig {
+    /// MiniJinja template for the PR title.
+    /// Default: the only commit's subject, or the branch name
+    pub title: Option<String>,
+
+    /// MiniJinja template for the PR body.
+    /// Default: the task prompt, the commit list and diff stats
+    pub body: Option<String>,
 }
 
 /// Strategy for deriving worktree/window names from branch names
@@ -428,7 +979,7 @@ impl Config {
 
             // Default pre_remove hook for Node.js projects
             if config.pre_remove.is_none() && has_node_modules {
-                config.pre_remove = Some(vec![NODE_MODULES_CLEANUP_SCRIPT.to_string()]);
+                config.pre_remove = Some(vec![NODE_MODULES_CLEANUP_SCRIPT.into()]);
             }
         } else {
             // Apply fallback defaults for when not in a git repo (e.g., `workmux init`).
@@ -485,7 +1036,7 @@ impl Config {
             }
 
             if config.pre_remove.is_none() && has_node_modules {
-                config.pre_remove = Some(vec![NODE_MODULES_CLEANUP_SCRIPT.to_string()]);
+                config.pre_remove = Some(vec![NODE_MODULES_CLEANUP_SCRIPT.into()]);
             }
         } else if config.panes.is_none() {
             config.panes = Some(Self::default_panes());
@@ -505,27 +1056,147 @@ impl Config {
         if !path.exists() {
             return Ok(None);
         }
+        Self::load_with_extends(path, &mut Vec::new()).map(Some)
+    }
+
+    /// Read a config file and merge the files listed in its `extends:` beneath it.
+    /// `chain` holds the files currently being loaded, to detect cycles.
+    fn load_with_extends(path: &Path, chain: &mut Vec<PathBuf>) -> anyhow::Result<Self> {
         debug!(path = %path.display(), "config:reading file");
+        let canonical = path
+            .canonicalize()
+            .with_context(|| format!("Config file not found: {}", path.display()))?;
+        if chain.contains(&canonical) {
+            anyhow::bail!("Config extends itself in a cycle: {}", path.display());
+        }
+
         let contents = fs::read_to_string(path)?;
-        let config: Config = serde_yaml::from_str(&contents)
+        let mut config: Config = serde_yaml::from_str(&contents)
             .map_err(|e| anyhow::anyhow!("Failed to parse config at {}: {}", path.display(), e))?;
-        Ok(Some(config))
+
+        let Some(extends) = config.extends.take() else {
+            return Ok(config);
+        };
+        chain.push(canonical);
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let mut base = Config::default();
+        for include in extends.paths() {
+            let include_path = resolve_include_path(dir, include);
+            let included = Self::load_with_extends(&include_path, chain).with_context(|| {
+                format!(
+                    "Failed to load '{}' extended by {}",
+                    include,
+                    path.display()
+                )
+            })?;
+            base = base.merge(included);
+        }
+        chain.pop();
+        Ok(base.merge(config))
+    }
+
+    /// Apply a named profile from `profiles:` on top of this config.
+    ///
+    /// Profile values override the loaded config; `"<global>"` in a profile list
+    /// expands to the list it replaces. An agent given on the command line still wins.
+    pub fn apply_profile(self, name: &str, cli_agent: Option<&str>) -> anyhow::Result<Self> {
+        let Some(profile) = self.profiles.as_ref().and_then(|p| p.get(name)).cloned() else {
+            let available: Vec<&str> = self
+                .profiles
+                .iter()
+                .flat_map(|p| p.keys())
+                .map(String::as_str)
+                .collect();
+            if available.is_empty() {
+                anyhow::bail!("Unknown profile '{}': no profiles are configured", name);
+            }
+            anyhow::bail!(
+                "Unknown profile '{}'. Available profiles: {}",
+                name,
+                available.join(", ")
+            );
+        };
+        debug!(profile = name, "config:applying profile");
+
+        let mut config = self.merge(profile);
+        if let Some(agent) = cli_agent {
+            config.agent = Some(agent.to_string());
+        }
+        Ok(config)
+    }
+
+    /// Apply `overrides` entries matching `branch`, in order. An agent given on the
+    /// command line still wins.
+    pub fn apply_branch_overrides(
+        self,
+        branch: &str,
+        cli_agent: Option<&str>,
+    ) -> anyhow::Result<Self> {
+        let Some(overrides) = self.overrides.clone() else {
+            return Ok(self);
+        };
+        let mut config = self;
+        for (pattern, overlay) in overrides.matching(branch)? {
+            debug!(branch, pattern, "config:applying branch override");
+            config = config.merge(overlay.clone());
+        }
+        if let Some(agent) = cli_agent {
+            config.agent = Some(agent.to_string());
+        }
+        Ok(config)
+    }
+
+    /// Resolve the config for a branch: matching `overrides` first, then `profile`,
+    /// so an explicitly selected profile wins over branch patterns.
+    pub fn for_branch(
+        self,
+        branch: Option<&str>,
+        profile: Option<&str>,
+        cli_agent: Option<&str>,
+    ) -> anyhow::Result<Self> {
+        let config = match branch {
+            Some(branch) => self.apply_branch_overrides(branch, cli_agent)?,
+            None => self,
+        };
+        match profile {
+            Some(name) => config.apply_profile(name, cli_agent),
+            None => Ok(config),
+        }
+    }
+
+    /// `main_branch` set by the last override matching `branch`, if any.
+    /// New matching branches are created from it when no base is given.
+    pub fn override_main_branch(&self, branch: &str) -> anyhow::Result<Option<String>> {
+        let Some(overrides) = &self.overrides else {
+            return Ok(None);
+        };
+        Ok(overrides
+            .matching(branch)?
+            .into_iter()
+            .rev()
+            .find_map(|(_, overlay)| overlay.main_branch.clone()))
     }
 
     /// Load the global configuration file from the XDG config directory.
     fn load_global() -> anyhow::Result<Option<Self>> {
-        // Check ~/.config/workmux (XDG convention, works cross-platform)
-        if let Some(home_dir) = home::home_dir() {
-            let xdg_config_path = home_dir.join(".config/workmux/config.yaml");
-            if xdg_config_path.exists() {
-                return Self::load_from_path(&xdg_config_path);
-            }
-            let xdg_config_path_yml = home_dir.join(".config/workmux/config.yml");
-            if xdg_config_path_yml.exists() {
-                return Self::load_from_path(&xdg_config_path_yml);
-            }
+        match global_config_path() {
+            Some(path) => Self::load_from_path(&path),
+            None => Ok(None),
+        }
+    }
+
+    /// The config files that make up the loaded configuration, lowest precedence first:
+    /// the global config, then the project config, each preceded by the files it extends.
+    pub fn source_files() -> anyhow::Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        if let Some(path) = global_config_path() {
+            collect_extends(&path, &mut files);
+        }
+        let start_dir = std::env::current_dir().unwrap_or_default();
+        if let Some(location) = find_project_config(&start_dir)? {
+            collect_extends(&location.config_path, &mut files);
         }
-        Ok(None)
+        Ok(files)
     }
 
     /// Load project config and return its location.
@@ -556,17 +1227,17 @@ impl Config {
     fn merge(self, project: Self) -> Self {
         /// Merge vectors with "<global>" placeholder expansion.
         /// When project contains "<global>", it expands to global items at that position.
-        fn merge_vec_with_placeholder(
-            global: Option<Vec<String>>,
-            project: Option<Vec<String>>,
-        ) -> Option<Vec<String>> {
+        fn merge_vec_with_placeholder<T: GlobalPlaceholder>(
+            global: Option<Vec<T>>,
+            project: Option<Vec<T>>,
+        ) -> Option<Vec<T>> {
             match (global, project) {
                 (Some(global_items), Some(project_items)) => {
-                    let has_placeholder = project_items.iter().any(|s| s == "<global>");
+                    let has_placeholder = project_items.iter().any(|s| s.is_global_placeholder());
                     if has_placeholder {
                         let mut result = Vec::new();
                         for item in project_items {
-                            if item == "<global>" {
+                            if item.is_global_placeholder() {
                                 result.extend(global_items.clone());
                             } else {
                                 result.push(item);
@@ -601,11 +1272,17 @@ impl Config {
             window_prefix,
             agent,
             merge_strategy,
+            forge,
+            gitea,
+            pr,
             worktree_prefix,
             panes,
             status_format,
             auto_name,
             nerdfont,
+            gc,
+            du,
+            ports,
         );
 
         // Special case: worktree_naming (project wins if not default)
@@ -616,14 +1293,20 @@ impl Config {
         };
 
         // List values with "<global>" placeholder support
+        merged.pre_create = merge_vec_with_placeholder(self.pre_create, project.pre_create);
         merged.post_create = merge_vec_with_placeholder(self.post_create, project.post_create);
         merged.pre_merge = merge_vec_with_placeholder(self.pre_merge, project.pre_merge);
+        merged.post_merge = merge_vec_with_placeholder(self.post_merge, project.post_merge);
         merged.pre_remove = merge_vec_with_placeholder(self.pre_remove, project.pre_remove);
+        merged.post_remove = merge_vec_with_placeholder(self.post_remove, project.post_remove);
+        merged.on_open = merge_vec_with_placeholder(self.on_open, project.on_open);
+        merged.on_close = merge_vec_with_placeholder(self.on_close, project.on_close);
 
         // File config with placeholder support
         merged.files = FileConfig {
             copy: merge_vec_with_placeholder(self.files.copy, project.files.copy),
             symlink: merge_vec_with_placeholder(self.files.symlink, project.files.symlink),
+            template: project.files.template.or(self.files.template),
         };
 
         // Status icons: per-field override
@@ -631,6 +1314,25 @@ impl Config {
             working: project.status_icons.working.or(self.status_icons.working),
             waiting: project.status_icons.waiting.or(self.status_icons.waiting),
             done: project.status_icons.done.or(self.status_icons.done),
+            setup: project.status_icons.setup.or(self.status_icons.setup),
+        };
+
+        // Profiles: project entries replace global entries with the same name
+        merged.profiles = match (self.profiles, project.profiles) {
+            (Some(mut global), Some(project)) => {
+                global.extend(project);
+                Some(global)
+            }
+            (global, project) => project.or(global),
+        };
+
+        // Overrides: global entries first, so matching project entries are applied last
+        merged.overrides = match (self.overrides, project.overrides) {
+            (Some(mut global), Some(project)) => {
+                global.0.extend(project.0);
+                Some(global)
+            }
+            (global, project) => project.or(global),
         };
 
         // Dashboard actions: per-field override
@@ -731,10 +1433,33 @@ impl Config {
 # main_branch: main
 
 # Default merge strategy for `workmux merge`.
-# Options: merge (default), rebase, squash
-# CLI flags (--rebase, --squash) always override this.
+# Options: merge (default), rebase, squash, pr
+# CLI flags (--rebase, --squash) always override this, except with `pr`, which
+# merges the branch's pull request through the forge (like --via-pr) and uses
+# --rebase/--squash as the PR's merge method.
 # merge_strategy: rebase
 
+# Code forge for PR features (`add --pr`, `list --pr`, dashboard PR column).
+# Options: github (uses `gh`), gitlab (uses `glab`), gitea (REST API, also
+# for Forgejo)
+# Default: Detected from the origin remote URL, falls back to github.
+# Set this for self-hosted instances whose hostname doesn't say which it is.
+# forge: gitlab
+
+# Gitea/Forgejo API settings.
+# gitea:
+#   base_url: https://git.example.com   # Default: https://<origin host>
+#   token_env: GITEA_TOKEN              # Default: GITEA_TOKEN, then FORGEJO_TOKEN
+
+# Title and body templates for `workmux pr create`. Variables: branch, base,
+# commits (subjects, oldest first), prompt (the task prompt, if any),
+# lines_added, lines_removed.
+# pr:
+#   title: "{{ branch }}"
+#   body: |
+#     {% for commit in commits %}- {{ commit }}
+#     {% endfor %}
+
 #-------------------------------------------------------------------------------
 # Naming & Paths
 #-------------------------------------------------------------------------------
@@ -743,6 +1468,11 @@ impl Config {
 # Can be relative to repo root or absolute.
 # Default: Sibling directory '<project>__worktrees'.
 # worktree_dir: .worktrees
+#
+# worktree_dir, pane commands and hooks can use ${VAR:-default} and
+# {{ handle }}, {{ branch }}, {{ project_root }} (resolved at use time).
+# In commands, rendered values are shell-quoted and ${...} is left for the shell.
+# worktree_dir: ${WORKTREES_ROOT:-../worktrees}
 
 # Strategy for deriving names from branch names.
 # Options: full (default), basename (part after last '/').
@@ -792,6 +1522,15 @@ impl Config {
 #   model: "gpt-4o-mini"
 #   system_prompt: "Generate a kebab-case git branch name."
 #   background: true  # Always run in background when using --auto-name
+#   # Use an OpenAI-compatible endpoint instead of the `llm` CLI:
+#   provider: openai
+#   base_url: "http://localhost:11434/v1"  # e.g. Ollama; default is OpenAI
+#   api_key_env: OPENAI_API_KEY
+#   # Prompts for generated commit messages and PR descriptions
+#   # (`merge --squash --generate-message`, `pr create --generate`, `g` in the
+#   # dashboard diff view):
+#   commit_prompt: "Write a conventional commit message for this diff."
+#   pr_prompt: "Write a PR title on the first line, then a markdown body."
 
 #-------------------------------------------------------------------------------
 # Hooks
@@ -804,6 +1543,18 @@ impl Config {
 # post_create:
 #   - "<global>"
 #   - mise use
+#
+# Hooks can also be written as objects with execution options:
+#   - command: pnpm install
+#     name: install
+#     timeout: 600              # Kill after N seconds
+#     parallel: deps            # Consecutive hooks in the same group run together
+#     continue_on_error: true   # Don't abort if this hook fails
+#     env: { CI: "1" }
+#     when: test -f package.json  # Only run if this command succeeds
+#     background: true          # post_create only: don't block the window;
+#                               # pane commands start once background hooks pass
+# Output of every hook is also logged under ~/.local/state/workmux/logs/hooks.
 
 # Commands to run before merging (e.g., linting, tests).
 # Aborts the merge if any command fails.
@@ -831,6 +1582,52 @@ impl Config {
 #   - mkdir -p "$WM_PROJECT_ROOT/artifacts/$WM_HANDLE"
 #   - cp -r test-results/ "$WM_PROJECT_ROOT/artifacts/$WM_HANDLE/"
 
+# More lifecycle hooks. All receive WM_HANDLE, WM_BRANCH_NAME, WM_WORKTREE_PATH
+# and WM_PROJECT_ROOT (plus WM_PORT* when ports are reserved).
+#   - pre_create: before the worktree is created; runs in the project root and
+#     aborts creation on failure. Also receives WM_BASE_BRANCH.
+#   - post_merge: after a successful merge, in the target worktree.
+#     Also receives WM_TARGET_BRANCH.
+#   - post_remove: after the worktree is removed; runs in the project root.
+#   - on_open / on_close: when `workmux open` / `workmux close` open or close
+#     the worktree's window; run in the worktree.
+# pre_create:
+#   - echo "$WM_BRANCH_NAME" | grep -Eq '^(feat|fix)/'
+# post_remove:
+#   - dropdb --if-exists "app_$WM_HANDLE"
+# on_open:
+#   - docker compose up -d
+# on_close:
+#   - docker compose stop
+
+#-------------------------------------------------------------------------------
+# Shared config and profiles
+#-------------------------------------------------------------------------------
+
+# Pull in shared config files (relative to this file). This file overrides them.
+# extends:
+#   - ../shared/workmux-base.yaml
+
+# Named overlays, selected with `workmux add --profile <name>`.
+# profiles:
+#   frontend:
+#     panes:
+#       - command: <agent>
+#         focus: true
+#       - command: pnpm dev
+#         split: horizontal
+#   backend:
+#     agent: codex
+
+# Overlays for branches matching a glob (or a regex prefixed with `re:`),
+# applied by add, open and merge. Later matches win; --profile wins over both.
+# overrides:
+#   "hotfix/*":
+#     main_branch: release
+#     merge_strategy: squash
+#   "re:^feat/.+":
+#     agent: codex
+
 #-------------------------------------------------------------------------------
 # Files
 #-------------------------------------------------------------------------------
@@ -838,8 +1635,12 @@ impl Config {
 # File operations when creating a worktree.
 # files:
 #   # Files to copy (useful for .env files that need to be unique).
+#   # Mode per entry: reflink (default, copy-on-write with fallback),
+#   # hardlink, copy, or incremental (skip unchanged files).
 #   copy:
 #     - .env.local
+#     - path: node_modules
+#       mode: hardlink
 #
 #   # Files/directories to symlink (saves disk space, shares caches).
 #   # Default: None.
@@ -847,6 +1648,47 @@ impl Config {
 #   symlink:
 #     - "<global>"
 #     - node_modules
+#
+#   # Files rendered with per-worktree variables: handle, branch,
+#   # worktree_path, project_root, port and ports (see `ports` below).
+#   # Example .env.template line: DATABASE_URL=postgres://localhost/app_{{ handle | slugify }}
+#   template:
+#     - source: .env.template
+#       dest: .env
+
+#-------------------------------------------------------------------------------
+# Garbage Collection
+#-------------------------------------------------------------------------------
+
+# Policies for `workmux gc`. A worktree is collected if it matches any policy.
+# Worktrees with uncommitted changes or an open window are always skipped.
+# gc:
+#   older_than_days: 14   # Last commit older than N days
+#   merged: true          # Branch fully merged into main
+#   gone: true            # Upstream remote branch deleted
+#   pr_closed: true       # PR closed or merged (requires gh CLI)
+#   on_add: true          # Run automatically before `workmux add`
+
+# Ignored directories that `workmux du --clean` may delete. Names match at any
+# depth; entries with a `/` are globs from the worktree root.
+# Default: target, node_modules, dist, build, .next, __pycache__
+# du:
+#   clean:
+#     - target
+#     - node_modules
+#     - packages/*/dist
+
+#-------------------------------------------------------------------------------
+# Ports
+#-------------------------------------------------------------------------------
+
+# Reserve unique ports for each worktree so dev servers don't collide.
+# Exposed as WM_PORT (first port), WM_PORT_1, WM_PORT_2, ... in hooks, pane
+# commands and a generated .env.workmux file. Released on `workmux remove`.
+# ports:
+#   count: 2
+#   range_start: 4000
+#   range_end: 4999
 
 #-------------------------------------------------------------------------------
 # Dashboard
@@ -1095,4 +1937,189 @@ mod tests {
         let loc = result.unwrap();
         assert!(loc.config_path.ends_with("backend/.workmux.yaml"));
     }
+
+    #[test]
+    fn files_copy_accepts_modes_and_global_placeholder() {
+        use super::{Config, CopyEntry, CopyMode};
+
+        let global: Config = serde_yaml::from_str("files:\n  copy:\n    - .env\n").unwrap();
+        let project: Config = serde_yaml::from_str(
+            "files:\n  copy:\n    - \"<global>\"\n    - path: node_modules\n      mode: hardlink\n",
+        )
+        .unwrap();
+
+        let merged = global.merge(project);
+        let copy = merged.files.copy.unwrap();
+        assert_eq!(copy[0], CopyEntry::Pattern(".env".to_string()));
+        assert_eq!(copy[0].mode(), CopyMode::Reflink);
+        assert_eq!(copy[1].pattern(), "node_modules");
+        assert_eq!(copy[1].mode(), CopyMode::Hardlink);
+    }
+
+    #[test]
+    fn hooks_accept_strings_and_structured_entries() {
+        use super::{Config, HookEntry};
+
+        let config: Config = serde_yaml::from_str(
+            "post_create:\n  - direnv allow\n  - command: pnpm install\n    timeout: 600\n    parallel: deps\n    env:\n      CI: \"1\"\n",
+        )
+        .unwrap();
+
+        let hooks = config.post_create.unwrap();
+        assert_eq!(hooks[0], HookEntry::Command("direnv allow".to_string()));
+        let spec = hooks[1].to_spec();
+        assert_eq!(spec.command, "pnpm install");
+        assert_eq!(spec.timeout, Some(600));
+        assert_eq!(spec.parallel.as_deref(), Some("deps"));
+        assert_eq!(spec.env.get("CI").map(String::as_str), Some("1"));
+        assert!(!spec.continue_on_error);
+    }
+
+    #[test]
+    fn lifecycle_hooks_merge_with_global_placeholder() {
+        use super::{Config, HookEntry};
+
+        let global: Config =
+            serde_yaml::from_str("post_remove:\n  - notify removed\non_close:\n  - stop\n")
+                .unwrap();
+        let project: Config = serde_yaml::from_str(
+            "pre_create:\n  - ./check-branch\npost_remove:\n  - \"<global>\"\n  - dropdb app\n",
+        )
+        .unwrap();
+
+        let merged = global.merge(project);
+        assert_eq!(
+            merged.pre_create.unwrap(),
+            vec![HookEntry::from("./check-branch")]
+        );
+        assert_eq!(
+            merged.post_remove.unwrap(),
+            vec![
+                HookEntry::from("notify removed"),
+                HookEntry::from("dropdb app")
+            ]
+        );
+        assert_eq!(merged.on_close.unwrap(), vec![HookEntry::from("stop")]);
+        assert!(merged.on_open.is_none());
+    }
+
+    #[test]
+    fn extends_merges_included_files_beneath_the_config() {
+        use super::Config;
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("shared")).unwrap();
+        fs::write(
+            dir.path().join("shared/base.yaml"),
+            "agent: codex\nwindow_prefix: base-\npost_create:\n  - npm install\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join(".workmux.yaml"),
+            "include: shared/base.yaml\nwindow_prefix: app-\npost_create:\n  - \"<global>\"\n  - direnv allow\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from_path(&dir.path().join(".workmux.yaml"))
+            .unwrap()
+            .unwrap();
+
+        assert!(config.extends.is_none());
+        assert_eq!(config.agent.as_deref(), Some("codex"));
+        assert_eq!(config.window_prefix.as_deref(), Some("app-"));
+        assert_eq!(config.post_create.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn extends_cycle_is_an_error() {
+        use super::Config;
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.yaml"), "extends: b.yaml\n").unwrap();
+        fs::write(dir.path().join("b.yaml"), "extends: [a.yaml]\n").unwrap();
+
+        let err = Config::load_from_path(&dir.path().join("a.yaml")).unwrap_err();
+        assert!(format!("{:#}", err).contains("cycle"));
+    }
+
+    #[test]
+    fn apply_profile_overrides_config_but_not_cli_agent() {
+        use super::Config;
+
+        let config: Config = serde_yaml::from_str(
+            "agent: claude\nwindow_prefix: wm-\nprofiles:\n  frontend:\n    agent: gemini\n    panes:\n      - command: npm run dev\n",
+        )
+        .unwrap();
+
+        let frontend = config.clone().apply_profile("frontend", None).unwrap();
+        assert_eq!(frontend.agent.as_deref(), Some("gemini"));
+        assert_eq!(frontend.window_prefix.as_deref(), Some("wm-"));
+        assert_eq!(frontend.panes.unwrap().len(), 1);
+
+        let with_cli = config
+            .clone()
+            .apply_profile("frontend", Some("codex"))
+            .unwrap();
+        assert_eq!(with_cli.agent.as_deref(), Some("codex"));
+
+        let err = config.apply_profile("backend", None).unwrap_err();
+        assert!(err.to_string().contains("Available profiles: frontend"));
+    }
+
+    #[test]
+    fn branch_overrides_apply_in_order_before_the_profile() {
+        use super::{Config, MergeStrategy};
+
+        let global: Config = serde_yaml::from_str(
+            "overrides:\n  \"hotfix/*\":\n    merge_strategy: squash\n    agent: gemini\n",
+        )
+        .unwrap();
+        let project: Config = serde_yaml::from_str(
+            "agent: claude\noverrides:\n  \"re:^hotfix/\\\\d+\":\n    main_branch: release\n    agent: codex\nprofiles:\n  review:\n    agent: opencode\n",
+        )
+        .unwrap();
+        let config = global.merge(project);
+
+        let feature = config
+            .clone()
+            .apply_branch_overrides("feat/login", None)
+            .unwrap();
+        assert_eq!(feature.agent.as_deref(), Some("claude"));
+        assert_eq!(feature.merge_strategy, None);
+
+        let hotfix = config
+            .clone()
+            .apply_branch_overrides("hotfix/42", None)
+            .unwrap();
+        assert_eq!(hotfix.agent.as_deref(), Some("codex"));
+        assert_eq!(hotfix.merge_strategy, Some(MergeStrategy::Squash));
+        assert_eq!(hotfix.main_branch.as_deref(), Some("release"));
+        assert_eq!(
+            config.override_main_branch("hotfix/42").unwrap().as_deref(),
+            Some("release")
+        );
+        assert_eq!(config.override_main_branch("hotfix/x").unwrap(), None);
+
+        let with_profile = config
+            .clone()
+            .for_branch(Some("hotfix/42"), Some("review"), None)
+            .unwrap();
+        assert_eq!(with_profile.agent.as_deref(), Some("opencode"));
+
+        let with_cli = config
+            .for_branch(Some("hotfix/42"), None, Some("aider"))
+            .unwrap();
+        assert_eq!(with_cli.agent.as_deref(), Some("aider"));
+    }
+
+    #[test]
+    fn branch_pattern_matches_globs_and_regexes() {
+        use super::branch_pattern_matches;
+
+        assert!(branch_pattern_matches("feat/*", "feat/login").unwrap());
+        assert!(!branch_pattern_matches("feat/*", "fix/login").unwrap());
+        assert!(branch_pattern_matches("re:^(fix|hotfix)/", "hotfix/1").unwrap());
+        assert!(!branch_pattern_matches("re:^fix/", "hotfix/1").unwrap());
+        assert!(branch_pattern_matches("re:(", "x").is_err());
+    }
 }
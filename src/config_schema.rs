@@ -0,0 +1,622 @@
+//! JSON Schema for `.workmux.yaml` and helpers for `workmux config validate`.
+//!
+//! The schema is written by hand to mirror the serde layout of [`crate::config::Config`];
+//! the tests below keep the two in sync.
+
+use serde_json::{Value as Json, json};
+use serde_yaml::Value as Yaml;
+
+/// One step in the path to a value inside a config document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// Render a path as `files.copy[2].mode`.
+pub fn display_path(path: &[Segment]) -> String {
+    let mut out = String::new();
+    for segment in path {
+        match segment {
+            Segment::Key(key) => {
+                if !out.is_empty() {
+                    out.push('.');
+                }
+                out.push_str(key);
+            }
+            Segment::Index(i) => out.push_str(&format!("[{}]", i)),
+        }
+    }
+    out
+}
+
+/// JSON Schema (draft-07) describing the workmux config file.
+pub fn schema() -> Json {
+    let string_list = json!({ "type": "array", "items": { "type": "string" } });
+    let include = json!({
+        "description": "Config file(s) merged beneath this one, relative to this file",
+        "anyOf": [{ "type": "string" }, string_list]
+    });
+
+    let files = json!({
+        "description": "Files copied, symlinked or rendered into new worktrees",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "copy": {
+                "type": "array",
+                "items": {
+                    "anyOf": [
+                        { "type": "string" },
+                        {
+                            "type": "object",
+                            "additionalProperties": false,
+                            "required": ["path"],
+                            "properties": {
+                                "path": { "type": "string" },
+                                "mode": { "enum": ["reflink", "hardlink", "copy", "incremental"] }
+                            }
+                        }
+                    ]
+                }
+            },
+            "symlink": string_list,
+            "template": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "required": ["source"],
+                    "properties": {
+                        "source": { "type": "string" },
+                        "dest": { "type": "string" }
+                    }
+                }
+            }
+        }
+    });
+    let definitions = json!({
+        "pane": {
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "command": { "type": "string" },
+                "focus": { "type": "boolean" },
+                "split": { "enum": ["horizontal", "vertical"] },
+                "size": { "type": "integer", "minimum": 0 },
+                "percentage": { "type": "integer", "minimum": 1, "maximum": 100 },
+                "target": { "type": "integer", "minimum": 0 }
+            }
+        },
+        "hook": {
+            "anyOf": [
+                { "type": "string" },
+                {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "required": ["command"],
+                    "properties": {
+                        "command": { "type": "string" },
+                        "name": { "type": "string" },
+                        "timeout": { "type": "integer", "minimum": 0 },
+                        "parallel": { "type": "string" },
+                        "continue_on_error": { "type": "boolean" },
+                        "env": {
+                            "type": "object",
+                            "additionalProperties": { "type": "string" }
+                        },
+                        "when": { "type": "string" },
+                        "background": { "type": "boolean" }
+                    }
+                }
+            ]
+        }
+    });
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "workmux configuration",
+        "description": "Configuration for workmux, read from .workmux.yaml or ~/.config/workmux/config.yaml",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "main_branch": {
+                "description": "The primary branch to merge into (auto-detected if not set)",
+                "type": "string"
+            },
+            "worktree_dir": {
+                "description": "Directory where worktrees are created, relative to the repo root or absolute",
+                "type": "string"
+            },
+            "window_prefix": {
+                "description": "Prefix for tmux window names (default: wm-)",
+                "type": "string"
+            },
+            "panes": {
+                "description": "Pane layout for new worktree windows",
+                "type": "array",
+                "items": { "$ref": "#/definitions/pane" }
+            },
+            "pre_create": hooks("Commands run in the main worktree before the worktree is created"),
+            "post_create": hooks("Commands run in the new worktree before the window opens"),
+            "pre_merge": hooks("Commands run in the worktree before merging; a failure aborts the merge"),
+            "post_merge": hooks("Commands run in the target worktree after a merge"),
+            "pre_remove": hooks("Commands run in the worktree before it is removed"),
+            "post_remove": hooks("Commands run in the main worktree after a worktree is removed"),
+            "on_open": hooks("Commands run in the worktree before its window opens"),
+            "on_close": hooks("Commands run in the worktree before its window closes"),
+            "agent": {
+                "description": "Agent command substituted for <agent> in pane commands (default: claude)",
+                "type": "string"
+            },
+            "merge_strategy": {
+                "description": "Default strategy for workmux merge",
+                "enum": ["merge", "rebase", "squash", "pr"]
+            },
+            "forge": {
+                "description": "Code forge for PR features (default: detected from the origin URL)",
+                "enum": ["github", "gitlab", "gitea", "forgejo"]
+            },
+            "gitea": {
+                "description": "Gitea/Forgejo API settings",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "base_url": { "type": "string" },
+                    "token": { "type": "string" },
+                    "token_env": { "type": "string" }
+                }
+            },
+            "pr": {
+                "description": "Title and body templates for workmux pr create",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "title": { "type": "string" },
+                    "body": { "type": "string" }
+                }
+            },
+            "worktree_naming": {
+                "description": "How worktree and window names are derived from branch names",
+                "enum": ["full", "basename"]
+            },
+            "worktree_prefix": {
+                "description": "Prefix added to worktree directory and window names",
+                "type": "string"
+            },
+            "files": files,
+            "status_format": {
+                "description": "Show agent status icons in the tmux window name",
+                "type": "boolean"
+            },
+            "status_icons": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "working": { "type": "string" },
+                    "waiting": { "type": "string" },
+                    "done": { "type": "string" },
+                    "setup": { "type": "string" }
+                }
+            },
+            "auto_name": {
+                "description": "LLM settings for --auto-name and generated commit messages and PR descriptions",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "model": { "type": "string" },
+                    "system_prompt": { "type": "string" },
+                    "background": { "type": "boolean" },
+                    "provider": { "enum": ["llm", "openai"] },
+                    "base_url": { "type": "string" },
+                    "api_key_env": { "type": "string" },
+                    "commit_prompt": { "type": "string" },
+                    "pr_prompt": { "type": "string" }
+                }
+            },
+            "dashboard": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "commit": { "type": "string" },
+                    "merge": { "type": "string" },
+                    "preview_size": { "type": "integer", "minimum": 10, "maximum": 90 },
+                    "show_check_counts": { "type": "boolean" }
+                }
+            },
+            "nerdfont": {
+                "description": "Use Nerd Font icons",
+                "type": "boolean"
+            },
+            "gc": {
+                "description": "Policies for workmux gc",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "older_than_days": { "type": "integer", "minimum": 0 },
+                    "merged": { "type": "boolean" },
+                    "gone": { "type": "boolean" },
+                    "pr_closed": { "type": "boolean" },
+                    "on_add": { "type": "boolean" }
+                }
+            },
+            "du": {
+                "description": "Directories that workmux du --clean may delete",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "clean": { "type": "array", "items": { "type": "string" } }
+                }
+            },
+            "ports": {
+                "description": "Per-worktree port allocation",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "count": { "type": "integer", "minimum": 0, "maximum": 65535 },
+                    "range_start": { "type": "integer", "minimum": 0, "maximum": 65535 },
+                    "range_end": { "type": "integer", "minimum": 0, "maximum": 65535 }
+                }
+            },
+            "extends": include.clone(),
+            "include": include,
+            "profiles": {
+                "description": "Named config overlays selected with workmux add --profile",
+                "type": "object",
+                "additionalProperties": { "$ref": "#" }
+            },
+            "overrides": {
+                "description": "Config overlays for branches matching a glob, or a regex prefixed with re:",
+                "type": "object",
+                "additionalProperties": { "$ref": "#" }
+            }
+        },
+        "definitions": definitions
+    })
+}
+
+fn hooks(description: &str) -> Json {
+    json!({
+        "description": description,
+        "type": "array",
+        "items": { "$ref": "#/definitions/hook" }
+    })
+}
+
+/// Walk a parsed YAML document against the schema and return the path of every
+/// key the schema does not allow.
+pub fn unknown_keys(document: &Yaml, schema: &Json) -> Vec<Vec<Segment>> {
+    let mut found = Vec::new();
+    walk(document, schema, schema, &mut Vec::new(), &mut found);
+    found
+}
+
+fn walk(
+    value: &Yaml,
+    node: &Json,
+    root: &Json,
+    path: &mut Vec<Segment>,
+    found: &mut Vec<Vec<Segment>>,
+) {
+    let node = resolve_ref(node, root);
+
+    if let Some(variants) = node.get("anyOf").and_then(Json::as_array) {
+        if let Some(variant) = variants
+            .iter()
+            .map(|v| resolve_ref(v, root))
+            .find(|v| type_matches(v, value))
+        {
+            walk(value, variant, root, path, found);
+        }
+        return;
+    }
+
+    match value {
+        Yaml::Mapping(map) => {
+            let properties = node.get("properties").and_then(Json::as_object);
+            let additional = node.get("additionalProperties");
+            for (key, child) in map {
+                let Some(key) = key.as_str() else { continue };
+                path.push(Segment::Key(key.to_string()));
+                if let Some(schema) = properties.and_then(|p| p.get(key)) {
+                    walk(child, schema, root, path, found);
+                } else if let Some(schema) = additional.filter(|a| a.is_object()) {
+                    walk(child, schema, root, path, found);
+                } else if additional == Some(&Json::Bool(false)) {
+                    found.push(path.clone());
+                }
+                path.pop();
+            }
+        }
+        Yaml::Sequence(items) => {
+            if let Some(schema) = node.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    path.push(Segment::Index(i));
+                    walk(item, schema, root, path, found);
+                    path.pop();
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn resolve_ref<'a>(node: &'a Json, root: &'a Json) -> &'a Json {
+    match node.get("$ref").and_then(Json::as_str) {
+        Some("#") => root,
+        Some(pointer) => pointer
+            .strip_prefix('#')
+            .and_then(|p| root.pointer(p))
+            .unwrap_or(node),
+        None => node,
+    }
+}
+
+fn type_matches(node: &Json, value: &Yaml) -> bool {
+    match node.get("type").and_then(Json::as_str) {
+        Some("object") => value.is_mapping(),
+        Some("array") => value.is_sequence(),
+        Some("string") => value.is_string(),
+        Some("boolean") => value.is_bool(),
+        Some("integer") => value.is_u64() || value.is_i64(),
+        _ => false,
+    }
+}
+
+/// Find the 1-based line and column of the value at `path` in a YAML source.
+///
+/// This is a lightweight scan of block-style YAML (the style config files are
+/// written in), used to point error messages at the offending key.
+pub fn locate(source: &str, path: &[Segment]) -> Option<(usize, usize)> {
+    let lines: Vec<&str> = source.lines().collect();
+    let first = (0..lines.len()).find(|&i| is_content(lines[i]))?;
+    let mut start = first;
+    let mut end = lines.len();
+    let mut col = indent(lines[first]);
+    let mut position = (first, col);
+
+    for segment in path {
+        match segment {
+            Segment::Key(key) => {
+                let line = (start..end)
+                    .find(|&i| starts_at(lines[i], col) && key_matches(&lines[i][col..], key))?;
+                position = (line, col);
+                let block_end = (line + 1..end)
+                    .find(|&i| {
+                        is_content(lines[i]) && {
+                            let n = indent(lines[i]);
+                            n < col || (n == col && !is_item(&lines[i][n..]))
+                        }
+                    })
+                    .unwrap_or(end);
+                start = line + 1;
+                end = block_end;
+                col = (start..end)
+                    .find(|&i| is_content(lines[i]))
+                    .map(|i| indent(lines[i]))
+                    .unwrap_or(col);
+            }
+            Segment::Index(index) => {
+                let items: Vec<usize> = (start..end)
+                    .filter(|&i| starts_at(lines[i], col) && is_item(&lines[i][col..]))
+                    .collect();
+                let line = *items.get(*index)?;
+                let item_end = items.get(index + 1).copied().unwrap_or(end);
+                let rest = &lines[line][col + 1..];
+                let content = rest.trim_start();
+                if content.is_empty() || content.starts_with('#') {
+                    start = line + 1;
+                    col = (start..item_end)
+                        .find(|&i| is_content(lines[i]))
+                        .map(|i| indent(lines[i]))
+                        .unwrap_or(col);
+                } else {
+                    start = line;
+                    col = col + 1 + (rest.len() - content.len());
+                }
+                position = (line, col);
+                end = item_end;
+            }
+        }
+    }
+
+    Some((position.0 + 1, position.1 + 1))
+}
+
+fn is_content(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    !trimmed.is_empty() && !trimmed.starts_with('#') && trimmed != "---"
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn is_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// Whether the text of `line` begins at `col`, allowing for `- ` sequence markers before it.
+fn starts_at(line: &str, col: usize) -> bool {
+    line.len() > col
+        && line.is_char_boundary(col)
+        && line[..col].chars().all(|c| c == ' ' || c == '-')
+        && !line[col..].starts_with(' ')
+}
+
+fn key_matches(text: &str, key: &str) -> bool {
+    let rest = [
+        text.strip_prefix(key),
+        text.strip_prefix('"')
+            .and_then(|t| t.strip_prefix(key))
+            .and_then(|t| t.strip_prefix('"')),
+        text.strip_prefix('\'')
+            .and_then(|t| t.strip_prefix(key))
+            .and_then(|t| t.strip_prefix('\'')),
+    ];
+    rest.into_iter().flatten().any(|rest| {
+        rest.trim_start_matches(' ')
+            .strip_prefix(':')
+            .is_some_and(|after| after.is_empty() || after.starts_with([' ', '\t']))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, HookEntry, HookSpec, PaneConfig};
+
+    fn key(k: &str) -> Segment {
+        Segment::Key(k.to_string())
+    }
+
+    fn object_keys(node: &Json) -> Vec<String> {
+        let mut keys: Vec<String> = node["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    fn value_keys(value: &Json) -> Vec<String> {
+        let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn schema_covers_every_config_field() {
+        let schema = schema();
+        let config = Config {
+            profiles: Some(Default::default()),
+            overrides: Some(Default::default()),
+            ..Default::default()
+        };
+        let mut expected = value_keys(&serde_json::to_value(config).unwrap());
+        expected.push("extends".to_string());
+        expected.push("include".to_string());
+        expected.sort();
+        assert_eq!(object_keys(&schema), expected);
+
+        for field in [
+            "files",
+            "status_icons",
+            "dashboard",
+            "auto_name",
+            "gc",
+            "du",
+            "ports",
+        ] {
+            let defaults = serde_json::to_value(Config {
+                auto_name: Some(Default::default()),
+                gc: Some(Default::default()),
+                du: Some(Default::default()),
+                ports: Some(Default::default()),
+                ..Default::default()
+            })
+            .unwrap();
+            assert_eq!(
+                object_keys(&schema["properties"][field]),
+                value_keys(&defaults[field]),
+                "{field}"
+            );
+        }
+
+        let pane = serde_json::to_value(PaneConfig {
+            command: None,
+            focus: false,
+            split: None,
+            size: None,
+            percentage: None,
+            target: None,
+        })
+        .unwrap();
+        assert_eq!(
+            object_keys(&schema["definitions"]["pane"]),
+            value_keys(&pane)
+        );
+
+        let hook = serde_json::to_value(HookEntry::Detailed(HookSpec::default())).unwrap();
+        assert_eq!(
+            object_keys(&schema["definitions"]["hook"]["anyOf"][1]),
+            value_keys(&hook)
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_found_at_any_depth() {
+        let document: Yaml = serde_yaml::from_str(
+            r#"
+main_branch: main
+panes:
+  - command: vim
+    fucus: true
+post_create:
+  - pnpm install
+  - command: make
+    timeot: 10
+files:
+  copy:
+    - path: .env
+      mod: copy
+profiles:
+  review:
+    agnet: codex
+colour: red
+"#,
+        )
+        .unwrap();
+        let found: Vec<String> = unknown_keys(&document, &schema())
+            .iter()
+            .map(|p| display_path(p))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                "panes[0].fucus",
+                "post_create[1].timeot",
+                "files.copy[0].mod",
+                "profiles.review.agnet",
+                "colour",
+            ]
+        );
+    }
+
+    #[test]
+    fn locate_points_at_nested_keys_and_items() {
+        let source = "\
+# comment
+main_branch: main
+panes:
+  - command: vim
+    focus: true
+  -
+    split: horizontal
+files:
+  copy:
+  - .env
+  - path: secrets
+    mode: copy
+";
+        assert_eq!(locate(source, &[key("main_branch")]), Some((2, 1)));
+        assert_eq!(
+            locate(source, &[key("panes"), Segment::Index(0), key("focus")]),
+            Some((5, 5))
+        );
+        assert_eq!(
+            locate(source, &[key("panes"), Segment::Index(1), key("split")]),
+            Some((7, 5))
+        );
+        assert_eq!(
+            locate(
+                source,
+                &[key("files"), key("copy"), Segment::Index(1), key("mode")]
+            ),
+            Some((12, 5))
+        );
+        assert_eq!(locate(source, &[key("focus")]), None);
+    }
+}
@@ -0,0 +1,561 @@
+//! Code forge abstraction for pull/merge request lookups.
+//!
+//! GitHub is reached through `gh`, GitLab through `glab` and Gitea/Forgejo
+//! through its REST API. The forge is taken from the `forge` config option, or
+//! detected from the origin remote's host.
+
+use anyhow::{Context, Result, anyhow};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, ForgeKind, GiteaConfig};
+use crate::github::{PrDetails, PrSummary};
+use crate::{git, gitea, github, gitlab};
+
+/// A PR to open with [`Forge::create_pr`]
+pub struct NewPr<'a> {
+    pub head: &'a str,
+    pub base: &'a str,
+    pub title: &'a str,
+    pub body: &'a str,
+    pub draft: bool,
+    pub labels: &'a [String],
+}
+
+/// A PR that was just opened
+pub struct CreatedPr {
+    pub number: u32,
+    pub url: String,
+}
+
+/// A failed CI check, with its log when the forge exposes it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedCheck {
+    pub name: String,
+    pub url: Option<String>,
+    pub log: Option<String>,
+}
+
+/// An unresolved review thread on a PR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewThread {
+    pub path: String,
+    /// Line in the PR's version of the file; None for file-level comments and
+    /// threads whose line no longer exists
+    pub line: Option<u32>,
+    pub comments: Vec<ReviewComment>,
+}
+
+/// A comment in a review thread
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewComment {
+    pub author: String,
+    pub body: String,
+}
+
+/// An issue to start a worktree from
+#[derive(Debug, Clone, Serialize)]
+pub struct Issue {
+    pub number: u32,
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>,
+    pub url: String,
+}
+
+/// Outcome of a single check run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Success,
+    Failure,
+    Pending,
+    Skipped,
+}
+
+/// A single CI job, workflow job or commit status on a PR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckRun {
+    pub name: String,
+    pub status: CheckStatus,
+    /// Unix timestamps; None when the check hasn't started or finished yet
+    pub started_at: Option<u64>,
+    pub completed_at: Option<u64>,
+}
+
+impl CheckRun {
+    /// How long the check ran, or has been running at `now`
+    pub fn duration(&self, now: u64) -> Option<u64> {
+        let started_at = self.started_at?;
+        Some(self.completed_at.unwrap_or(now).saturating_sub(started_at))
+    }
+}
+
+/// Where a PR stands with its reviewers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewDecision {
+    Approved,
+    ChangesRequested,
+    ReviewRequired,
+}
+
+/// A PR with its reviews and individual checks, for the dashboard's PR panel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrOverview {
+    pub number: u32,
+    pub title: String,
+    pub url: String,
+    pub state: String,
+    pub is_draft: bool,
+    /// None when no review is required and nobody has reviewed yet
+    pub review_decision: Option<ReviewDecision>,
+    /// Reviewers (users or teams) who haven't reviewed yet
+    pub requested_reviewers: Vec<String>,
+    pub checks: Vec<CheckRun>,
+    /// Whether the PR merges cleanly; None while the forge is still computing it
+    pub mergeable: Option<bool>,
+}
+
+/// How a PR is merged on the forge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeMethod {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+/// Operations workmux needs from a code forge.
+///
+/// GitLab merge requests are mapped onto the same types as GitHub pull
+/// requests, as are Gitea pull requests, with GitHub's state names (OPEN,
+/// MERGED, CLOSED).
+pub trait Forge {
+    /// What the forge calls a change request ("PR" or "MR")
+    fn request_label(&self) -> &'static str;
+
+    /// Find a PR whose head branch is `branch` in `owner`'s repository
+    fn find_pr_by_head_ref(&self, owner: &str, branch: &str) -> Result<Option<PrSummary>>;
+
+    /// Fetch the details needed to check out a PR
+    fn get_pr_details(&self, number: u32) -> Result<PrDetails>;
+
+    /// Fetch an issue's title, body and labels
+    fn get_issue(&self, number: u32) -> Result<Issue>;
+
+    /// List PRs for a repository, keyed by head branch, with their checks rollup
+    fn list_prs(&self, repo_root: &Path) -> Result<HashMap<String, PrSummary>>;
+
+    /// Make sure the PR's head branch can be fetched and return the remote to
+    /// fetch it from (origin, or a remote added for the fork)
+    fn checkout_remote(&self, pr: &PrDetails) -> Result<String>;
+
+    /// Open a PR for a pushed branch
+    fn create_pr(&self, workdir: &Path, pr: &NewPr) -> Result<CreatedPr>;
+
+    /// The most recent PR from `branch`, with its checks rollup
+    fn pr_status(&self, workdir: &Path, branch: &str) -> Result<Option<PrSummary>>;
+
+    /// Merge a PR, or enable auto-merge so the forge merges it once its
+    /// checks pass
+    fn merge_pr(&self, workdir: &Path, number: u32, method: MergeMethod, auto: bool) -> Result<()>;
+
+    /// Failed checks from the latest CI run on `branch`
+    fn failed_checks(&self, workdir: &Path, branch: &str) -> Result<Vec<FailedCheck>>;
+
+    /// Unresolved review threads on a PR, oldest first
+    fn review_threads(&self, workdir: &Path, number: u32) -> Result<Vec<ReviewThread>>;
+
+    /// A PR's title, review state, individual checks and mergeability
+    fn pr_overview(&self, workdir: &Path, number: u32) -> Result<PrOverview>;
+
+    /// Re-run the failed checks of the latest CI run on `branch`. Returns the
+    /// number of runs (workflow runs or pipelines) restarted.
+    fn rerun_failed_checks(&self, workdir: &Path, branch: &str) -> Result<usize>;
+
+    /// Take a draft PR out of draft
+    fn mark_ready(&self, workdir: &Path, number: u32) -> Result<()>;
+}
+
+pub struct GitHub;
+
+impl Forge for GitHub {
+    fn request_label(&self) -> &'static str {
+        "PR"
+    }
+
+    fn find_pr_by_head_ref(&self, owner: &str, branch: &str) -> Result<Option<PrSummary>> {
+        github::find_pr_by_head_ref(owner, branch)
+    }
+
+    fn get_pr_details(&self, number: u32) -> Result<PrDetails> {
+        github::get_pr_details(number)
+    }
+
+    fn get_issue(&self, number: u32) -> Result<Issue> {
+        github::get_issue(number)
+    }
+
+    fn list_prs(&self, repo_root: &Path) -> Result<HashMap<String, PrSummary>> {
+        github::list_prs_in_repo(repo_root)
+    }
+
+    fn checkout_remote(&self, pr: &PrDetails) -> Result<String> {
+        let current_repo_owner = git::get_repo_owner()
+            .context("Failed to determine repository owner from origin remote")?;
+        if pr.is_fork(&current_repo_owner) {
+            git::ensure_fork_remote(&pr.head_repository_owner.login)
+        } else {
+            Ok("origin".to_string())
+        }
+    }
+
+    fn create_pr(&self, workdir: &Path, pr: &NewPr) -> Result<CreatedPr> {
+        let url = github::create_pr(workdir, pr)?;
+        created_from_url(url)
+    }
+
+    fn pr_status(&self, workdir: &Path, branch: &str) -> Result<Option<PrSummary>> {
+        github::pr_status(workdir, branch)
+    }
+
+    fn merge_pr(&self, workdir: &Path, number: u32, method: MergeMethod, auto: bool) -> Result<()> {
+        github::merge_pr(workdir, number, method, auto)
+    }
+
+    fn failed_checks(&self, workdir: &Path, branch: &str) -> Result<Vec<FailedCheck>> {
+        github::failed_checks(workdir, branch)
+    }
+
+    fn review_threads(&self, workdir: &Path, number: u32) -> Result<Vec<ReviewThread>> {
+        github::review_threads(workdir, number)
+    }
+
+    fn pr_overview(&self, workdir: &Path, number: u32) -> Result<PrOverview> {
+        github::pr_overview(workdir, number)
+    }
+
+    fn rerun_failed_checks(&self, workdir: &Path, branch: &str) -> Result<usize> {
+        github::rerun_failed_checks(workdir, branch)
+    }
+
+    fn mark_ready(&self, workdir: &Path, number: u32) -> Result<()> {
+        github::mark_ready(workdir, number)
+    }
+}
+
+pub struct GitLab;
+
+impl Forge for GitLab {
+    fn request_label(&self) -> &'static str {
+        "MR"
+    }
+
+    fn find_pr_by_head_ref(&self, owner: &str, branch: &str) -> Result<Option<PrSummary>> {
+        gitlab::find_pr_by_head_ref(owner, branch)
+    }
+
+    fn get_pr_details(&self, number: u32) -> Result<PrDetails> {
+        gitlab::get_pr_details(number)
+    }
+
+    fn get_issue(&self, number: u32) -> Result<Issue> {
+        gitlab::get_issue(number)
+    }
+
+    fn list_prs(&self, repo_root: &Path) -> Result<HashMap<String, PrSummary>> {
+        gitlab::list_prs_in_repo(repo_root)
+    }
+
+    fn checkout_remote(&self, pr: &PrDetails) -> Result<String> {
+        fork_remote_from_url(pr)
+    }
+
+    fn create_pr(&self, workdir: &Path, pr: &NewPr) -> Result<CreatedPr> {
+        let url = gitlab::create_merge_request(workdir, pr)?;
+        created_from_url(url)
+    }
+
+    fn pr_status(&self, workdir: &Path, branch: &str) -> Result<Option<PrSummary>> {
+        gitlab::pr_status(workdir, branch)
+    }
+
+    fn merge_pr(&self, workdir: &Path, number: u32, method: MergeMethod, auto: bool) -> Result<()> {
+        gitlab::merge_merge_request(workdir, number, method, auto)
+    }
+
+    fn failed_checks(&self, workdir: &Path, branch: &str) -> Result<Vec<FailedCheck>> {
+        gitlab::failed_checks(workdir, branch)
+    }
+
+    fn review_threads(&self, workdir: &Path, number: u32) -> Result<Vec<ReviewThread>> {
+        gitlab::review_threads(workdir, number)
+    }
+
+    fn pr_overview(&self, workdir: &Path, number: u32) -> Result<PrOverview> {
+        gitlab::pr_overview(workdir, number)
+    }
+
+    fn rerun_failed_checks(&self, workdir: &Path, branch: &str) -> Result<usize> {
+        gitlab::rerun_failed_checks(workdir, branch)
+    }
+
+    fn mark_ready(&self, workdir: &Path, number: u32) -> Result<()> {
+        gitlab::mark_ready(workdir, number)
+    }
+}
+
+pub struct Gitea {
+    repo_root: Option<PathBuf>,
+    config: GiteaConfig,
+}
+
+impl Gitea {
+    fn client(&self) -> Result<gitea::Client> {
+        gitea::Client::for_repo(self.repo_root.as_deref(), &self.config)
+    }
+}
+
+impl Forge for Gitea {
+    fn request_label(&self) -> &'static str {
+        "PR"
+    }
+
+    fn find_pr_by_head_ref(&self, owner: &str, branch: &str) -> Result<Option<PrSummary>> {
+        self.client()?.find_pr_by_head_ref(owner, branch)
+    }
+
+    fn get_pr_details(&self, number: u32) -> Result<PrDetails> {
+        let origin_url = git::get_remote_url_in("origin", self.repo_root.as_deref())?;
+        self.client()?.get_pr_details(number, &origin_url)
+    }
+
+    fn get_issue(&self, number: u32) -> Result<Issue> {
+        self.client()?.get_issue(number)
+    }
+
+    fn list_prs(&self, _repo_root: &Path) -> Result<HashMap<String, PrSummary>> {
+        self.client()?.list_prs()
+    }
+
+    fn checkout_remote(&self, pr: &PrDetails) -> Result<String> {
+        fork_remote_from_url(pr)
+    }
+
+    fn create_pr(&self, _workdir: &Path, pr: &NewPr) -> Result<CreatedPr> {
+        self.client()?.create_pr(pr)
+    }
+
+    fn pr_status(&self, _workdir: &Path, branch: &str) -> Result<Option<PrSummary>> {
+        self.client()?.pr_status(branch)
+    }
+
+    fn merge_pr(
+        &self,
+        _workdir: &Path,
+        number: u32,
+        method: MergeMethod,
+        auto: bool,
+    ) -> Result<()> {
+        self.client()?.merge_pr(number, method, auto)
+    }
+
+    fn failed_checks(&self, _workdir: &Path, branch: &str) -> Result<Vec<FailedCheck>> {
+        self.client()?.failed_checks(branch)
+    }
+
+    fn review_threads(&self, _workdir: &Path, number: u32) -> Result<Vec<ReviewThread>> {
+        self.client()?.review_threads(number)
+    }
+
+    fn pr_overview(&self, _workdir: &Path, number: u32) -> Result<PrOverview> {
+        self.client()?.pr_overview(number)
+    }
+
+    fn rerun_failed_checks(&self, _workdir: &Path, _branch: &str) -> Result<usize> {
+        Err(anyhow!(
+            "Gitea has no API for re-running checks; re-run them from the PR page"
+        ))
+    }
+
+    fn mark_ready(&self, _workdir: &Path, number: u32) -> Result<()> {
+        self.client()?.mark_ready(number)
+    }
+}
+
+/// The PR number is the last segment of its web URL (.../pull/12, .../-/merge_requests/12)
+fn created_from_url(url: String) -> Result<CreatedPr> {
+    let number = url
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| anyhow!("Could not find the PR number in '{}'", url))?;
+    Ok(CreatedPr { number, url })
+}
+
+/// Set up a remote for a fork PR from the clone URL the forge reported, or use
+/// origin when the PR is not from a fork
+fn fork_remote_from_url(pr: &PrDetails) -> Result<String> {
+    let Some(url) = &pr.head_repository_url else {
+        return Ok("origin".to_string());
+    };
+    // GitLab namespaces can be nested groups; keep the remote name free of
+    // slashes so "remote/branch" stays unambiguous
+    let remote_name = format!("fork-{}", pr.head_repository_owner.login.replace('/', "-"));
+    git::ensure_remote(&remote_name, url).with_context(|| {
+        format!(
+            "Failed to set up remote for fork '{}'",
+            pr.head_repository_owner.login
+        )
+    })?;
+    Ok(remote_name)
+}
+
+/// Unix time of an RFC 3339 timestamp ("2024-05-01T12:30:00Z",
+/// "2024-05-01T14:30:00.123+02:00"). None for malformed timestamps and the
+/// zero dates forges report for checks that haven't finished.
+pub fn parse_timestamp(timestamp: &str) -> Option<u64> {
+    let (date, time) = timestamp.split_once('T')?;
+    let mut date_parts = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (
+        date_parts.next()??,
+        date_parts.next()??,
+        date_parts.next()??,
+    );
+
+    let (clock, offset) = match time.find(['Z', 'z', '+', '-']) {
+        Some(i) => time.split_at(i),
+        None => return None,
+    };
+    let mut clock_parts = clock.splitn(3, ':');
+    let hour: i64 = clock_parts.next()?.parse().ok()?;
+    let minute: i64 = clock_parts.next()?.parse().ok()?;
+    // Drop fractional seconds
+    let second: i64 = clock_parts.next()?.split('.').next()?.parse().ok()?;
+    let offset_secs = match offset {
+        "Z" | "z" => 0,
+        _ => {
+            let (hours, minutes) = offset[1..].split_once(':')?;
+            let secs = hours.parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60;
+            if offset.starts_with('-') { -secs } else { secs }
+        }
+    };
+
+    // Days since the epoch for a proleptic Gregorian date (Howard Hinnant's
+    // days_from_civil)
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    let secs = days * 86_400 + hour * 3600 + minute * 60 + second - offset_secs;
+    u64::try_from(secs).ok().filter(|&secs| secs > 0)
+}
+
+/// Guess the forge from a remote URL's host
+pub fn detect_kind(url: &str) -> Option<ForgeKind> {
+    let parsed = git_url_parse::GitUrl::parse(url).ok()?;
+    let host = parsed.host()?.to_ascii_lowercase();
+    if host.contains("gitlab") {
+        Some(ForgeKind::Gitlab)
+    } else if host.contains("github") {
+        Some(ForgeKind::Github)
+    } else if host.contains("gitea") || host.contains("forgejo") || host == "codeberg.org" {
+        Some(ForgeKind::Gitea)
+    } else {
+        None
+    }
+}
+
+/// The forge for a repository (the current directory's when `repo_root` is None).
+///
+/// The `forge` config option wins; otherwise the origin URL decides, falling
+/// back to GitHub.
+pub fn for_repo(repo_root: Option<&Path>, config: &Config) -> Box<dyn Forge> {
+    let kind = config
+        .forge
+        .or_else(|| {
+            git::get_remote_url_in("origin", repo_root)
+                .ok()
+                .and_then(|url| detect_kind(&url))
+        })
+        .unwrap_or(ForgeKind::Github);
+
+    match kind {
+        ForgeKind::Github => Box::new(GitHub),
+        ForgeKind::Gitlab => Box::new(GitLab),
+        ForgeKind::Gitea => Box::new(Gitea {
+            repo_root: repo_root.map(Path::to_path_buf),
+            config: config.gitea.clone().unwrap_or_default(),
+        }),
+    }
+}
+
+/// The forge for the current repository, honoring the `forge` config option
+pub fn current() -> Box<dyn Forge> {
+    let config = Config::load(None).unwrap_or_default();
+    for_repo(None, &config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn created_from_url_parses_the_number() {
+        let created = created_from_url("https://github.com/o/r/pull/42".to_string()).unwrap();
+        assert_eq!(created.number, 42);
+        let created =
+            created_from_url("https://gitlab.com/g/r/-/merge_requests/7/".to_string()).unwrap();
+        assert_eq!(created.number, 7);
+        assert!(created_from_url("https://github.com/o/r".to_string()).is_err());
+    }
+
+    #[test]
+    fn parse_timestamp_handles_offsets_and_zero_dates() {
+        assert_eq!(parse_timestamp("1970-01-02T00:00:00Z"), Some(86_400));
+        assert_eq!(parse_timestamp("2024-05-01T12:30:00Z"), Some(1_714_566_600));
+        assert_eq!(
+            parse_timestamp("2024-05-01T14:30:00.123+02:00"),
+            Some(1_714_566_600)
+        );
+        assert_eq!(
+            parse_timestamp("2024-05-01T07:30:00-05:00"),
+            Some(1_714_566_600)
+        );
+        assert_eq!(parse_timestamp("0001-01-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("yesterday"), None);
+    }
+
+    #[test]
+    fn detect_kind_from_remote_host() {
+        assert_eq!(
+            detect_kind("git@github.com:owner/repo.git"),
+            Some(ForgeKind::Github)
+        );
+        assert_eq!(
+            detect_kind("https://github.example.com/owner/repo"),
+            Some(ForgeKind::Github)
+        );
+        assert_eq!(
+            detect_kind("git@gitlab.com:group/sub/repo.git"),
+            Some(ForgeKind::Gitlab)
+        );
+        assert_eq!(
+            detect_kind("https://gitlab.corp.example/group/repo.git"),
+            Some(ForgeKind::Gitlab)
+        );
+        assert_eq!(
+            detect_kind("https://codeberg.org/owner/repo.git"),
+            Some(ForgeKind::Gitea)
+        );
+        assert_eq!(
+            detect_kind("ssh://git@forgejo.example.com:2222/owner/repo.git"),
+            Some(ForgeKind::Gitea)
+        );
+        assert_eq!(detect_kind("git@git.example.com:team/repo.git"), None);
+    }
+}
@@ -1,12 +1,12 @@
 use anyhow::{Context, Result, anyhow};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use tracing::debug;
 
 use crate::cmd::Cmd;
 
 use super::repo::has_commits;
-use super::{ForkBranchSpec, RemoteBranchSpec};
+use super::{BranchTip, ForkBranchSpec, RemoteBranchSpec};
 
 /// Get the default branch (main or master)
 pub fn get_default_branch() -> Result<String> {
@@ -231,6 +231,38 @@ pub fn get_gone_branches() -> Result<HashSet<String>> {
     Ok(gone)
 }
 
+/// Get the tip commit (SHA and committer timestamp) of every local branch.
+pub fn get_branch_tips() -> Result<HashMap<String, BranchTip>> {
+    let output = Cmd::new("git")
+        .args(&[
+            "for-each-ref",
+            "--format=%(refname:short)|%(objectname)|%(committerdate:unix)",
+            "refs/heads",
+        ])
+        .run_and_capture_stdout()?;
+
+    Ok(parse_branch_tips(&output))
+}
+
+fn parse_branch_tips(output: &str) -> HashMap<String, BranchTip> {
+    let mut tips = HashMap::new();
+    for line in output.lines() {
+        let mut parts = line.splitn(3, '|');
+        if let (Some(branch), Some(sha), Some(ts)) = (parts.next(), parts.next(), parts.next())
+            && let Ok(committed_at) = ts.trim().parse::<i64>()
+        {
+            tips.insert(
+                branch.to_string(),
+                BranchTip {
+                    sha: sha.to_string(),
+                    committed_at,
+                },
+            );
+        }
+    }
+    tips
+}
+
 /// Unset the upstream tracking for a branch
 pub fn unset_branch_upstream(branch_name: &str) -> Result<()> {
     if !branch_has_upstream(branch_name)? {
@@ -300,10 +332,96 @@ pub fn get_branch_base_in(branch: &str, workdir: Option<&Path>) -> Result<String
     Ok(output)
 }
 
+/// Store the config profile a branch's worktree was created with
+pub fn set_branch_profile(branch: &str, profile: &str) -> Result<()> {
+    Cmd::new("git")
+        .args(&[
+            "config",
+            "--local",
+            &format!("branch.{}.workmux-profile", branch),
+            profile,
+        ])
+        .run()
+        .context("Failed to set workmux-profile config")?;
+    Ok(())
+}
+
+/// Retrieve the config profile a branch's worktree was created with, if any
+pub fn get_branch_profile(branch: &str) -> Option<String> {
+    let config_key = format!("branch.{}.workmux-profile", branch);
+    Cmd::new("git")
+        .args(&["config", "--local", &config_key])
+        .run_and_capture_stdout()
+        .ok()
+        .filter(|profile| !profile.is_empty())
+}
+
+/// Record the labels to add to the PR opened for a branch
+pub fn set_branch_labels(branch: &str, labels: &[String]) -> Result<()> {
+    let config_key = format!("branch.{}.workmux-label", branch);
+    // Fails when there are no labels yet, which is fine
+    let _ = Cmd::new("git")
+        .args(&["config", "--local", "--unset-all", &config_key])
+        .run();
+    for label in labels {
+        Cmd::new("git")
+            .args(&["config", "--local", "--add", &config_key, label])
+            .run()
+            .context("Failed to set workmux-label config")?;
+    }
+    Ok(())
+}
+
+/// Retrieve the labels recorded for a branch's PR
+pub fn get_branch_labels_in(branch: &str, workdir: &Path) -> Vec<String> {
+    let config_key = format!("branch.{}.workmux-label", branch);
+    Cmd::new("git")
+        .workdir(workdir)
+        .args(&["config", "--local", "--get-all", &config_key])
+        .run_and_capture_stdout()
+        .map(|output| output.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Record the number of the PR opened for a branch
+pub fn set_branch_pr(worktree_path: &Path, branch: &str, number: u32) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&[
+            "config",
+            "--local",
+            &format!("branch.{}.workmux-pr", branch),
+            &number.to_string(),
+        ])
+        .run()
+        .context("Failed to set workmux-pr config")?;
+    Ok(())
+}
+
+/// Retrieve the number of the PR opened for a branch with `workmux pr create`, if any
+pub fn get_branch_pr_in(branch: &str, workdir: Option<&Path>) -> Option<u32> {
+    let config_key = format!("branch.{}.workmux-pr", branch);
+    let cmd = Cmd::new("git").args(&["config", "--local", &config_key]);
+    let cmd = match workdir {
+        Some(path) => cmd.workdir(path),
+        None => cmd,
+    };
+    cmd.run_and_capture_stdout().ok()?.parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_branch_tips() {
+        let output = "main|abc123|1700000000\nfeature/x|def456|1700000500\nbroken|zzz|nope";
+        let tips = parse_branch_tips(output);
+        assert_eq!(tips.len(), 2);
+        assert_eq!(tips["main"].sha, "abc123");
+        assert_eq!(tips["feature/x"].committed_at, 1700000500);
+    }
+
     #[test]
     fn test_parse_fork_branch_spec_valid() {
         let spec = parse_fork_branch_spec("someuser:feature-branch").unwrap();
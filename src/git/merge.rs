@@ -19,6 +19,31 @@ pub fn commit_with_editor(worktree_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Commit staged changes with a prepared message, opening the user's editor to review it
+pub fn commit_with_editor_message(worktree_path: &Path, message: &str) -> Result<()> {
+    let status = Command::new("git")
+        .current_dir(worktree_path)
+        .args(["commit", "--edit", "-m", message])
+        .status()
+        .context("Failed to run git commit")?;
+
+    if !status.success() {
+        return Err(anyhow!("Commit was aborted or failed"));
+    }
+
+    Ok(())
+}
+
+/// Commit staged changes with the given message, without opening an editor
+pub fn commit_with_message(worktree_path: &Path, message: &str) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["commit", "-m", message])
+        .run()
+        .context("Failed to commit")?;
+    Ok(())
+}
+
 /// Merge a branch into the current branch in a specific worktree
 pub fn merge_in_worktree(worktree_path: &Path, branch_name: &str) -> Result<()> {
     Cmd::new("git")
@@ -29,6 +54,16 @@ pub fn merge_in_worktree(worktree_path: &Path, branch_name: &str) -> Result<()>
     Ok(())
 }
 
+/// Fast-forward the branch checked out in a worktree to `upstream`
+pub fn fast_forward_in_worktree(worktree_path: &Path, upstream: &str) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["merge", "--ff-only", upstream])
+        .run()
+        .with_context(|| format!("Failed to fast-forward to '{}'", upstream))?;
+    Ok(())
+}
+
 /// Rebase the current branch in a worktree onto a base branch
 pub fn rebase_branch_onto_base(worktree_path: &Path, base_branch: &str) -> Result<()> {
     Cmd::new("git")
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result, anyhow};
 use git_url_parse::GitUrl;
 use git_url_parse::types::provider::GenericProvider;
+use std::path::Path;
 use tracing::info;
 
 use crate::cmd::Cmd;
@@ -34,6 +35,14 @@ pub fn fetch_remote(remote: &str) -> Result<()> {
     Ok(())
 }
 
+/// The remote a branch tracks
+pub fn get_branch_remote(branch: &str) -> Result<String> {
+    Cmd::new("git")
+        .args(&["config", "--get", &format!("branch.{}.remote", branch)])
+        .run_and_capture_stdout()
+        .with_context(|| format!("Branch '{}' does not track a remote", branch))
+}
+
 /// Fetch from remote with prune to update remote-tracking refs
 pub fn fetch_prune() -> Result<()> {
     Cmd::new("git")
@@ -43,6 +52,16 @@ pub fn fetch_prune() -> Result<()> {
     Ok(())
 }
 
+/// Push a branch from a worktree and set it to track the remote branch
+pub fn push_branch(worktree_path: &Path, remote: &str, branch: &str) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["push", "--set-upstream", remote, branch])
+        .run()
+        .with_context(|| format!("Failed to push '{}' to remote '{}'", branch, remote))?;
+    Ok(())
+}
+
 /// Add a git remote if it doesn't exist
 pub fn add_remote(name: &str, url: &str) -> Result<()> {
     Cmd::new("git")
@@ -64,10 +83,18 @@ pub fn set_remote_url(name: &str, url: &str) -> Result<()> {
 /// Get the remote URL for a given remote name
 /// Note: Returns the configured URL, not the resolved URL after insteadOf substitution
 pub fn get_remote_url(remote: &str) -> Result<String> {
+    get_remote_url_in(remote, None)
+}
+
+/// Get the remote URL for a given remote name in a specific repository
+pub fn get_remote_url_in(remote: &str, workdir: Option<&Path>) -> Result<String> {
     // Use git config to get the raw URL, not the insteadOf-resolved one
     // git remote get-url resolves insteadOf, which breaks our owner parsing in tests
-    Cmd::new("git")
-        .args(&["config", "--get", &format!("remote.{}.url", remote)])
+    let mut cmd = Cmd::new("git");
+    if let Some(dir) = workdir {
+        cmd = cmd.workdir(dir);
+    }
+    cmd.args(&["config", "--get", &format!("remote.{}.url", remote)])
         .run_and_capture_stdout()
         .with_context(|| format!("Failed to get URL for remote '{}'", remote))
 }
@@ -83,10 +110,18 @@ pub fn ensure_fork_remote(fork_owner: &str) -> Result<String> {
     }
 
     let remote_name = format!("fork-{}", fork_owner);
-
-    // Construct fork URL based on origin URL format, preserving host and protocol
     let origin_url = get_remote_url("origin")?;
-    let parsed_url = GitUrl::parse(&origin_url).with_context(|| {
+    let fork_url = fork_url_from_origin(&origin_url, fork_owner)?;
+
+    ensure_remote(&remote_name, &fork_url)
+        .with_context(|| format!("Failed to set up remote for fork '{}'", fork_owner))?;
+    Ok(remote_name)
+}
+
+/// Construct a fork's clone URL from the origin URL, preserving its protocol,
+/// host, port and SSH user (self-hosted forges often run SSH on another port)
+fn fork_url_from_origin(origin_url: &str, fork_owner: &str) -> Result<String> {
+    let parsed_url = GitUrl::parse(origin_url).with_context(|| {
         format!(
             "Failed to parse origin URL for fork remote construction: {}",
             origin_url
@@ -95,36 +130,48 @@ pub fn ensure_fork_remote(fork_owner: &str) -> Result<String> {
 
     let host = parsed_url.host().unwrap_or("github.com");
     let scheme = parsed_url.scheme().unwrap_or("ssh");
+    let port = parsed_url
+        .port()
+        .map(|p| format!(":{}", p))
+        .unwrap_or_default();
 
     let provider: GenericProvider = parsed_url
         .provider_info()
         .with_context(|| "Failed to extract provider info from origin URL")?;
     let repo_name = provider.repo();
 
-    let fork_url = match scheme {
-        "https" => format!("https://{}/{}/{}.git", host, fork_owner, repo_name),
-        "http" => format!("http://{}/{}/{}.git", host, fork_owner, repo_name),
+    Ok(match scheme {
+        "https" | "http" => format!(
+            "{}://{}{}/{}/{}.git",
+            scheme, host, port, fork_owner, repo_name
+        ),
         _ => {
-            // SSH or other schemes
-            format!("git@{}:{}/{}.git", host, fork_owner, repo_name)
+            // SSH or other schemes. The scp-like form can't carry a port.
+            let user = parsed_url.user().unwrap_or("git");
+            if port.is_empty() {
+                format!("{}@{}:{}/{}.git", user, host, fork_owner, repo_name)
+            } else {
+                format!(
+                    "ssh://{}@{}{}/{}/{}.git",
+                    user, host, port, fork_owner, repo_name
+                )
+            }
         }
-    };
-
-    // Check if remote exists and update URL if needed
-    if remote_exists(&remote_name)? {
-        let current_url = get_remote_url(&remote_name)?;
-        if current_url != fork_url {
-            info!(remote = %remote_name, url = %fork_url, "git:updating fork remote URL");
-            set_remote_url(&remote_name, &fork_url)
-                .with_context(|| format!("Failed to update remote for fork '{}'", fork_owner))?;
+    })
+}
+
+/// Add a remote with the given URL, or point an existing remote at it
+pub fn ensure_remote(name: &str, url: &str) -> Result<()> {
+    if remote_exists(name)? {
+        if get_remote_url(name)? != url {
+            info!(remote = %name, url = %url, "git:updating remote URL");
+            set_remote_url(name, url)?;
         }
     } else {
-        info!(remote = %remote_name, url = %fork_url, "git:adding fork remote");
-        add_remote(&remote_name, &fork_url)
-            .with_context(|| format!("Failed to add remote for fork '{}'", fork_owner))?;
+        info!(remote = %name, url = %url, "git:adding remote");
+        add_remote(name, url)?;
     }
-
-    Ok(remote_name)
+    Ok(())
 }
 
 /// Parse the repository owner from a git remote URL
@@ -133,8 +180,10 @@ fn parse_owner_from_git_url(url: &str) -> Option<&str> {
     if let Some(https_part) = url
         .strip_prefix("https://")
         .or_else(|| url.strip_prefix("http://"))
+        .or_else(|| url.strip_prefix("ssh://"))
     {
         // HTTPS format: https://github.com/owner/repo.git or https://github.enterprise.com/owner/repo.git
+        // SSH URL format: ssh://git@git.example.com:2222/owner/repo.git
         https_part.split('/').nth(1)
     } else if url.starts_with("git@") {
         // SSH format: git@github.com:owner/repo.git or git@github.enterprise.com:owner/repo.git
@@ -146,6 +195,15 @@ fn parse_owner_from_git_url(url: &str) -> Option<&str> {
     }
 }
 
+/// Extract the repository path (e.g. "owner/repo" or "group/subgroup/repo")
+/// from a remote URL
+pub fn repo_path_from_url(url: &str) -> Option<String> {
+    let parsed = GitUrl::parse(url).ok()?;
+    let path = parsed.path().trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    path.contains('/').then(|| path.to_string())
+}
+
 /// Get the repository owner from the origin remote URL
 pub fn get_repo_owner() -> Result<String> {
     let url = get_remote_url("origin")?;
@@ -157,7 +215,48 @@ pub fn get_repo_owner() -> Result<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_owner_from_git_url;
+    use super::{fork_url_from_origin, parse_owner_from_git_url, repo_path_from_url};
+
+    #[test]
+    fn test_repo_path_from_url() {
+        assert_eq!(
+            repo_path_from_url("git@gitlab.com:group/sub/repo.git").as_deref(),
+            Some("group/sub/repo")
+        );
+        assert_eq!(
+            repo_path_from_url("https://gitlab.example.com/group/repo").as_deref(),
+            Some("group/repo")
+        );
+        assert_eq!(
+            repo_path_from_url("ssh://git@git.example.com:2222/owner/repo.git").as_deref(),
+            Some("owner/repo")
+        );
+    }
+
+    #[test]
+    fn test_fork_url_from_origin_preserves_protocol_and_host() {
+        assert_eq!(
+            fork_url_from_origin("git@github.com:owner/repo.git", "alice").unwrap(),
+            "git@github.com:alice/repo.git"
+        );
+        assert_eq!(
+            fork_url_from_origin("https://github.com/owner/repo", "alice").unwrap(),
+            "https://github.com/alice/repo.git"
+        );
+    }
+
+    #[test]
+    fn test_fork_url_from_origin_keeps_ssh_port_and_user() {
+        assert_eq!(
+            fork_url_from_origin("ssh://forgejo@git.example.com:2222/owner/repo.git", "alice")
+                .unwrap(),
+            "ssh://forgejo@git.example.com:2222/alice/repo.git"
+        );
+        assert_eq!(
+            fork_url_from_origin("https://git.example.com:3000/owner/repo.git", "alice").unwrap(),
+            "https://git.example.com:3000/alice/repo.git"
+        );
+    }
 
     #[test]
     fn test_parse_repo_owner_https_github_com() {
@@ -239,6 +338,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_repo_owner_ssh_url_with_port() {
+        assert_eq!(
+            parse_owner_from_git_url("ssh://git@git.example.com:2222/owner/repo.git"),
+            Some("owner")
+        );
+    }
+
     #[test]
     fn test_parse_repo_owner_invalid_format() {
         assert_eq!(parse_owner_from_git_url("not-a-valid-url"), None);
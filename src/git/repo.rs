@@ -15,6 +15,42 @@ pub fn is_path_ignored(repo_path: &Path, file_path: &str) -> bool {
         .unwrap_or(false)
 }
 
+/// List files tracked by git in a worktree, relative to the worktree root.
+pub fn list_tracked_files(worktree_path: &Path) -> Result<Vec<String>> {
+    let output = Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["ls-files", "-z"])
+        .run()
+        .context("Failed to list tracked files")?;
+    Ok(split_nul(&output.stdout))
+}
+
+/// List ignored files in a worktree, relative to the worktree root.
+/// Fully ignored directories are returned once with a trailing `/`.
+pub fn list_ignored_entries(worktree_path: &Path) -> Result<Vec<String>> {
+    let output = Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&[
+            "ls-files",
+            "-z",
+            "--others",
+            "--ignored",
+            "--exclude-standard",
+            "--directory",
+        ])
+        .run()
+        .context("Failed to list ignored files")?;
+    Ok(split_nul(&output.stdout))
+}
+
+fn split_nul(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .split(|b| *b == 0)
+        .filter(|s| !s.is_empty())
+        .map(|s| String::from_utf8_lossy(s).into_owned())
+        .collect()
+}
+
 /// Check if we're in a git repository
 pub fn is_git_repo() -> Result<bool> {
     Cmd::new("git")
@@ -6,7 +6,7 @@ use std::process::Command;
 use crate::cmd::Cmd;
 
 use super::GitStatus;
-use super::branch::{get_branch_base_in, get_default_branch_in};
+use super::branch::{get_branch_base_in, get_branch_pr_in, get_default_branch_in};
 
 /// Check if the worktree has uncommitted changes
 pub fn has_uncommitted_changes(worktree_path: &Path) -> Result<bool> {
@@ -73,6 +73,33 @@ pub fn has_unstaged_changes(worktree_path: &Path) -> Result<bool> {
     Ok(!no_changes)
 }
 
+/// Get the diff of the staged changes in a worktree
+pub fn get_staged_diff(worktree_path: &Path) -> Result<String> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["diff", "--cached"])
+        .run_and_capture_stdout()
+}
+
+/// Get the diff of a branch against the point where it forked from `base`
+pub fn get_branch_diff(worktree_path: &Path, base: &str) -> Result<String> {
+    let range = format!("{}...HEAD", base);
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["diff", &range])
+        .run_and_capture_stdout()
+}
+
+/// Get the subjects of the commits on HEAD that are not on `base`, oldest first
+pub fn get_commit_subjects(worktree_path: &Path, base: &str) -> Result<Vec<String>> {
+    let range = format!("{}..HEAD", base);
+    let output = Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["log", "--reverse", "--format=%s", &range])
+        .run_and_capture_stdout()?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
 /// Parse git status porcelain v2 output to extract branch info and dirty state.
 /// Returns (branch_name, ahead, behind, is_dirty, has_upstream).
 fn parse_porcelain_v2_status(output: &str) -> (Option<String>, usize, usize, bool, bool) {
@@ -298,6 +325,8 @@ pub fn get_git_status(worktree_path: &Path) -> GitStatus {
         .or_else(|| get_default_branch_in(Some(worktree_path)).ok())
         .unwrap_or_else(|| "main".to_string());
 
+    let pr_number = get_branch_pr_in(&branch, Some(worktree_path));
+
     // On the base branch: no branch-level diff, but still show uncommitted changes
     if branch == base_branch {
         let stats = get_diff_stats(worktree_path, &branch);
@@ -312,6 +341,7 @@ pub fn get_git_status(worktree_path: &Path) -> GitStatus {
             base_branch,
             branch: Some(branch),
             has_upstream,
+            pr_number,
             ..Default::default()
         };
     }
@@ -348,6 +378,7 @@ pub fn get_git_status(worktree_path: &Path) -> GitStatus {
         base_branch,
         branch: Some(branch),
         has_upstream,
+        pr_number,
     }
 }
 
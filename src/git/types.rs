@@ -12,6 +12,15 @@ pub struct ForkBranchSpec {
     pub branch: String,
 }
 
+/// Tip commit of a local branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchTip {
+    /// Full SHA of the tip commit
+    pub sha: String,
+    /// Committer timestamp of the tip commit (unix seconds)
+    pub committed_at: i64,
+}
+
 /// Custom error type for worktree not found
 #[derive(Debug, thiserror::Error)]
 #[error("Worktree not found: {0}")]
@@ -50,4 +59,7 @@ pub struct GitStatus {
     /// Whether the branch has an upstream tracking branch
     #[serde(default)]
     pub has_upstream: bool,
+    /// Number of the PR opened with `workmux pr create`, if any
+    #[serde(default)]
+    pub pr_number: Option<u32>,
 }
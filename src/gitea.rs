@@ -0,0 +1,970 @@
+//! Gitea and Forgejo pull requests, fetched through the REST API (`/api/v1`).
+
+use anyhow::{Context, Result, anyhow};
+use git_url_parse::GitUrl;
+use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::config::GiteaConfig;
+use crate::forge::{
+    CheckRun, CheckStatus, CreatedPr, FailedCheck, Issue, MergeMethod, NewPr, PrOverview,
+    ReviewComment, ReviewDecision, ReviewThread, parse_timestamp,
+};
+use crate::git;
+use crate::github::{Author, CheckState, PrDetails, PrSummary, RepositoryOwner};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Page size for list requests (Gitea's default maximum)
+const PAGE_SIZE: usize = 50;
+
+/// Pages fetched when listing PRs, matching the 200 PRs `gh pr list` fetches
+const MAX_PAGES: usize = 4;
+
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    number: u32,
+    title: String,
+    state: String,
+    #[serde(default)]
+    merged: bool,
+    #[serde(default)]
+    draft: bool,
+    head: Branch,
+    base: Branch,
+    user: Option<User>,
+    #[serde(default)]
+    html_url: String,
+    /// Null when no reviewer was requested
+    #[serde(default)]
+    requested_reviewers: Option<Vec<User>>,
+    #[serde(default)]
+    mergeable: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct Branch {
+    #[serde(rename = "ref")]
+    ref_name: String,
+    sha: String,
+    /// Missing when the head repository (a fork) has been deleted
+    repo: Option<Repository>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    full_name: String,
+    owner: User,
+    clone_url: String,
+    ssh_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CombinedStatus {
+    #[serde(default)]
+    statuses: Option<Vec<CommitStatus>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CommitStatus {
+    status: String,
+    #[serde(default)]
+    context: String,
+    #[serde(default)]
+    target_url: String,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaIssue {
+    number: u32,
+    title: String,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    labels: Vec<Label>,
+    #[serde(default)]
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Label {
+    #[serde(default)]
+    id: u64,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Review {
+    id: u64,
+    state: String,
+    user: Option<User>,
+    #[serde(default)]
+    dismissed: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewLineComment {
+    body: String,
+    user: Option<User>,
+    path: String,
+    /// Line in the new file; 0 when the comment is on a removed line
+    #[serde(default)]
+    position: u32,
+    /// Set once someone resolves the conversation
+    resolver: Option<User>,
+}
+
+impl PullRequest {
+    /// Map Gitea's open/closed + merged flag onto GitHub's states
+    fn state(&self) -> &'static str {
+        match (self.state.as_str(), self.merged) {
+            ("open", _) => "OPEN",
+            (_, true) => "MERGED",
+            _ => "CLOSED",
+        }
+    }
+
+    /// Older Gitea versions only mark drafts with a "WIP:" title prefix
+    fn is_draft(&self) -> bool {
+        let title = self.title.to_ascii_uppercase();
+        self.draft || title.starts_with("WIP:") || title.starts_with("[WIP]")
+    }
+
+    fn summary(&self, checks: Option<CheckState>) -> PrSummary {
+        PrSummary {
+            number: self.number,
+            title: self.title.clone(),
+            state: self.state().to_string(),
+            is_draft: self.is_draft(),
+            checks,
+        }
+    }
+
+    fn head_owner(&self) -> Option<&str> {
+        self.head.repo.as_ref().map(|r| r.owner.login.as_str())
+    }
+}
+
+/// Group unresolved comments by file and line, keeping the order of first
+/// appearance
+fn group_review_comments(comments: Vec<ReviewLineComment>) -> Vec<ReviewThread> {
+    let mut threads: Vec<ReviewThread> = Vec::new();
+    for comment in comments.into_iter().filter(|c| c.resolver.is_none()) {
+        let line = Some(comment.position).filter(|&line| line > 0);
+        let reply = ReviewComment {
+            author: comment.user.map(|u| u.login).unwrap_or_default(),
+            body: comment.body,
+        };
+        match threads
+            .iter_mut()
+            .find(|thread| thread.path == comment.path && thread.line == line)
+        {
+            Some(thread) => thread.comments.push(reply),
+            None => threads.push(ReviewThread {
+                path: comment.path,
+                line,
+                comments: vec![reply],
+            }),
+        }
+    }
+    threads
+}
+
+/// Map a commit status state onto a check status
+fn check_status(status: &str) -> CheckStatus {
+    match status {
+        "success" => CheckStatus::Success,
+        "failure" | "error" => CheckStatus::Failure,
+        "warning" | "skipped" => CheckStatus::Skipped,
+        _ => CheckStatus::Pending,
+    }
+}
+
+/// A commit status as a check run. Statuses are updated in place, so the
+/// time between creation and the last update stands in for the run time.
+fn check_run(status: CommitStatus) -> CheckRun {
+    let check = check_status(&status.status);
+    CheckRun {
+        name: status.context,
+        status: check,
+        started_at: status.created_at.as_deref().and_then(parse_timestamp),
+        completed_at: status
+            .updated_at
+            .as_deref()
+            .filter(|_| check != CheckStatus::Pending)
+            .and_then(parse_timestamp),
+    }
+}
+
+/// The latest verdict of each reviewer decides; one outstanding change
+/// request outweighs any approvals
+fn review_decision(reviews: &[Review], has_requested_reviewers: bool) -> Option<ReviewDecision> {
+    let mut verdicts: HashMap<&str, &str> = HashMap::new();
+    for review in reviews
+        .iter()
+        .filter(|review| !review.dismissed)
+        .filter(|review| matches!(review.state.as_str(), "APPROVED" | "REQUEST_CHANGES"))
+    {
+        if let Some(user) = &review.user {
+            verdicts.insert(&user.login, &review.state);
+        }
+    }
+
+    if verdicts.values().any(|&state| state == "REQUEST_CHANGES") {
+        Some(ReviewDecision::ChangesRequested)
+    } else if !verdicts.is_empty() {
+        Some(ReviewDecision::Approved)
+    } else if has_requested_reviewers {
+        Some(ReviewDecision::ReviewRequired)
+    } else {
+        None
+    }
+}
+
+/// A title without the "WIP:" or "[WIP]" prefix Gitea marks drafts with
+fn strip_wip_prefix(title: &str) -> &str {
+    let upper = title.to_ascii_uppercase();
+    ["WIP:", "[WIP]"]
+        .iter()
+        .find(|prefix| upper.starts_with(*prefix))
+        .map_or(title, |prefix| title[prefix.len()..].trim_start())
+}
+
+/// IDs of the named labels, which Gitea requires instead of names
+fn label_ids(available: &[Label], names: &[String]) -> Result<Vec<u64>> {
+    let mut ids = Vec::with_capacity(names.len());
+    let mut missing = Vec::new();
+    for name in names {
+        match available
+            .iter()
+            .find(|label| label.name.eq_ignore_ascii_case(name))
+        {
+            Some(label) => ids.push(label.id),
+            None => missing.push(name.as_str()),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(anyhow!(
+            "Label(s) not found in the repository: {}",
+            missing.join(", ")
+        ));
+    }
+    Ok(ids)
+}
+
+/// Aggregate commit statuses (including Gitea/Forgejo Actions) into a CheckState
+fn aggregate_statuses(statuses: &[CommitStatus]) -> Option<CheckState> {
+    if statuses.is_empty() {
+        return None;
+    }
+
+    let mut passed = 0u32;
+    let mut failed = 0u32;
+    let mut pending = 0u32;
+    let mut skipped = 0u32;
+
+    for status in statuses {
+        match check_status(&status.status) {
+            CheckStatus::Success => passed += 1,
+            CheckStatus::Failure => failed += 1,
+            CheckStatus::Skipped => skipped += 1,
+            CheckStatus::Pending => pending += 1,
+        }
+    }
+
+    let total = passed + failed + pending;
+    if total == 0 {
+        return if skipped > 0 {
+            Some(CheckState::Success)
+        } else {
+            None
+        };
+    }
+
+    Some(if failed > 0 {
+        CheckState::Failure { passed, total }
+    } else if pending > 0 {
+        CheckState::Pending { passed, total }
+    } else {
+        CheckState::Success
+    })
+}
+
+/// A Gitea/Forgejo API client scoped to one repository
+pub struct Client {
+    base_url: String,
+    repo: String,
+    token: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    fn new(base_url: &str, repo: &str, token: Option<String>) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(Some(HTTP_TIMEOUT))
+            .http_status_as_error(false)
+            .build()
+            .into();
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            repo: repo.to_string(),
+            token,
+            agent,
+        }
+    }
+
+    /// Client for the repository behind the origin remote of `repo_root`
+    /// (the current directory when None)
+    pub fn for_repo(repo_root: Option<&Path>, config: &GiteaConfig) -> Result<Self> {
+        let origin_url = git::get_remote_url_in("origin", repo_root)?;
+        let repo = git::repo_path_from_url(&origin_url)
+            .ok_or_else(|| anyhow!("Could not parse repository from origin URL: {}", origin_url))?;
+        let base_url = match &config.base_url {
+            Some(url) => url.clone(),
+            None => base_url_from_remote(&origin_url).ok_or_else(|| {
+                anyhow!(
+                    "Could not determine the Gitea URL from origin '{}'. Set gitea.base_url in config.",
+                    origin_url
+                )
+            })?,
+        };
+        Ok(Self::new(&base_url, &repo, config.token()))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/api/v1/repos/{}/{}", self.base_url, self.repo, path)
+    }
+
+    fn auth(&self) -> Option<String> {
+        self.token.as_ref().map(|token| format!("token {}", token))
+    }
+
+    fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = self.url(path);
+        let mut request = self.agent.get(&url).header("Accept", "application/json");
+        if let Some(auth) = self.auth() {
+            request = request.header("Authorization", &auth);
+        }
+        let response = request
+            .call()
+            .with_context(|| format!("Failed to reach {}", url))?;
+        Self::read_json(&url, response)
+    }
+
+    fn post<T: DeserializeOwned>(&self, path: &str, body: &serde_json::Value) -> Result<T> {
+        let text = self.post_text(path, body)?;
+        let url = self.url(path);
+        serde_json::from_str(&text).with_context(|| format!("Unexpected response from {}", url))
+    }
+
+    /// POST and return the raw response body, for endpoints that reply with
+    /// no JSON
+    fn post_text(&self, path: &str, body: &serde_json::Value) -> Result<String> {
+        let url = self.url(path);
+        self.send_json(self.agent.post(&url), &url, body)
+    }
+
+    fn patch_text(&self, path: &str, body: &serde_json::Value) -> Result<String> {
+        let url = self.url(path);
+        self.send_json(self.agent.patch(&url), &url, body)
+    }
+
+    fn send_json(
+        &self,
+        request: ureq::RequestBuilder<ureq::typestate::WithBody>,
+        url: &str,
+        body: &serde_json::Value,
+    ) -> Result<String> {
+        let mut request = request.header("Accept", "application/json");
+        if let Some(auth) = self.auth() {
+            request = request.header("Authorization", &auth);
+        }
+        let response = request
+            .send_json(body)
+            .with_context(|| format!("Failed to reach {}", url))?;
+        Self::read_text(url, response)
+    }
+
+    fn read_json<T: DeserializeOwned>(
+        url: &str,
+        response: ureq::http::Response<ureq::Body>,
+    ) -> Result<T> {
+        let text = Self::read_text(url, response)?;
+        serde_json::from_str(&text).with_context(|| format!("Unexpected response from {}", url))
+    }
+
+    fn read_text(url: &str, mut response: ureq::http::Response<ureq::Body>) -> Result<String> {
+        let status = response.status();
+        let text = response
+            .body_mut()
+            .read_to_string()
+            .context("Failed to read Gitea response")?;
+        if !status.is_success() {
+            let message = serde_json::from_str::<serde_json::Value>(&text)
+                .ok()
+                .and_then(|v| v["message"].as_str().map(str::to_string))
+                .unwrap_or_else(|| text.trim().to_string());
+            return Err(anyhow!(
+                "Gitea request to {} failed ({}): {}",
+                url,
+                status,
+                message
+            ));
+        }
+        Ok(text)
+    }
+
+    /// Open a PR. Gitea marks drafts with a "WIP:" title prefix.
+    pub fn create_pr(&self, pr: &NewPr) -> Result<CreatedPr> {
+        let title = if pr.draft {
+            format!("WIP: {}", pr.title)
+        } else {
+            pr.title.to_string()
+        };
+        let labels = if pr.labels.is_empty() {
+            Vec::new()
+        } else {
+            label_ids(&self.labels()?, pr.labels)?
+        };
+        let created: PullRequest = self.post(
+            "pulls",
+            &serde_json::json!({
+                "head": pr.head,
+                "base": pr.base,
+                "title": title,
+                "body": pr.body,
+                "labels": labels,
+            }),
+        )?;
+        Ok(CreatedPr {
+            number: created.number,
+            url: created.html_url,
+        })
+    }
+
+    /// The repository's labels
+    fn labels(&self) -> Result<Vec<Label>> {
+        let mut labels = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let batch: Vec<Label> =
+                self.get(&format!("labels?limit={}&page={}", PAGE_SIZE, page))?;
+            let done = batch.len() < PAGE_SIZE;
+            labels.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(labels)
+    }
+
+    /// All PRs (open and closed), newest first
+    fn pulls(&self) -> Result<Vec<PullRequest>> {
+        let mut pulls = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let batch: Vec<PullRequest> = self.get(&format!(
+                "pulls?state=all&sort=recentupdate&limit={}&page={}",
+                PAGE_SIZE, page
+            ))?;
+            let done = batch.len() < PAGE_SIZE;
+            pulls.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(pulls)
+    }
+
+    fn checks(&self, sha: &str) -> Option<CheckState> {
+        let combined: CombinedStatus = self.get(&format!("commits/{}/status", sha)).ok()?;
+        aggregate_statuses(&combined.statuses.unwrap_or_default())
+    }
+
+    /// PRs keyed by head branch. Checks are only fetched for open PRs, one
+    /// request each.
+    pub fn list_prs(&self) -> Result<HashMap<String, PrSummary>> {
+        let mut map = HashMap::new();
+        for pr in self.pulls()? {
+            if map.contains_key(&pr.head.ref_name) {
+                continue;
+            }
+            let checks = if pr.state == "open" {
+                self.checks(&pr.head.sha)
+            } else {
+                None
+            };
+            map.insert(pr.head.ref_name.clone(), pr.summary(checks));
+        }
+        Ok(map)
+    }
+
+    /// The most recent PR from `branch`, with its commit status
+    pub fn pr_status(&self, branch: &str) -> Result<Option<PrSummary>> {
+        Ok(self
+            .pulls()?
+            .iter()
+            .find(|pr| pr.head.ref_name == branch)
+            .map(|pr| {
+                let checks = if pr.state == "open" {
+                    self.checks(&pr.head.sha)
+                } else {
+                    None
+                };
+                pr.summary(checks)
+            }))
+    }
+
+    /// Merge a PR, or schedule it to merge once its checks succeed when
+    /// `auto` is set
+    pub fn merge_pr(&self, number: u32, method: MergeMethod, auto: bool) -> Result<()> {
+        let method = match method {
+            MergeMethod::Merge => "merge",
+            MergeMethod::Squash => "squash",
+            MergeMethod::Rebase => "rebase",
+        };
+        self.post_text(
+            &format!("pulls/{}/merge", number),
+            &serde_json::json!({
+                "Do": method,
+                "merge_when_checks_succeed": auto,
+            }),
+        )?;
+        Ok(())
+    }
+
+    /// Failed commit statuses on the branch's head. Gitea doesn't expose
+    /// Actions logs through the API, so only names and links are returned.
+    pub fn failed_checks(&self, branch: &str) -> Result<Vec<FailedCheck>> {
+        let combined: CombinedStatus = self.get(&format!(
+            "commits/{}/status",
+            utf8_percent_encode(branch, NON_ALPHANUMERIC)
+        ))?;
+        Ok(combined
+            .statuses
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|status| matches!(status.status.as_str(), "failure" | "error"))
+            .map(|status| FailedCheck {
+                name: status.context,
+                url: Some(status.target_url).filter(|url| !url.is_empty()),
+                log: None,
+            })
+            .collect())
+    }
+
+    /// Unresolved review comments, grouped into one thread per commented line.
+    /// Gitea has no thread IDs; replies share the line of the comment they
+    /// answer.
+    pub fn review_threads(&self, number: u32) -> Result<Vec<ReviewThread>> {
+        let reviews: Vec<Review> =
+            self.get(&format!("pulls/{}/reviews?limit={}", number, PAGE_SIZE))?;
+        let mut comments = Vec::new();
+        for review in reviews.iter().filter(|review| review.state != "PENDING") {
+            let batch: Vec<ReviewLineComment> =
+                self.get(&format!("pulls/{}/reviews/{}/comments", number, review.id))?;
+            comments.extend(batch);
+        }
+        Ok(group_review_comments(comments))
+    }
+
+    /// A PR's reviews, commit statuses and mergeability
+    pub fn pr_overview(&self, number: u32) -> Result<PrOverview> {
+        let pr: PullRequest = self.get(&format!("pulls/{}", number))?;
+        let reviews: Vec<Review> =
+            self.get(&format!("pulls/{}/reviews?limit={}", number, PAGE_SIZE))?;
+        let statuses = self
+            .get::<CombinedStatus>(&format!("commits/{}/status", pr.head.sha))?
+            .statuses
+            .unwrap_or_default();
+        Ok(to_overview(pr, &reviews, statuses))
+    }
+
+    /// Take a PR out of draft by dropping its "WIP:" title prefix
+    pub fn mark_ready(&self, number: u32) -> Result<()> {
+        let pr: PullRequest = self.get(&format!("pulls/{}", number))?;
+        let title = strip_wip_prefix(&pr.title);
+        if title != pr.title {
+            self.patch_text(
+                &format!("pulls/{}", number),
+                &serde_json::json!({ "title": title }),
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn find_pr_by_head_ref(&self, owner: &str, branch: &str) -> Result<Option<PrSummary>> {
+        Ok(self
+            .pulls()?
+            .iter()
+            .find(|pr| {
+                pr.head.ref_name == branch
+                    && pr
+                        .head_owner()
+                        .is_some_and(|login| login.eq_ignore_ascii_case(owner))
+            })
+            .map(|pr| pr.summary(None)))
+    }
+
+    pub fn get_issue(&self, number: u32) -> Result<Issue> {
+        let issue: GiteaIssue = self.get(&format!("issues/{}", number))?;
+        Ok(Issue {
+            number: issue.number,
+            title: issue.title,
+            body: issue.body,
+            labels: issue.labels.into_iter().map(|label| label.name).collect(),
+            url: issue.html_url,
+        })
+    }
+
+    /// PR details. For fork PRs, `head_repository_url` is the fork's clone URL
+    /// using the same protocol as `origin_url`.
+    pub fn get_pr_details(&self, number: u32, origin_url: &str) -> Result<PrDetails> {
+        let pr: PullRequest = self.get(&format!("pulls/{}", number))?;
+
+        let is_fork = match (&pr.head.repo, &pr.base.repo) {
+            (Some(head), Some(base)) => !head.full_name.eq_ignore_ascii_case(&base.full_name),
+            _ => false,
+        };
+        let head_repository_url = pr.head.repo.as_ref().filter(|_| is_fork).map(|repo| {
+            if origin_url.starts_with("http") {
+                repo.clone_url.clone()
+            } else {
+                repo.ssh_url.clone()
+            }
+        });
+        let owner = pr
+            .head_owner()
+            .map(str::to_string)
+            .or_else(|| self.repo.split('/').next().map(str::to_string))
+            .unwrap_or_default();
+
+        Ok(PrDetails {
+            state: pr.state().to_string(),
+            is_draft: pr.is_draft(),
+            head_repository_owner: RepositoryOwner { login: owner },
+            author: Author {
+                login: pr.user.map(|u| u.login).unwrap_or_default(),
+            },
+            head_ref_name: pr.head.ref_name,
+            title: pr.title,
+            head_repository_url,
+        })
+    }
+}
+
+fn to_overview(pr: PullRequest, reviews: &[Review], statuses: Vec<CommitStatus>) -> PrOverview {
+    let requested_reviewers: Vec<String> = pr
+        .requested_reviewers
+        .iter()
+        .flatten()
+        .map(|user| user.login.clone())
+        .collect();
+    let is_open = pr.state == "open";
+    PrOverview {
+        number: pr.number,
+        state: pr.state().to_string(),
+        is_draft: pr.is_draft(),
+        review_decision: review_decision(reviews, !requested_reviewers.is_empty()),
+        requested_reviewers,
+        checks: statuses.into_iter().map(check_run).collect(),
+        // Gitea only computes mergeability for open PRs
+        mergeable: Some(pr.mergeable).filter(|_| is_open),
+        title: pr.title,
+        url: pr.html_url,
+    }
+}
+
+/// Web URL of the instance hosting a remote. SSH remotes are assumed to be
+/// served over HTTPS on the same host.
+fn base_url_from_remote(url: &str) -> Option<String> {
+    let parsed = GitUrl::parse(url).ok()?;
+    let host = parsed.host()?;
+    Some(match parsed.scheme() {
+        Some(scheme @ ("http" | "https")) => {
+            let port = parsed.port().map(|p| format!(":{}", p)).unwrap_or_default();
+            format!("{}://{}{}", scheme, host, port)
+        }
+        _ => format!("https://{}", host),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::stub_server;
+
+    const OPEN_PR: &str = r#"{
+        "number": 7, "title": "Add parser", "state": "open", "merged": false,
+        "head": {"ref": "parser", "sha": "abc123",
+                 "repo": {"full_name": "team/app", "owner": {"login": "team"},
+                          "clone_url": "https://git.example.com/team/app.git",
+                          "ssh_url": "git@git.example.com:team/app.git"}},
+        "base": {"ref": "main", "sha": "def456",
+                 "repo": {"full_name": "team/app", "owner": {"login": "team"},
+                          "clone_url": "https://git.example.com/team/app.git",
+                          "ssh_url": "git@git.example.com:team/app.git"}},
+        "user": {"login": "bob"}
+    }"#;
+
+    #[test]
+    fn list_prs_fetches_checks_for_open_prs() {
+        let merged = r#"{"number": 3, "title": "WIP: Old", "state": "closed", "merged": true,
+            "head": {"ref": "old", "sha": "111", "repo": null},
+            "base": {"ref": "main", "sha": "222", "repo": null}, "user": null}"#;
+        let (url, server) = stub_server(vec![
+            format!("[{}, {}]", OPEN_PR, merged),
+            r#"{"state": "pending", "statuses": [{"status": "success"}, {"status": "pending"}]}"#
+                .to_string(),
+        ]);
+
+        let client = Client::new(&url, "team/app", Some("secret".to_string()));
+        let prs = client.list_prs().unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with(
+            "GET /api/v1/repos/team/app/pulls?state=all&sort=recentupdate&limit=50&page=1 "
+        ));
+        assert!(
+            requests[0]
+                .to_lowercase()
+                .contains("authorization: token secret")
+        );
+        assert!(requests[1].starts_with("GET /api/v1/repos/team/app/commits/abc123/status "));
+
+        let open = &prs["parser"];
+        assert_eq!(open.number, 7);
+        assert_eq!(open.state, "OPEN");
+        assert_eq!(
+            open.checks,
+            Some(CheckState::Pending {
+                passed: 1,
+                total: 2
+            })
+        );
+        let old = &prs["old"];
+        assert_eq!(old.state, "MERGED");
+        assert!(old.is_draft);
+        assert_eq!(old.checks, None);
+    }
+
+    #[test]
+    fn merge_pr_posts_the_merge_method() {
+        let (url, server) = stub_server(vec![String::new()]);
+
+        let client = Client::new(&url, "team/app", None);
+        client.merge_pr(7, MergeMethod::Squash, true).unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /api/v1/repos/team/app/pulls/7/merge "));
+        let body = requests[0].split("\r\n\r\n").nth(1).unwrap();
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["Do"], "squash");
+        assert_eq!(body["merge_when_checks_succeed"], true);
+    }
+
+    #[test]
+    fn pr_overview_uses_latest_review_verdicts() {
+        let pr = OPEN_PR.replacen(
+            r#""user": {"login": "bob"}"#,
+            r#""user": {"login": "bob"}, "mergeable": true,
+               "requested_reviewers": [{"login": "cat"}]"#,
+            1,
+        );
+        let reviews = r#"[
+            {"id": 1, "state": "REQUEST_CHANGES", "user": {"login": "ann"}},
+            {"id": 2, "state": "COMMENT", "user": {"login": "dan"}},
+            {"id": 3, "state": "APPROVED", "user": {"login": "ann"}}
+        ]"#;
+        let statuses = r#"{"statuses": [
+            {"status": "success", "context": "ci / test",
+             "created_at": "2024-05-01T12:00:00+02:00", "updated_at": "2024-05-01T12:01:30+02:00"},
+            {"status": "pending", "context": "ci / lint",
+             "created_at": "2024-05-01T12:00:00+02:00", "updated_at": "2024-05-01T12:00:10+02:00"}
+        ]}"#;
+        let (url, server) = stub_server(vec![pr, reviews.to_string(), statuses.to_string()]);
+
+        let client = Client::new(&url, "team/app", None);
+        let overview = client.pr_overview(7).unwrap();
+        server.join().unwrap();
+
+        assert_eq!(overview.review_decision, Some(ReviewDecision::Approved));
+        assert_eq!(overview.requested_reviewers, vec!["cat"]);
+        assert_eq!(overview.mergeable, Some(true));
+        assert_eq!(overview.checks[0].duration(0), Some(90));
+        assert_eq!(overview.checks[1].status, CheckStatus::Pending);
+        assert_eq!(overview.checks[1].completed_at, None);
+    }
+
+    #[test]
+    fn mark_ready_drops_the_wip_prefix() {
+        let pr = OPEN_PR.replacen("Add parser", "[WIP] Add parser", 1);
+        let (url, server) = stub_server(vec![pr, "{}".to_string()]);
+
+        let client = Client::new(&url, "team/app", None);
+        client.mark_ready(7).unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[1].starts_with("PATCH /api/v1/repos/team/app/pulls/7 "));
+        let body = requests[1].split("\r\n\r\n").nth(1).unwrap();
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["title"], "Add parser");
+    }
+
+    #[test]
+    fn create_pr_sends_label_ids() {
+        let (url, server) = stub_server(vec![
+            r#"[{"id": 3, "name": "bug"}, {"id": 5, "name": "Agent"}]"#.to_string(),
+            OPEN_PR.to_string(),
+        ]);
+
+        let client = Client::new(&url, "team/app", None);
+        let labels = vec!["agent".to_string(), "bug".to_string()];
+        client
+            .create_pr(&NewPr {
+                head: "feature",
+                base: "main",
+                title: "Add parser",
+                body: "",
+                draft: false,
+                labels: &labels,
+            })
+            .unwrap();
+
+        let requests = server.join().unwrap();
+        let body = requests[1].split("\r\n\r\n").nth(1).unwrap();
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["labels"], serde_json::json!([5, 3]));
+
+        let missing = label_ids(&[], &labels).unwrap_err();
+        assert!(missing.to_string().contains("agent, bug"));
+    }
+
+    #[test]
+    fn review_threads_group_unresolved_comments_by_line() {
+        let (url, server) = stub_server(vec![
+            r#"[{"id": 1, "state": "REQUEST_CHANGES"}, {"id": 2, "state": "PENDING"}]"#.to_string(),
+            r#"[
+                {"body": "Handle the error", "user": {"login": "rev"}, "path": "src/a.rs",
+                 "position": 10, "resolver": null},
+                {"body": "Typo", "user": {"login": "rev"}, "path": "README.md",
+                 "position": 2, "resolver": {"login": "bob"}},
+                {"body": "And log it", "user": {"login": "ann"}, "path": "src/a.rs",
+                 "position": 10, "resolver": null}
+            ]"#
+            .to_string(),
+        ]);
+
+        let client = Client::new(&url, "team/app", None);
+        let threads = client.review_threads(7).unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /api/v1/repos/team/app/pulls/7/reviews?limit=50 "));
+        assert!(requests[1].starts_with("GET /api/v1/repos/team/app/pulls/7/reviews/1/comments "));
+        assert_eq!(
+            threads,
+            vec![ReviewThread {
+                path: "src/a.rs".to_string(),
+                line: Some(10),
+                comments: vec![
+                    ReviewComment {
+                        author: "rev".to_string(),
+                        body: "Handle the error".to_string(),
+                    },
+                    ReviewComment {
+                        author: "ann".to_string(),
+                        body: "And log it".to_string(),
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn fork_pr_details_use_the_fork_clone_url() {
+        let fork_pr = r#"{
+            "number": 9, "title": "Fix typo", "state": "open",
+            "head": {"ref": "typo", "sha": "abc",
+                     "repo": {"full_name": "alice/app", "owner": {"login": "alice"},
+                              "clone_url": "https://git.example.com/alice/app.git",
+                              "ssh_url": "ssh://git@git.example.com:2222/alice/app.git"}},
+            "base": {"ref": "main", "sha": "def",
+                     "repo": {"full_name": "team/app", "owner": {"login": "team"},
+                              "clone_url": "https://git.example.com/team/app.git",
+                              "ssh_url": "ssh://git@git.example.com:2222/team/app.git"}},
+            "user": {"login": "alice"}
+        }"#;
+        let (url, server) = stub_server(vec![fork_pr.to_string(), OPEN_PR.to_string()]);
+        let client = Client::new(&url, "team/app", None);
+
+        let details = client
+            .get_pr_details(9, "ssh://git@git.example.com:2222/team/app.git")
+            .unwrap();
+        assert_eq!(details.head_ref_name, "typo");
+        assert_eq!(details.head_repository_owner.login, "alice");
+        assert_eq!(details.author.login, "alice");
+        assert_eq!(
+            details.head_repository_url.as_deref(),
+            Some("ssh://git@git.example.com:2222/alice/app.git")
+        );
+
+        let same_repo = client
+            .get_pr_details(7, "https://git.example.com/team/app")
+            .unwrap();
+        assert_eq!(same_repo.head_repository_url, None);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /api/v1/repos/team/app/pulls/9 "));
+        assert!(!requests[0].to_lowercase().contains("authorization:"));
+    }
+
+    #[test]
+    fn base_url_from_remote_keeps_http_ports() {
+        assert_eq!(
+            base_url_from_remote("git@codeberg.org:owner/repo.git").as_deref(),
+            Some("https://codeberg.org")
+        );
+        assert_eq!(
+            base_url_from_remote("http://localhost:3000/owner/repo.git").as_deref(),
+            Some("http://localhost:3000")
+        );
+        assert_eq!(
+            base_url_from_remote("ssh://git@git.example.com:2222/owner/repo.git").as_deref(),
+            Some("https://git.example.com")
+        );
+    }
+
+    #[test]
+    fn aggregate_statuses_states() {
+        let statuses = |names: &[&str]| -> Vec<CommitStatus> {
+            names
+                .iter()
+                .map(|s| CommitStatus {
+                    status: s.to_string(),
+                    ..Default::default()
+                })
+                .collect()
+        };
+        assert_eq!(aggregate_statuses(&[]), None);
+        assert_eq!(
+            aggregate_statuses(&statuses(&["success", "warning"])),
+            Some(CheckState::Success)
+        );
+        assert_eq!(
+            aggregate_statuses(&statuses(&["success", "error", "pending"])),
+            Some(CheckState::Failure {
+                passed: 1,
+                total: 3
+            })
+        );
+    }
+}
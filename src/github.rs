@@ -5,6 +5,11 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use tracing::debug;
 
+use crate::forge::{
+    CheckRun, CheckStatus, FailedCheck, Issue, MergeMethod, NewPr, PrOverview, ReviewComment,
+    ReviewDecision, ReviewThread, parse_timestamp,
+};
+
 #[derive(Debug, Deserialize)]
 pub struct PrDetails {
     #[serde(rename = "headRefName")]
@@ -16,6 +21,10 @@ pub struct PrDetails {
     pub is_draft: bool,
     pub title: String,
     pub author: Author,
+    /// Clone URL of the head repository, when the forge reports it directly
+    /// instead of deriving it from the owner (GitLab forks)
+    #[serde(skip)]
+    pub head_repository_url: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -58,12 +67,76 @@ pub struct PrSummary {
     pub checks: Option<CheckState>,
 }
 
+impl PrSummary {
+    /// A PR known only by the number `workmux pr create` recorded, shown until
+    /// the forge reports it
+    pub fn recorded(number: u32) -> Self {
+        Self {
+            number,
+            title: String::new(),
+            state: "OPEN".to_string(),
+            is_draft: false,
+            checks: None,
+        }
+    }
+}
+
 /// Handles both CheckRun (status/conclusion) and StatusContext (state) from GitHub API
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct CheckRollupItem {
     #[serde(alias = "state")]
     status: Option<String>,
     conclusion: Option<String>,
+    /// CheckRun name; StatusContext items carry `context` instead
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    context: String,
+    #[serde(default)]
+    workflow_name: String,
+    started_at: Option<String>,
+    completed_at: Option<String>,
+}
+
+impl CheckRollupItem {
+    /// None for states that are neither passing, failing, skipped nor running
+    fn check_status(&self) -> Option<CheckStatus> {
+        let status = self.status.as_deref().unwrap_or("");
+        let conclusion = self.conclusion.as_deref().unwrap_or("");
+
+        match (status, conclusion) {
+            // Success states
+            (_, "SUCCESS") | ("SUCCESS", _) => Some(CheckStatus::Success),
+            // Failure states (expanded to catch all failure-like conclusions)
+            (_, "FAILURE" | "CANCELLED" | "TIMED_OUT" | "STARTUP_FAILURE" | "ACTION_REQUIRED")
+            | ("FAILURE" | "ERROR", _) => Some(CheckStatus::Failure),
+            // Neutral/skipped - track but don't count toward active total
+            (_, "NEUTRAL" | "SKIPPED") => Some(CheckStatus::Skipped),
+            // Pending states (expanded)
+            ("IN_PROGRESS" | "QUEUED" | "PENDING" | "REQUESTED" | "WAITING", _) => {
+                Some(CheckStatus::Pending)
+            }
+            _ => None,
+        }
+    }
+
+    fn check_run(&self) -> CheckRun {
+        let name = if !self.context.is_empty() {
+            self.context.clone()
+        } else if self.workflow_name.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{} / {}", self.workflow_name, self.name)
+        };
+        CheckRun {
+            name,
+            // Unknown states (e.g. a status that is still EXPECTED) haven't reported yet
+            status: self.check_status().unwrap_or(CheckStatus::Pending),
+            started_at: self.started_at.as_deref().and_then(parse_timestamp),
+            completed_at: self.completed_at.as_deref().and_then(parse_timestamp),
+        }
+    }
 }
 
 /// Aggregate check results into a single CheckState
@@ -78,20 +151,12 @@ fn aggregate_checks(checks: &[CheckRollupItem]) -> Option<CheckState> {
     let mut skipped = 0u32;
 
     for check in checks {
-        let status = check.status.as_deref().unwrap_or("");
-        let conclusion = check.conclusion.as_deref().unwrap_or("");
-
-        match (status, conclusion) {
-            // Success states
-            (_, "SUCCESS") | ("SUCCESS", _) => passed += 1,
-            // Failure states (expanded to catch all failure-like conclusions)
-            (_, "FAILURE" | "CANCELLED" | "TIMED_OUT" | "STARTUP_FAILURE" | "ACTION_REQUIRED")
-            | ("FAILURE" | "ERROR", _) => failed += 1,
-            // Neutral/skipped - track but don't count toward active total
-            (_, "NEUTRAL" | "SKIPPED") => skipped += 1,
-            // Pending states (expanded)
-            ("IN_PROGRESS" | "QUEUED" | "PENDING" | "REQUESTED" | "WAITING", _) => pending += 1,
-            _ => {}
+        match check.check_status() {
+            Some(CheckStatus::Success) => passed += 1,
+            Some(CheckStatus::Failure) => failed += 1,
+            Some(CheckStatus::Skipped) => skipped += 1,
+            Some(CheckStatus::Pending) => pending += 1,
+            None => {}
         }
     }
 
@@ -232,40 +297,40 @@ pub fn get_pr_details(pr_number: u32) -> Result<PrDetails> {
     Ok(pr_details)
 }
 
-/// Internal struct for parsing batch PR list results
 #[derive(Debug, Deserialize)]
-struct PrBatchItem {
+struct IssueView {
     number: u32,
     title: String,
-    state: String,
-    #[serde(rename = "isDraft")]
-    is_draft: bool,
-    #[serde(rename = "headRefName")]
-    head_ref_name: String,
-    #[serde(rename = "statusCheckRollup", default)]
-    status_check_rollup: Vec<CheckRollupItem>,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    labels: Vec<Label>,
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Label {
+    name: String,
 }
 
-/// Fetch all PRs for the current repository.
-pub fn list_prs() -> Result<HashMap<String, PrSummary>> {
+/// Fetches an issue using the GitHub CLI
+pub fn get_issue(number: u32) -> Result<Issue> {
     let output = Command::new("gh")
         .args([
-            "pr",
-            "list",
-            "--state",
-            "all",
+            "issue",
+            "view",
+            &number.to_string(),
             "--json",
-            "number,title,state,isDraft,headRefName,statusCheckRollup",
-            "--limit",
-            "200",
+            "number,title,body,labels,url",
         ])
         .output();
 
     let output = match output {
         Ok(out) => out,
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-            debug!("github:gh CLI not found, skipping PR lookup");
-            return Ok(HashMap::new());
+            return Err(anyhow!(
+                "GitHub CLI (gh) is required for --issue. Install from https://cli.github.com"
+            ));
         }
         Err(e) => {
             return Err(e).context("Failed to execute gh command");
@@ -273,32 +338,33 @@ pub fn list_prs() -> Result<HashMap<String, PrSummary>> {
     };
 
     if !output.status.success() {
-        debug!("github:pr list batch failed, treating as no PRs found");
-        return Ok(HashMap::new());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("gh issue view failed: {}", stderr.trim()));
     }
 
-    let json_str = String::from_utf8(output.stdout).context("gh output is not valid UTF-8")?;
-
-    let prs: Vec<PrBatchItem> =
-        serde_json::from_str(&json_str).context("Failed to parse gh JSON output")?;
-
-    let pr_map = prs
-        .into_iter()
-        .map(|pr| {
-            (
-                pr.head_ref_name,
-                PrSummary {
-                    number: pr.number,
-                    title: pr.title,
-                    state: pr.state,
-                    is_draft: pr.is_draft,
-                    checks: aggregate_checks(&pr.status_check_rollup),
-                },
-            )
-        })
-        .collect();
+    let issue: IssueView =
+        serde_json::from_slice(&output.stdout).context("Failed to parse gh JSON output")?;
+    Ok(Issue {
+        number: issue.number,
+        title: issue.title,
+        body: issue.body,
+        labels: issue.labels.into_iter().map(|label| label.name).collect(),
+        url: issue.url,
+    })
+}
 
-    Ok(pr_map)
+/// Internal struct for parsing batch PR list results
+#[derive(Debug, Deserialize)]
+struct PrBatchItem {
+    number: u32,
+    title: String,
+    state: String,
+    #[serde(rename = "isDraft")]
+    is_draft: bool,
+    #[serde(rename = "headRefName")]
+    head_ref_name: String,
+    #[serde(rename = "statusCheckRollup", default)]
+    status_check_rollup: Vec<CheckRollupItem>,
 }
 
 /// List PRs for a specific repository
@@ -351,6 +417,440 @@ pub fn list_prs_in_repo(repo_root: &Path) -> Result<HashMap<String, PrSummary>>
     Ok(map)
 }
 
+/// The most recent PR whose head is `branch`, with its checks rollup
+pub fn pr_status(workdir: &Path, branch: &str) -> Result<Option<PrSummary>> {
+    let output = Command::new("gh")
+        .current_dir(workdir)
+        .args([
+            "pr",
+            "list",
+            "--head",
+            branch,
+            "--state",
+            "all",
+            "--json",
+            "number,title,state,isDraft,headRefName,statusCheckRollup",
+            "--limit",
+            "1",
+        ])
+        .output()
+        .context("Failed to run gh. Is the GitHub CLI installed?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("gh pr list failed: {}", stderr.trim()));
+    }
+
+    let prs: Vec<PrBatchItem> =
+        serde_json::from_slice(&output.stdout).context("Failed to parse gh JSON output")?;
+    Ok(prs.into_iter().next().map(|pr| PrSummary {
+        number: pr.number,
+        title: pr.title,
+        state: pr.state,
+        is_draft: pr.is_draft,
+        checks: aggregate_checks(&pr.status_check_rollup),
+    }))
+}
+
+/// Merge a PR with `gh pr merge`, or enable auto-merge when `auto` is set
+pub fn merge_pr(workdir: &Path, number: u32, method: MergeMethod, auto: bool) -> Result<()> {
+    let number = number.to_string();
+    let mut cmd = Command::new("gh");
+    cmd.current_dir(workdir).args(["pr", "merge", &number]);
+    cmd.arg(match method {
+        MergeMethod::Merge => "--merge",
+        MergeMethod::Squash => "--squash",
+        MergeMethod::Rebase => "--rebase",
+    });
+    if auto {
+        cmd.arg("--auto");
+    }
+    let output = cmd
+        .output()
+        .context("Failed to run gh. Is the GitHub CLI installed?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("gh pr merge failed: {}", stderr.trim()));
+    }
+    Ok(())
+}
+
+/// Take a draft PR out of draft with `gh pr ready`
+pub fn mark_ready(workdir: &Path, number: u32) -> Result<()> {
+    let output = Command::new("gh")
+        .current_dir(workdir)
+        .args(["pr", "ready", &number.to_string()])
+        .output()
+        .context("Failed to run gh. Is the GitHub CLI installed?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("gh pr ready failed: {}", stderr.trim()));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrView {
+    number: u32,
+    title: String,
+    url: String,
+    state: String,
+    is_draft: bool,
+    /// Empty when the repository doesn't require reviews
+    #[serde(default)]
+    review_decision: Option<String>,
+    #[serde(default)]
+    review_requests: Vec<ReviewRequest>,
+    #[serde(default)]
+    status_check_rollup: Vec<CheckRollupItem>,
+    #[serde(default)]
+    mergeable: String,
+}
+
+/// A requested reviewer: users have a login, teams an "org/team" slug
+#[derive(Debug, Deserialize)]
+struct ReviewRequest {
+    login: Option<String>,
+    slug: Option<String>,
+    name: Option<String>,
+}
+
+impl From<PrView> for PrOverview {
+    fn from(pr: PrView) -> Self {
+        PrOverview {
+            number: pr.number,
+            title: pr.title,
+            url: pr.url,
+            state: pr.state,
+            is_draft: pr.is_draft,
+            review_decision: match pr.review_decision.as_deref() {
+                Some("APPROVED") => Some(ReviewDecision::Approved),
+                Some("CHANGES_REQUESTED") => Some(ReviewDecision::ChangesRequested),
+                Some("REVIEW_REQUIRED") => Some(ReviewDecision::ReviewRequired),
+                _ => None,
+            },
+            requested_reviewers: pr
+                .review_requests
+                .into_iter()
+                .filter_map(|request| request.login.or(request.slug).or(request.name))
+                .collect(),
+            checks: pr
+                .status_check_rollup
+                .iter()
+                .map(CheckRollupItem::check_run)
+                .collect(),
+            mergeable: match pr.mergeable.as_str() {
+                "MERGEABLE" => Some(true),
+                "CONFLICTING" => Some(false),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// A PR's reviews, check runs and mergeability from `gh pr view`
+pub fn pr_overview(workdir: &Path, number: u32) -> Result<PrOverview> {
+    let output = Command::new("gh")
+        .current_dir(workdir)
+        .args([
+            "pr",
+            "view",
+            &number.to_string(),
+            "--json",
+            "number,title,url,state,isDraft,reviewDecision,reviewRequests,statusCheckRollup,mergeable",
+        ])
+        .output()
+        .context("Failed to run gh. Is the GitHub CLI installed?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("gh pr view failed: {}", stderr.trim()));
+    }
+
+    let pr: PrView =
+        serde_json::from_slice(&output.stdout).context("Failed to parse gh JSON output")?;
+    Ok(pr.into())
+}
+
+/// A GitHub Actions workflow run, from `gh run list`
+#[derive(Debug, Deserialize)]
+struct WorkflowRun {
+    #[serde(rename = "databaseId")]
+    database_id: u64,
+    #[serde(rename = "headSha")]
+    head_sha: String,
+    #[serde(rename = "workflowName")]
+    workflow_name: String,
+    conclusion: String,
+    url: String,
+}
+
+impl WorkflowRun {
+    fn failed(&self) -> bool {
+        matches!(
+            self.conclusion.as_str(),
+            "failure" | "timed_out" | "startup_failure"
+        )
+    }
+}
+
+/// Workflow runs for the branch's latest commit
+fn latest_runs(workdir: &Path, branch: &str) -> Result<Vec<WorkflowRun>> {
+    let output = Command::new("gh")
+        .current_dir(workdir)
+        .args([
+            "run",
+            "list",
+            "--branch",
+            branch,
+            "--json",
+            "databaseId,headSha,workflowName,conclusion,url",
+            "--limit",
+            "20",
+        ])
+        .output()
+        .context("Failed to run gh. Is the GitHub CLI installed?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("gh run list failed: {}", stderr.trim()));
+    }
+
+    let runs: Vec<WorkflowRun> =
+        serde_json::from_slice(&output.stdout).context("Failed to parse gh JSON output")?;
+    // Runs come newest first; only the latest commit's runs are relevant
+    let Some(head_sha) = runs.first().map(|run| run.head_sha.clone()) else {
+        return Ok(Vec::new());
+    };
+    Ok(runs
+        .into_iter()
+        .filter(|run| run.head_sha == head_sha)
+        .collect())
+}
+
+/// Failed GitHub Actions jobs for the branch's latest commit, with the logs of
+/// their failed steps
+pub fn failed_checks(workdir: &Path, branch: &str) -> Result<Vec<FailedCheck>> {
+    let mut failed = Vec::new();
+    for run in latest_runs(workdir, branch)?
+        .iter()
+        .filter(|run| run.failed())
+    {
+        let jobs = failed_job_logs(workdir, run.database_id);
+        if jobs.is_empty() {
+            failed.push(FailedCheck {
+                name: run.workflow_name.clone(),
+                url: Some(run.url.clone()),
+                log: None,
+            });
+        }
+        failed.extend(jobs.into_iter().map(|(job, log)| FailedCheck {
+            name: format!("{} / {}", run.workflow_name, job),
+            url: Some(run.url.clone()),
+            log: Some(log),
+        }));
+    }
+    Ok(failed)
+}
+
+/// Re-run the failed jobs of the failed workflow runs for the branch's latest
+/// commit
+pub fn rerun_failed_checks(workdir: &Path, branch: &str) -> Result<usize> {
+    let runs = latest_runs(workdir, branch)?;
+    let failed: Vec<_> = runs.iter().filter(|run| run.failed()).collect();
+    for run in &failed {
+        let output = Command::new("gh")
+            .current_dir(workdir)
+            .args(["run", "rerun", &run.database_id.to_string(), "--failed"])
+            .output()
+            .context("Failed to run gh. Is the GitHub CLI installed?")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("gh run rerun failed: {}", stderr.trim()));
+        }
+    }
+    Ok(failed.len())
+}
+
+/// Logs of a run's failed steps, grouped by job
+fn failed_job_logs(workdir: &Path, run_id: u64) -> Vec<(String, String)> {
+    let output = Command::new("gh")
+        .current_dir(workdir)
+        .args(["run", "view", &run_id.to_string(), "--log-failed"])
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_failed_log(&String::from_utf8_lossy(&output.stdout))
+        }
+        Ok(output) => {
+            debug!(run_id, stderr = %String::from_utf8_lossy(&output.stderr), "github:run log unavailable");
+            Vec::new()
+        }
+        Err(e) => {
+            debug!(run_id, error = %e, "github:run log unavailable");
+            Vec::new()
+        }
+    }
+}
+
+/// Group `gh run view --log-failed` output ("job<TAB>step<TAB>timestamp text")
+/// by job, in the order the jobs appear
+fn parse_failed_log(output: &str) -> Vec<(String, String)> {
+    let mut jobs: Vec<(String, String)> = Vec::new();
+    for line in output.lines() {
+        let mut fields = line.splitn(3, '\t');
+        let (Some(job), Some(_step), Some(text)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        // Each line starts with an RFC 3339 timestamp
+        let text = match text.split_once(' ') {
+            Some((timestamp, rest)) if timestamp.ends_with('Z') => rest,
+            None if text.ends_with('Z') => "",
+            _ => text,
+        };
+        match jobs.last_mut() {
+            Some((name, log)) if name == job => {
+                log.push_str(text);
+                log.push('\n');
+            }
+            _ => jobs.push((job.to_string(), format!("{}\n", text))),
+        }
+    }
+    jobs
+}
+
+const REVIEW_THREADS_QUERY: &str = "query($owner: String!, $name: String!, $number: Int!) { \
+    repository(owner: $owner, name: $name) { pullRequest(number: $number) { \
+    reviewThreads(first: 100) { nodes { isResolved path line \
+    comments(first: 50) { nodes { author { login } body } } } } } } }";
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadsResponse {
+    data: ReviewThreadsData,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadsData {
+    repository: ReviewThreadsRepository,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadsRepository {
+    #[serde(rename = "pullRequest")]
+    pull_request: ReviewThreadsPullRequest,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadsPullRequest {
+    #[serde(rename = "reviewThreads")]
+    review_threads: Nodes<ReviewThreadNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadNode {
+    #[serde(rename = "isResolved")]
+    is_resolved: bool,
+    path: String,
+    line: Option<u32>,
+    comments: Nodes<ReviewCommentNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewCommentNode {
+    /// None when the author's account was deleted
+    author: Option<Author>,
+    body: String,
+}
+
+/// Unresolved review threads on a PR, through the GraphQL API (the REST API
+/// doesn't say whether a thread is resolved)
+pub fn review_threads(workdir: &Path, number: u32) -> Result<Vec<ReviewThread>> {
+    let output = Command::new("gh")
+        .current_dir(workdir)
+        .args([
+            "api",
+            "graphql",
+            "-F",
+            "owner={owner}",
+            "-F",
+            "name={repo}",
+            "-F",
+            &format!("number={}", number),
+            "-f",
+            &format!("query={}", REVIEW_THREADS_QUERY),
+        ])
+        .output()
+        .context("Failed to run gh. Is the GitHub CLI installed?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("gh api graphql failed: {}", stderr.trim()));
+    }
+
+    let response: ReviewThreadsResponse =
+        serde_json::from_slice(&output.stdout).context("Failed to parse gh JSON output")?;
+    Ok(unresolved_threads(
+        response.data.repository.pull_request.review_threads.nodes,
+    ))
+}
+
+fn unresolved_threads(nodes: Vec<ReviewThreadNode>) -> Vec<ReviewThread> {
+    nodes
+        .into_iter()
+        .filter(|thread| !thread.is_resolved)
+        .map(|thread| ReviewThread {
+            path: thread.path,
+            line: thread.line,
+            comments: thread
+                .comments
+                .nodes
+                .into_iter()
+                .map(|comment| ReviewComment {
+                    author: comment.author.map(|a| a.login).unwrap_or_default(),
+                    body: comment.body,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Create a pull request with the GitHub CLI and return its URL
+pub fn create_pr(workdir: &Path, pr: &NewPr) -> Result<String> {
+    let mut cmd = Command::new("gh");
+    cmd.current_dir(workdir).args([
+        "pr", "create", "--head", pr.head, "--base", pr.base, "--title", pr.title, "--body",
+        pr.body,
+    ]);
+    if pr.draft {
+        cmd.arg("--draft");
+    }
+    for label in pr.labels {
+        cmd.args(["--label", label]);
+    }
+    let output = cmd
+        .output()
+        .context("Failed to run gh. Is the GitHub CLI installed?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("gh pr create failed: {}", stderr.trim()));
+    }
+
+    // gh prints progress lines before the PR URL
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout.trim().lines().last().unwrap_or_default().to_string())
+}
+
 /// Get the path to the PR status cache file
 fn get_pr_cache_path() -> Result<PathBuf> {
     let home = home::home_dir().ok_or_else(|| anyhow!("Could not find home directory"))?;
@@ -370,6 +870,16 @@ pub fn load_pr_cache() -> HashMap<PathBuf, HashMap<String, PrSummary>> {
     HashMap::new()
 }
 
+/// Add a PR to the status cache, so the dashboard shows it on its next start
+pub fn cache_pr(repo_root: &Path, branch: &str, pr: PrSummary) {
+    let mut statuses = load_pr_cache();
+    statuses
+        .entry(repo_root.to_path_buf())
+        .or_default()
+        .insert(branch.to_string(), pr);
+    save_pr_cache(&statuses);
+}
+
 /// Save the PR status cache to disk
 pub fn save_pr_cache(statuses: &HashMap<PathBuf, HashMap<String, PrSummary>>) {
     if let Ok(path) = get_pr_cache_path()
@@ -387,9 +897,105 @@ mod tests {
         CheckRollupItem {
             status: status.map(String::from),
             conclusion: conclusion.map(String::from),
+            ..Default::default()
         }
     }
 
+    #[test]
+    fn parse_failed_log_groups_lines_by_job() {
+        let output = "lint\tRun clippy\t2024-05-01T10:00:00.1234567Z error: unused variable\n\
+                      lint\tRun clippy\t2024-05-01T10:00:01.0000000Z\n\
+                      test\tRun tests\t2024-05-01T10:00:02.0000000Z test foo ... FAILED\n";
+        assert_eq!(
+            parse_failed_log(output),
+            vec![
+                ("lint".to_string(), "error: unused variable\n\n".to_string()),
+                ("test".to_string(), "test foo ... FAILED\n".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn pr_overview_maps_reviews_checks_and_mergeability() {
+        let json = r#"{
+            "number": 12, "title": "Add parser", "url": "https://github.com/o/r/pull/12",
+            "state": "OPEN", "isDraft": true, "reviewDecision": "CHANGES_REQUESTED",
+            "reviewRequests": [
+                {"__typename": "User", "login": "ann"},
+                {"__typename": "Team", "name": "Core", "slug": "o/core"}
+            ],
+            "statusCheckRollup": [
+                {"__typename": "CheckRun", "name": "test", "workflowName": "CI",
+                 "status": "COMPLETED", "conclusion": "FAILURE",
+                 "startedAt": "2024-05-01T12:00:00Z", "completedAt": "2024-05-01T12:03:20Z"},
+                {"__typename": "CheckRun", "name": "lint", "workflowName": "CI",
+                 "status": "IN_PROGRESS", "conclusion": "",
+                 "startedAt": "2024-05-01T12:00:00Z", "completedAt": "0001-01-01T00:00:00Z"},
+                {"__typename": "StatusContext", "context": "ci/deploy", "state": "EXPECTED",
+                 "startedAt": "2024-05-01T12:00:00Z"}
+            ],
+            "mergeable": "CONFLICTING"
+        }"#;
+        let overview: PrOverview = serde_json::from_str::<PrView>(json).unwrap().into();
+
+        assert_eq!(
+            overview.review_decision,
+            Some(ReviewDecision::ChangesRequested)
+        );
+        assert_eq!(overview.requested_reviewers, vec!["ann", "o/core"]);
+        assert_eq!(overview.mergeable, Some(false));
+        assert!(overview.is_draft);
+
+        let checks: Vec<_> = overview
+            .checks
+            .iter()
+            .map(|check| {
+                (
+                    check.name.as_str(),
+                    check.status,
+                    check.duration(1_714_564_900),
+                )
+            })
+            .collect();
+        assert_eq!(
+            checks,
+            vec![
+                ("CI / test", CheckStatus::Failure, Some(200)),
+                ("CI / lint", CheckStatus::Pending, Some(100)),
+                ("ci/deploy", CheckStatus::Pending, Some(100)),
+            ]
+        );
+    }
+
+    #[test]
+    fn review_threads_skip_resolved_threads() {
+        let json = r#"{"data":{"repository":{"pullRequest":{"reviewThreads":{"nodes":[
+            {"isResolved":true,"path":"a.rs","line":1,"comments":{"nodes":[{"author":{"login":"x"},"body":"done"}]}},
+            {"isResolved":false,"path":"b.rs","line":null,"comments":{"nodes":[
+                {"author":{"login":"rev"},"body":"Rename this"},
+                {"author":null,"body":"+1"}
+            ]}}
+        ]}}}}}"#;
+        let response: ReviewThreadsResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            unresolved_threads(response.data.repository.pull_request.review_threads.nodes),
+            vec![ReviewThread {
+                path: "b.rs".to_string(),
+                line: None,
+                comments: vec![
+                    ReviewComment {
+                        author: "rev".to_string(),
+                        body: "Rename this".to_string(),
+                    },
+                    ReviewComment {
+                        author: String::new(),
+                        body: "+1".to_string(),
+                    },
+                ],
+            }]
+        );
+    }
+
     #[test]
     fn aggregate_checks_empty() {
         assert_eq!(aggregate_checks(&[]), None);
@@ -0,0 +1,935 @@
+//! GitLab merge requests, fetched through the GitLab CLI (`glab api graphql`).
+
+use anyhow::{Context, Result, anyhow};
+use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use tracing::debug;
+
+use crate::forge::{
+    CheckRun, CheckStatus, FailedCheck, Issue, MergeMethod, NewPr, PrOverview, ReviewComment,
+    ReviewDecision, ReviewThread, parse_timestamp,
+};
+use crate::git;
+use crate::github::{Author, CheckState, PrDetails, PrSummary, RepositoryOwner};
+
+/// Fields fetched for every merge request, including the head pipeline's jobs
+/// so checks can be aggregated without a request per MR.
+const MR_FIELDS: &str = "iid title state draft sourceBranch \
+    sourceProject { fullPath sshUrlToRepo httpUrlToRepo } \
+    author { username } \
+    headPipeline { jobs(first: 100) { nodes { status allowFailure } } }";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeRequest {
+    iid: String,
+    title: String,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    source_branch: String,
+    /// Missing when the source project is not visible to the current user
+    #[serde(default)]
+    source_project: Option<Project>,
+    #[serde(default)]
+    author: Option<User>,
+    #[serde(default)]
+    head_pipeline: Option<Pipeline>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Project {
+    full_path: String,
+    ssh_url_to_repo: Option<String>,
+    http_url_to_repo: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Pipeline {
+    jobs: Nodes<Job>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Job {
+    status: String,
+    #[serde(default)]
+    allow_failure: bool,
+}
+
+impl MergeRequest {
+    fn number(&self) -> u32 {
+        self.iid.parse().unwrap_or_default()
+    }
+
+    fn summary(&self) -> PrSummary {
+        PrSummary {
+            number: self.number(),
+            title: self.title.clone(),
+            state: normalize_state(&self.state).to_string(),
+            is_draft: self.draft,
+            checks: self
+                .head_pipeline
+                .as_ref()
+                .and_then(|p| aggregate_jobs(&p.jobs.nodes)),
+        }
+    }
+}
+
+/// Map GitLab MR states onto the GitHub states used throughout workmux
+fn normalize_state(state: &str) -> &'static str {
+    match state.to_ascii_lowercase().as_str() {
+        "opened" => "OPEN",
+        "merged" => "MERGED",
+        _ => "CLOSED",
+    }
+}
+
+/// Map a pipeline job status onto a check status
+fn job_status(status: &str, allow_failure: bool) -> CheckStatus {
+    match status.to_ascii_lowercase().as_str() {
+        "success" => CheckStatus::Success,
+        // Jobs allowed to fail only produce a warning in GitLab
+        "failed" if allow_failure => CheckStatus::Skipped,
+        "failed" | "canceled" | "canceling" => CheckStatus::Failure,
+        "skipped" | "manual" => CheckStatus::Skipped,
+        _ => CheckStatus::Pending,
+    }
+}
+
+/// Aggregate pipeline job statuses into a single CheckState
+fn aggregate_jobs(jobs: &[Job]) -> Option<CheckState> {
+    if jobs.is_empty() {
+        return None;
+    }
+
+    let mut passed = 0u32;
+    let mut failed = 0u32;
+    let mut pending = 0u32;
+    let mut skipped = 0u32;
+
+    for job in jobs {
+        match job_status(&job.status, job.allow_failure) {
+            CheckStatus::Success => passed += 1,
+            CheckStatus::Failure => failed += 1,
+            CheckStatus::Skipped => skipped += 1,
+            CheckStatus::Pending => pending += 1,
+        }
+    }
+
+    let total = passed + failed + pending;
+    if total == 0 {
+        return if skipped > 0 {
+            Some(CheckState::Success)
+        } else {
+            None
+        };
+    }
+
+    Some(if failed > 0 {
+        CheckState::Failure { passed, total }
+    } else if pending > 0 {
+        CheckState::Pending { passed, total }
+    } else {
+        CheckState::Success
+    })
+}
+
+/// Run a GraphQL query with `glab api graphql` and return its `data` object
+fn graphql(workdir: Option<&Path>, query: &str, variables: &[(&str, &str)]) -> Result<Value> {
+    let mut args = vec![
+        "graphql".to_string(),
+        "-f".to_string(),
+        format!("query={}", query),
+    ];
+    for (name, value) in variables {
+        args.push("-f".to_string());
+        args.push(format!("{}={}", name, value));
+    }
+
+    let stdout = glab_api(workdir, &args)?;
+    let mut response: Value =
+        serde_json::from_slice(&stdout).context("Failed to parse glab JSON output")?;
+    if let Some(message) = response
+        .pointer("/errors/0/message")
+        .and_then(|m| m.as_str())
+    {
+        return Err(anyhow!("GitLab API error: {}", message));
+    }
+    Ok(response["data"].take())
+}
+
+/// Run `glab api` with the given arguments and return its output
+fn glab_api(workdir: Option<&Path>, args: &[String]) -> Result<Vec<u8>> {
+    let mut cmd = Command::new("glab");
+    if let Some(dir) = workdir {
+        cmd.current_dir(dir);
+    }
+    cmd.arg("api").args(args);
+
+    let output = match cmd.output() {
+        Ok(out) => out,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(anyhow!(
+                "GitLab CLI (glab) is required. Install from https://gitlab.com/gitlab-org/cli"
+            ));
+        }
+        Err(e) => return Err(e).context("Failed to execute glab command"),
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("glab api failed: {}", stderr.trim()));
+    }
+    Ok(output.stdout)
+}
+
+/// GET a REST API path with `glab api` and parse the JSON response
+fn rest<T: DeserializeOwned>(workdir: Option<&Path>, path: &str) -> Result<T> {
+    let stdout = glab_api(workdir, &[path.to_string()])?;
+    serde_json::from_slice(&stdout)
+        .with_context(|| format!("Failed to parse GitLab response for {}", path))
+}
+
+/// Project path of the origin remote, as GitLab's GraphQL API expects it
+fn origin_project_path(workdir: Option<&Path>) -> Result<String> {
+    let url = git::get_remote_url_in("origin", workdir)?;
+    git::repo_path_from_url(&url)
+        .ok_or_else(|| anyhow!("Could not parse GitLab project from origin URL: {}", url))
+}
+
+/// List the project's merge requests, newest first, optionally only those
+/// from a given source branch
+fn list_merge_requests(
+    workdir: Option<&Path>,
+    source_branch: Option<&str>,
+) -> Result<Vec<MergeRequest>> {
+    let project = origin_project_path(workdir)?;
+    let (declaration, filter) = match source_branch {
+        Some(_) => (", $branch: String!", ", sourceBranches: [$branch]"),
+        None => ("", ""),
+    };
+    let query = format!(
+        "query($project: ID!{}) {{ project(fullPath: $project) {{ \
+         mergeRequests(state: all, first: 100{}) {{ nodes {{ {} }} }} }} }}",
+        declaration, filter, MR_FIELDS
+    );
+    let mut variables = vec![("project", project.as_str())];
+    variables.extend(source_branch.map(|branch| ("branch", branch)));
+
+    let mut data = graphql(workdir, &query, &variables)?;
+    let nodes = data
+        .pointer_mut("/project/mergeRequests/nodes")
+        .map(Value::take)
+        .ok_or_else(|| anyhow!("GitLab project '{}' not found", project))?;
+    serde_json::from_value(nodes).context("Failed to parse GitLab merge requests")
+}
+
+/// Find a merge request whose source branch lives in the given namespace
+pub fn find_pr_by_head_ref(owner: &str, branch: &str) -> Result<Option<PrSummary>> {
+    let mrs = match list_merge_requests(None, Some(branch)) {
+        Ok(mrs) => mrs,
+        Err(e) => {
+            debug!(owner, branch, error = %e, "gitlab:mr lookup failed, treating as no MR found");
+            return Ok(None);
+        }
+    };
+
+    Ok(mrs
+        .iter()
+        .find(|mr| {
+            mr.source_project.as_ref().is_some_and(|p| {
+                p.full_path
+                    .rsplit_once('/')
+                    .is_some_and(|(namespace, _)| namespace.eq_ignore_ascii_case(owner))
+            })
+        })
+        .map(MergeRequest::summary))
+}
+
+/// Fetch merge request details, including the fork URL when it comes from a fork
+pub fn get_pr_details(number: u32) -> Result<PrDetails> {
+    let project = origin_project_path(None)?;
+    let query = format!(
+        "query($project: ID!, $iid: String!) {{ project(fullPath: $project) {{ \
+         mergeRequest(iid: $iid) {{ {} }} }} }}",
+        MR_FIELDS
+    );
+    let iid = number.to_string();
+    let mut data = graphql(None, &query, &[("project", &project), ("iid", &iid)])?;
+    let mr = data
+        .pointer_mut("/project/mergeRequest")
+        .map(Value::take)
+        .filter(|v| !v.is_null())
+        .ok_or_else(|| anyhow!("Merge request !{} not found in {}", number, project))?;
+    let mr: MergeRequest =
+        serde_json::from_value(mr).context("Failed to parse GitLab merge request")?;
+
+    let origin_url = git::get_remote_url("origin").unwrap_or_default();
+    Ok(to_pr_details(mr, &project, &origin_url))
+}
+
+#[derive(Debug, Deserialize)]
+struct RestIssue {
+    iid: u32,
+    title: String,
+    /// Null when the issue has no description
+    description: Option<String>,
+    #[serde(default)]
+    labels: Vec<String>,
+    web_url: String,
+}
+
+/// Fetch an issue of the origin project
+pub fn get_issue(number: u32) -> Result<Issue> {
+    let project = utf8_percent_encode(&origin_project_path(None)?, NON_ALPHANUMERIC).to_string();
+    let issue: RestIssue = rest(None, &format!("projects/{}/issues/{}", project, number))?;
+    Ok(Issue {
+        number: issue.iid,
+        title: issue.title,
+        body: issue.description.unwrap_or_default(),
+        labels: issue.labels,
+        url: issue.web_url,
+    })
+}
+
+fn to_pr_details(mr: MergeRequest, project: &str, origin_url: &str) -> PrDetails {
+    let source_path = mr
+        .source_project
+        .as_ref()
+        .map(|p| p.full_path.as_str())
+        .unwrap_or(project);
+    let owner = source_path
+        .rsplit_once('/')
+        .map(|(namespace, _)| namespace)
+        .unwrap_or(source_path)
+        .to_string();
+
+    // Fork MRs are checked out from the source project, using the same
+    // protocol as origin
+    let head_repository_url = mr
+        .source_project
+        .as_ref()
+        .filter(|p| !p.full_path.eq_ignore_ascii_case(project))
+        .and_then(|p| {
+            if origin_url.starts_with("http") {
+                p.http_url_to_repo.clone()
+            } else {
+                p.ssh_url_to_repo.clone()
+            }
+        });
+
+    PrDetails {
+        head_ref_name: mr.source_branch,
+        head_repository_owner: RepositoryOwner { login: owner },
+        state: normalize_state(&mr.state).to_string(),
+        is_draft: mr.draft,
+        title: mr.title,
+        author: Author {
+            login: mr.author.map(|a| a.username).unwrap_or_default(),
+        },
+        head_repository_url,
+    }
+}
+
+/// Open a merge request with `glab mr create` and return its URL
+pub fn create_merge_request(workdir: &Path, mr: &NewPr) -> Result<String> {
+    let mut cmd = Command::new("glab");
+    cmd.current_dir(workdir).args([
+        "mr",
+        "create",
+        "--source-branch",
+        mr.head,
+        "--target-branch",
+        mr.base,
+        "--title",
+        mr.title,
+        "--description",
+        mr.body,
+        "--yes",
+    ]);
+    if mr.draft {
+        cmd.arg("--draft");
+    }
+    if !mr.labels.is_empty() {
+        cmd.args(["--label", &mr.labels.join(",")]);
+    }
+    let output = cmd
+        .output()
+        .context("Failed to run glab. Is the GitLab CLI installed?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("glab mr create failed: {}", stderr.trim()));
+    }
+
+    // glab prints progress lines before the MR URL
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .lines()
+        .rev()
+        .find(|line| line.trim_start().starts_with("http"))
+        .unwrap_or_default()
+        .trim()
+        .to_string())
+}
+
+/// The most recent merge request from `branch`, with its pipeline status
+pub fn pr_status(workdir: &Path, branch: &str) -> Result<Option<PrSummary>> {
+    let mrs = list_merge_requests(Some(workdir), Some(branch))?;
+    Ok(mrs.first().map(MergeRequest::summary))
+}
+
+/// Merge a merge request with `glab mr merge`, or set it to merge when the
+/// pipeline succeeds when `auto` is set
+pub fn merge_merge_request(
+    workdir: &Path,
+    number: u32,
+    method: MergeMethod,
+    auto: bool,
+) -> Result<()> {
+    let number = number.to_string();
+    let mut cmd = Command::new("glab");
+    cmd.current_dir(workdir)
+        .args(["mr", "merge", &number, "--yes"]);
+    // glab enables auto-merge by default
+    cmd.arg(format!("--auto-merge={}", auto));
+    match method {
+        MergeMethod::Merge => {}
+        MergeMethod::Squash => {
+            cmd.arg("--squash");
+        }
+        MergeMethod::Rebase => {
+            cmd.arg("--rebase");
+        }
+    }
+    let output = cmd
+        .output()
+        .context("Failed to run glab. Is the GitLab CLI installed?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("glab mr merge failed: {}", stderr.trim()));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct RestPipeline {
+    id: u64,
+    #[serde(default)]
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RestJob {
+    id: u64,
+    name: String,
+    stage: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    allow_failure: bool,
+    web_url: String,
+    started_at: Option<String>,
+    finished_at: Option<String>,
+}
+
+/// Failed jobs of the branch's latest pipeline, with their job logs
+pub fn failed_checks(workdir: &Path, branch: &str) -> Result<Vec<FailedCheck>> {
+    let project =
+        utf8_percent_encode(&origin_project_path(Some(workdir))?, NON_ALPHANUMERIC).to_string();
+    let branch = utf8_percent_encode(branch, NON_ALPHANUMERIC).to_string();
+
+    let pipelines: Vec<RestPipeline> = rest(
+        Some(workdir),
+        &format!("projects/{}/pipelines?ref={}&per_page=1", project, branch),
+    )?;
+    let Some(pipeline) = pipelines.first() else {
+        return Ok(Vec::new());
+    };
+    let jobs: Vec<RestJob> = rest(
+        Some(workdir),
+        &format!(
+            "projects/{}/pipelines/{}/jobs?scope[]=failed&per_page=100",
+            project, pipeline.id
+        ),
+    )?;
+
+    Ok(jobs
+        .into_iter()
+        .filter(|job| !job.allow_failure)
+        .map(|job| {
+            let log = glab_api(
+                Some(workdir),
+                &[format!("projects/{}/jobs/{}/trace", project, job.id)],
+            )
+            .map(|trace| String::from_utf8_lossy(&trace).into_owned())
+            .inspect_err(|e| debug!(job = job.id, error = %e, "gitlab:job trace unavailable"))
+            .ok();
+            FailedCheck {
+                name: format!("{} / {}", job.stage, job.name),
+                url: Some(job.web_url),
+                log,
+            }
+        })
+        .collect())
+}
+
+/// Retry the failed jobs of the branch's latest pipeline, if it failed
+pub fn rerun_failed_checks(workdir: &Path, branch: &str) -> Result<usize> {
+    let project =
+        utf8_percent_encode(&origin_project_path(Some(workdir))?, NON_ALPHANUMERIC).to_string();
+    let branch = utf8_percent_encode(branch, NON_ALPHANUMERIC).to_string();
+
+    let pipelines: Vec<RestPipeline> = rest(
+        Some(workdir),
+        &format!("projects/{}/pipelines?ref={}&per_page=1", project, branch),
+    )?;
+    let Some(pipeline) = pipelines
+        .first()
+        .filter(|pipeline| matches!(pipeline.status.as_str(), "failed" | "canceled"))
+    else {
+        return Ok(0);
+    };
+    glab_api(
+        Some(workdir),
+        &[
+            "--method".to_string(),
+            "POST".to_string(),
+            format!("projects/{}/pipelines/{}/retry", project, pipeline.id),
+        ],
+    )?;
+    Ok(1)
+}
+
+/// Take a draft merge request out of draft with `glab mr update --ready`
+pub fn mark_ready(workdir: &Path, number: u32) -> Result<()> {
+    let output = Command::new("glab")
+        .current_dir(workdir)
+        .args(["mr", "update", &number.to_string(), "--ready"])
+        .output()
+        .context("Failed to run glab. Is the GitLab CLI installed?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("glab mr update failed: {}", stderr.trim()));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct RestMergeRequest {
+    iid: u32,
+    title: String,
+    web_url: String,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    reviewers: Vec<User>,
+    #[serde(default)]
+    has_conflicts: bool,
+    /// Missing on GitLab versions before 15.6
+    detailed_merge_status: Option<String>,
+    head_pipeline: Option<RestPipeline>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Approvals {
+    #[serde(default)]
+    approvals_left: u32,
+    #[serde(default)]
+    approved_by: Vec<Approval>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Approval {
+    user: User,
+}
+
+/// A merge request's reviews, pipeline jobs and mergeability
+pub fn pr_overview(workdir: &Path, number: u32) -> Result<PrOverview> {
+    let project =
+        utf8_percent_encode(&origin_project_path(Some(workdir))?, NON_ALPHANUMERIC).to_string();
+    let mr: RestMergeRequest = rest(
+        Some(workdir),
+        &format!("projects/{}/merge_requests/{}", project, number),
+    )?;
+    // Approval rules may be unavailable (e.g. on instances without them)
+    let approvals: Option<Approvals> = rest(
+        Some(workdir),
+        &format!("projects/{}/merge_requests/{}/approvals", project, number),
+    )
+    .inspect_err(|e| debug!(mr = number, error = %e, "gitlab:approvals unavailable"))
+    .ok();
+    let jobs: Vec<RestJob> = match &mr.head_pipeline {
+        Some(pipeline) => rest(
+            Some(workdir),
+            &format!(
+                "projects/{}/pipelines/{}/jobs?per_page=100",
+                project, pipeline.id
+            ),
+        )?,
+        None => Vec::new(),
+    };
+    Ok(to_overview(mr, approvals, jobs))
+}
+
+fn to_overview(
+    mr: RestMergeRequest,
+    approvals: Option<Approvals>,
+    jobs: Vec<RestJob>,
+) -> PrOverview {
+    let merge_status = mr.detailed_merge_status.as_deref();
+    let approvers: Vec<&str> = approvals
+        .iter()
+        .flat_map(|approvals| &approvals.approved_by)
+        .map(|approval| approval.user.username.as_str())
+        .collect();
+    let review_decision = match (merge_status, &approvals) {
+        (Some("requested_changes"), _) => Some(ReviewDecision::ChangesRequested),
+        (Some("not_approved"), _) => Some(ReviewDecision::ReviewRequired),
+        (_, Some(approvals)) if approvals.approvals_left > 0 => {
+            Some(ReviewDecision::ReviewRequired)
+        }
+        _ if !approvers.is_empty() => Some(ReviewDecision::Approved),
+        _ => None,
+    };
+    let mergeable = if mr.has_conflicts {
+        Some(false)
+    } else {
+        match merge_status {
+            None | Some("checking" | "unchecked" | "preparing" | "approvals_syncing") => None,
+            Some("conflict" | "need_rebase") => Some(false),
+            Some(_) => Some(true),
+        }
+    };
+
+    PrOverview {
+        number: mr.iid,
+        title: mr.title,
+        url: mr.web_url,
+        state: normalize_state(&mr.state).to_string(),
+        is_draft: mr.draft,
+        review_decision,
+        requested_reviewers: mr
+            .reviewers
+            .into_iter()
+            .map(|reviewer| reviewer.username)
+            .filter(|username| !approvers.contains(&username.as_str()))
+            .collect(),
+        // Jobs come newest first; show them in pipeline order
+        checks: jobs
+            .into_iter()
+            .rev()
+            .map(|job| CheckRun {
+                status: job_status(&job.status, job.allow_failure),
+                name: format!("{} / {}", job.stage, job.name),
+                started_at: job.started_at.as_deref().and_then(parse_timestamp),
+                completed_at: job.finished_at.as_deref().and_then(parse_timestamp),
+            })
+            .collect(),
+        mergeable,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Discussion {
+    notes: Vec<Note>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Note {
+    body: String,
+    author: User,
+    #[serde(default)]
+    system: bool,
+    #[serde(default)]
+    resolvable: bool,
+    #[serde(default)]
+    resolved: bool,
+    position: Option<NotePosition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NotePosition {
+    new_path: Option<String>,
+    old_path: Option<String>,
+    new_line: Option<u32>,
+}
+
+/// Unresolved diff discussions on a merge request
+pub fn review_threads(workdir: &Path, number: u32) -> Result<Vec<ReviewThread>> {
+    let project =
+        utf8_percent_encode(&origin_project_path(Some(workdir))?, NON_ALPHANUMERIC).to_string();
+    let discussions: Vec<Discussion> = rest(
+        Some(workdir),
+        &format!(
+            "projects/{}/merge_requests/{}/discussions?per_page=100",
+            project, number
+        ),
+    )?;
+    Ok(unresolved_threads(discussions))
+}
+
+/// Discussions attached to a line of the diff whose first note is still
+/// unresolved. Overview comments have no position and aren't review threads.
+fn unresolved_threads(discussions: Vec<Discussion>) -> Vec<ReviewThread> {
+    discussions
+        .into_iter()
+        .filter_map(|discussion| {
+            let first = discussion.notes.first()?;
+            if first.system || !first.resolvable || first.resolved {
+                return None;
+            }
+            let position = first.position.as_ref()?;
+            let path = position
+                .new_path
+                .clone()
+                .or_else(|| position.old_path.clone())?;
+            let line = position.new_line;
+            Some(ReviewThread {
+                path,
+                line,
+                comments: discussion
+                    .notes
+                    .into_iter()
+                    .filter(|note| !note.system)
+                    .map(|note| ReviewComment {
+                        author: note.author.username,
+                        body: note.body,
+                    })
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// List merge requests for a repository, keyed by source branch
+pub fn list_prs_in_repo(repo_root: &Path) -> Result<HashMap<String, PrSummary>> {
+    let mrs = match list_merge_requests(Some(repo_root), None) {
+        Ok(mrs) => mrs,
+        Err(e) => {
+            tracing::warn!("Failed to list GitLab merge requests: {:#}", e);
+            return Ok(HashMap::new());
+        }
+    };
+
+    // Nodes come newest first; keep the most recent MR for each branch
+    let mut map = HashMap::new();
+    for mr in mrs {
+        map.entry(mr.source_branch.clone())
+            .or_insert_with(|| mr.summary());
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(status: &str, allow_failure: bool) -> Job {
+        Job {
+            status: status.to_string(),
+            allow_failure,
+        }
+    }
+
+    #[test]
+    fn aggregate_jobs_states() {
+        assert_eq!(aggregate_jobs(&[]), None);
+        assert_eq!(
+            aggregate_jobs(&[job("SUCCESS", false), job("SUCCESS", false)]),
+            Some(CheckState::Success)
+        );
+        assert_eq!(
+            aggregate_jobs(&[job("SUCCESS", false), job("RUNNING", false)]),
+            Some(CheckState::Pending {
+                passed: 1,
+                total: 2
+            })
+        );
+        assert_eq!(
+            aggregate_jobs(&[
+                job("SUCCESS", false),
+                job("FAILED", false),
+                job("PENDING", false)
+            ]),
+            Some(CheckState::Failure {
+                passed: 1,
+                total: 3
+            })
+        );
+        assert_eq!(
+            aggregate_jobs(&[job("CANCELED", false)]),
+            Some(CheckState::Failure {
+                passed: 0,
+                total: 1
+            })
+        );
+    }
+
+    #[test]
+    fn aggregate_jobs_ignores_allowed_failures_and_manual_jobs() {
+        assert_eq!(
+            aggregate_jobs(&[
+                job("SUCCESS", false),
+                job("FAILED", true),
+                job("MANUAL", false),
+                job("SKIPPED", false)
+            ]),
+            Some(CheckState::Success)
+        );
+        assert_eq!(
+            aggregate_jobs(&[job("MANUAL", false)]),
+            Some(CheckState::Success)
+        );
+    }
+
+    #[test]
+    fn review_threads_keep_unresolved_diff_discussions() {
+        let json = r#"[
+            {"notes": [{"body": "Overview", "author": {"username": "rev"}, "resolvable": false}]},
+            {"notes": [{"body": "Fixed", "author": {"username": "rev"}, "resolvable": true, "resolved": true,
+                        "position": {"new_path": "a.rs", "old_path": "a.rs", "new_line": 3}}]},
+            {"notes": [
+                {"body": "Use a match here", "author": {"username": "rev"}, "resolvable": true, "resolved": false,
+                 "position": {"new_path": "src/b.rs", "old_path": "src/b.rs", "new_line": 12}},
+                {"body": "changed this line", "author": {"username": "bot"}, "system": true},
+                {"body": "Will do", "author": {"username": "me"}, "resolvable": true, "resolved": false}
+            ]}
+        ]"#;
+        let discussions: Vec<Discussion> = serde_json::from_str(json).unwrap();
+        let threads = unresolved_threads(discussions);
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].path, "src/b.rs");
+        assert_eq!(threads[0].line, Some(12));
+        assert_eq!(
+            threads[0].comments,
+            vec![
+                ReviewComment {
+                    author: "rev".to_string(),
+                    body: "Use a match here".to_string(),
+                },
+                ReviewComment {
+                    author: "me".to_string(),
+                    body: "Will do".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn normalize_state_maps_to_github_states() {
+        assert_eq!(normalize_state("opened"), "OPEN");
+        assert_eq!(normalize_state("merged"), "MERGED");
+        assert_eq!(normalize_state("closed"), "CLOSED");
+        assert_eq!(normalize_state("locked"), "CLOSED");
+    }
+
+    #[test]
+    fn fork_merge_request_details() {
+        let json = r#"{
+            "iid": "42",
+            "title": "Fix parser",
+            "state": "opened",
+            "draft": true,
+            "sourceBranch": "fix-parser",
+            "sourceProject": {
+                "fullPath": "alice/repo",
+                "sshUrlToRepo": "git@gitlab.com:alice/repo.git",
+                "httpUrlToRepo": "https://gitlab.com/alice/repo.git"
+            },
+            "author": { "username": "alice" },
+            "headPipeline": { "jobs": { "nodes": [{ "status": "SUCCESS", "allowFailure": false }] } }
+        }"#;
+        let mr: MergeRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(mr.summary().number, 42);
+        assert_eq!(mr.summary().checks, Some(CheckState::Success));
+
+        let details = to_pr_details(mr, "team/repo", "git@gitlab.com:team/repo.git");
+        assert_eq!(details.head_ref_name, "fix-parser");
+        assert_eq!(details.head_repository_owner.login, "alice");
+        assert_eq!(details.state, "OPEN");
+        assert!(details.is_draft);
+        assert_eq!(details.author.login, "alice");
+        assert_eq!(
+            details.head_repository_url.as_deref(),
+            Some("git@gitlab.com:alice/repo.git")
+        );
+
+        let same_project: MergeRequest = serde_json::from_str(
+            r#"{"iid": "7", "title": "t", "state": "merged", "sourceBranch": "b",
+                "sourceProject": {"fullPath": "team/repo"}}"#,
+        )
+        .unwrap();
+        let details = to_pr_details(same_project, "team/repo", "");
+        assert_eq!(details.head_repository_url, None);
+        assert_eq!(details.state, "MERGED");
+    }
+
+    #[test]
+    fn merge_request_overview() {
+        let mr: RestMergeRequest = serde_json::from_str(
+            r#"{"iid": 5, "title": "Add parser", "web_url": "https://gitlab.com/g/r/-/merge_requests/5",
+                "state": "opened", "draft": false,
+                "reviewers": [{"username": "ann"}, {"username": "bob"}],
+                "has_conflicts": false, "detailed_merge_status": "mergeable",
+                "head_pipeline": {"id": 9, "status": "failed"}}"#,
+        )
+        .unwrap();
+        let approvals: Approvals = serde_json::from_str(
+            r#"{"approvals_left": 0, "approved_by": [{"user": {"username": "ann"}}]}"#,
+        )
+        .unwrap();
+        let jobs: Vec<RestJob> = serde_json::from_str(
+            r#"[{"id": 2, "name": "rspec", "stage": "test", "status": "running",
+                 "web_url": "", "started_at": "2024-05-01T12:00:00.000Z", "finished_at": null},
+                {"id": 1, "name": "build", "stage": "build", "status": "failed",
+                 "web_url": "", "started_at": "2024-05-01T11:58:00.000Z",
+                 "finished_at": "2024-05-01T11:59:30.000Z"}]"#,
+        )
+        .unwrap();
+
+        let overview = to_overview(mr, Some(approvals), jobs);
+        assert_eq!(overview.state, "OPEN");
+        assert_eq!(overview.review_decision, Some(ReviewDecision::Approved));
+        assert_eq!(overview.requested_reviewers, vec!["bob"]);
+        assert_eq!(overview.mergeable, Some(true));
+        let checks: Vec<_> = overview
+            .checks
+            .iter()
+            .map(|check| {
+                (
+                    check.name.as_str(),
+                    check.status,
+                    check.completed_at.is_some(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            checks,
+            vec![
+                ("build / build", CheckStatus::Failure, true),
+                ("test / rspec", CheckStatus::Pending, false),
+            ]
+        );
+    }
+}
@@ -1,17 +1,110 @@
 use anyhow::{Context, Result, anyhow};
+use serde_json::json;
+use std::borrow::Cow;
 use std::io::Write;
 use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use crate::config::{AutoNameConfig, LlmProvider};
 
 const DEFAULT_SYSTEM_PROMPT: &str = r#"Generate a short, valid git branch name (kebab-case) based on the user's input.
 Output ONLY the branch name."#;
 
-pub fn generate_branch_name(
-    prompt: &str,
-    model: Option<&str>,
-    system_prompt: Option<&str>,
-) -> Result<String> {
-    let system = system_prompt.unwrap_or(DEFAULT_SYSTEM_PROMPT);
-    let full_prompt = format!("{}\n\nUser Input:\n{}", system, prompt);
+const DEFAULT_COMMIT_PROMPT: &str = r#"Write a git commit message for the staged changes in the user's diff.
+
+Rules:
+- First line: imperative summary of at most 72 characters, no trailing period
+- If the change needs explaining, add a blank line and a short body saying what changed and why
+- Do not walk through the diff line by line
+
+Output ONLY the commit message."#;
+
+const DEFAULT_PR_PROMPT: &str = r#"Write a pull request title and description for the user's commits and diff.
+
+Rules:
+- First line: the title, imperative, at most 72 characters, no trailing period
+- Then a blank line and a concise markdown description of what changed and why
+- Use bullet points when there are several independent changes
+
+Output ONLY the title and description."#;
+
+/// How long to wait for an HTTP provider to answer.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Diffs are cut to this many bytes before being sent, to stay within context limits.
+const MAX_DIFF_BYTES: usize = 60_000;
+
+/// A generated pull request title and body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrDescription {
+    pub title: String,
+    pub body: String,
+}
+
+pub fn generate_branch_name(prompt: &str, config: &AutoNameConfig) -> Result<String> {
+    let system = config
+        .system_prompt
+        .as_deref()
+        .unwrap_or(DEFAULT_SYSTEM_PROMPT);
+
+    let raw = complete(system, prompt, config)?;
+    let branch_name = sanitize_branch_name(raw.trim());
+
+    if branch_name.is_empty() {
+        return Err(anyhow!("LLM returned empty branch name"));
+    }
+
+    Ok(branch_name)
+}
+
+/// Generate a commit message for a diff (usually `git diff --cached`).
+pub fn generate_commit_message(diff: &str, config: &AutoNameConfig) -> Result<String> {
+    let system = config
+        .commit_prompt
+        .as_deref()
+        .unwrap_or(DEFAULT_COMMIT_PROMPT);
+
+    let raw = complete(system, &truncate_diff(diff), config)?;
+    let message = strip_code_fence(&raw);
+
+    if message.is_empty() {
+        return Err(anyhow!("LLM returned empty commit message"));
+    }
+
+    Ok(message.to_string())
+}
+
+/// Generate a PR title and body from the branch's commit log and diff.
+pub fn generate_pr_description(
+    commits: &str,
+    diff: &str,
+    config: &AutoNameConfig,
+) -> Result<PrDescription> {
+    let system = config.pr_prompt.as_deref().unwrap_or(DEFAULT_PR_PROMPT);
+    let input = format!("Commits:\n{}\n\nDiff:\n{}", commits, truncate_diff(diff));
+
+    let raw = complete(system, &input, config)?;
+    parse_pr_description(&raw)
+}
+
+/// Send a system prompt and user input to the configured provider and return its reply.
+pub fn complete(system: &str, input: &str, config: &AutoNameConfig) -> Result<String> {
+    match config.provider() {
+        LlmProvider::Llm => complete_with_llm_cli(system, input, config.model.as_deref()),
+        LlmProvider::Openai => {
+            let model = config.model.as_deref().ok_or_else(|| {
+                anyhow!("auto_name.model is required when auto_name.provider is 'openai'")
+            })?;
+            let api_key = std::env::var(config.api_key_env())
+                .ok()
+                .filter(|k| !k.is_empty());
+            complete_with_openai(config.base_url(), api_key.as_deref(), model, system, input)
+        }
+    }
+}
+
+fn complete_with_llm_cli(system: &str, input: &str, model: Option<&str>) -> Result<String> {
+    let full_prompt = format!("{}\n\nUser Input:\n{}", system, input);
 
     let mut cmd = Command::new("llm");
     if let Some(m) = model {
@@ -36,14 +129,111 @@ pub fn generate_branch_name(
         return Err(anyhow!("llm command failed: {}", stderr));
     }
 
-    let raw = String::from_utf8(output.stdout)?;
-    let branch_name = sanitize_branch_name(raw.trim());
+    Ok(String::from_utf8(output.stdout)?)
+}
 
-    if branch_name.is_empty() {
-        return Err(anyhow!("LLM returned empty branch name"));
+/// Call an OpenAI-compatible `/chat/completions` endpoint (OpenAI, Ollama, llama.cpp, ...).
+fn complete_with_openai(
+    base_url: &str,
+    api_key: Option<&str>,
+    model: &str,
+    system: &str,
+    input: &str,
+) -> Result<String> {
+    let url = format!("{}/chat/completions", base_url.trim_end_matches('/'));
+    let body = json!({
+        "model": model,
+        "messages": [
+            { "role": "system", "content": system },
+            { "role": "user", "content": input },
+        ],
+    });
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(HTTP_TIMEOUT))
+        .http_status_as_error(false)
+        .build()
+        .into();
+    let mut request = agent.post(&url);
+    if let Some(key) = api_key {
+        request = request.header("Authorization", &format!("Bearer {}", key));
     }
+    let mut response = request
+        .send_json(&body)
+        .with_context(|| format!("Failed to reach {}", url))?;
 
-    Ok(branch_name)
+    let status = response.status();
+    let text = response
+        .body_mut()
+        .read_to_string()
+        .context("Failed to read LLM response")?;
+    let reply: serde_json::Value = serde_json::from_str(&text).ok().unwrap_or_default();
+
+    if !status.is_success() {
+        let message = reply
+            .pointer("/error/message")
+            .and_then(|m| m.as_str())
+            .unwrap_or(text.trim());
+        return Err(anyhow!(
+            "LLM request to {} failed ({}): {}",
+            url,
+            status,
+            message
+        ));
+    }
+
+    reply
+        .pointer("/choices/0/message/content")
+        .and_then(|c| c.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("Unexpected response from {}: {}", url, text.trim()))
+}
+
+fn truncate_diff(diff: &str) -> Cow<'_, str> {
+    if diff.len() <= MAX_DIFF_BYTES {
+        return Cow::Borrowed(diff);
+    }
+    let mut end = MAX_DIFF_BYTES;
+    while !diff.is_char_boundary(end) {
+        end -= 1;
+    }
+    Cow::Owned(format!(
+        "{}\n[diff truncated, {} more bytes]",
+        &diff[..end],
+        diff.len() - end
+    ))
+}
+
+/// Trim the reply and unwrap it if the model put it inside a code block.
+fn strip_code_fence(raw: &str) -> &str {
+    let trimmed = raw.trim();
+    if let Some(rest) = trimmed.strip_prefix("```")
+        && let Some(inner) = rest.strip_suffix("```")
+    {
+        // Drop the info string (e.g. "```text") on the opening line
+        return inner.split_once('\n').map_or("", |(_, body)| body).trim();
+    }
+    trimmed
+}
+
+fn parse_pr_description(raw: &str) -> Result<PrDescription> {
+    let text = strip_code_fence(raw);
+    let (title, body) = text.split_once('\n').unwrap_or((text, ""));
+    let title = title
+        .trim()
+        .trim_start_matches('#')
+        .trim()
+        .trim_start_matches("Title:")
+        .trim();
+
+    if title.is_empty() {
+        return Err(anyhow!("LLM returned empty PR title"));
+    }
+
+    Ok(PrDescription {
+        title: title.to_string(),
+        body: body.trim().to_string(),
+    })
 }
 
 fn sanitize_branch_name(raw: &str) -> String {
@@ -63,6 +253,91 @@ fn sanitize_branch_name(raw: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::stub_server_with_status;
+
+    #[test]
+    fn openai_provider_posts_chat_completion() {
+        let (url, server) = stub_server_with_status(vec![(
+            "200 OK",
+            r#"{"choices":[{"message":{"role":"assistant","content":"add-user-auth"}}]}"#
+                .to_string(),
+        )]);
+        let url = format!("{}/v1", url);
+
+        let reply =
+            complete_with_openai(&url, Some("sk-test"), "qwen2.5", "system text", "add auth")
+                .unwrap();
+        assert_eq!(reply, "add-user-auth");
+
+        let request = server.join().unwrap().remove(0);
+        assert!(request.starts_with("POST /v1/chat/completions HTTP/1.1"));
+        assert!(
+            request
+                .to_lowercase()
+                .contains("authorization: bearer sk-test")
+        );
+        let body: serde_json::Value =
+            serde_json::from_str(request.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+        assert_eq!(body["model"], "qwen2.5");
+        assert_eq!(body["messages"][0]["content"], "system text");
+        assert_eq!(body["messages"][1]["content"], "add auth");
+    }
+
+    #[test]
+    fn openai_provider_reports_api_errors() {
+        let (url, server) = stub_server_with_status(vec![(
+            "401 Unauthorized",
+            r#"{"error":{"message":"Invalid API key"}}"#.to_string(),
+        )]);
+        let url = format!("{}/v1", url);
+
+        let err = complete_with_openai(&url, None, "gpt-4o-mini", "s", "i").unwrap_err();
+        let request = server.join().unwrap().remove(0);
+        assert!(!request.to_lowercase().contains("authorization:"));
+        assert!(err.to_string().contains("401"));
+        assert!(err.to_string().contains("Invalid API key"));
+    }
+
+    #[test]
+    fn openai_provider_requires_a_model() {
+        let config = AutoNameConfig {
+            provider: Some(LlmProvider::Openai),
+            ..Default::default()
+        };
+        let err = complete("s", "i", &config).unwrap_err();
+        assert!(err.to_string().contains("auto_name.model is required"));
+    }
+
+    #[test]
+    fn commit_message_is_unwrapped_from_code_fences() {
+        assert_eq!(
+            strip_code_fence("```text\nFix typo in README\n\nIt said teh.\n```\n"),
+            "Fix typo in README\n\nIt said teh."
+        );
+        assert_eq!(strip_code_fence("  Fix typo\n"), "Fix typo");
+    }
+
+    #[test]
+    fn pr_description_splits_title_and_body() {
+        let pr = parse_pr_description("# Title: Add OAuth login\n\n- Adds a provider\n").unwrap();
+        assert_eq!(pr.title, "Add OAuth login");
+        assert_eq!(pr.body, "- Adds a provider");
+
+        let pr = parse_pr_description("Bump deps").unwrap();
+        assert_eq!(pr.title, "Bump deps");
+        assert_eq!(pr.body, "");
+
+        assert!(parse_pr_description("\n\n").is_err());
+    }
+
+    #[test]
+    fn long_diffs_are_truncated_on_a_char_boundary() {
+        let diff = "é".repeat(MAX_DIFF_BYTES);
+        let truncated = truncate_diff(&diff);
+        assert!(truncated.len() < diff.len());
+        assert!(truncated.ends_with(&format!("[diff truncated, {} more bytes]", MAX_DIFF_BYTES)));
+        assert!(matches!(truncate_diff("small"), Cow::Borrowed("small")));
+    }
 
     #[test]
     fn sanitize_branch_name_simple() {
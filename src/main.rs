@@ -1,10 +1,17 @@
+// The config JSON Schema is a single large `json!` literal.
+#![recursion_limit = "256"]
+
 mod claude;
 mod cli;
 mod cmd;
 mod command;
 mod config;
+mod config_schema;
+mod forge;
 mod git;
+mod gitea;
 mod github;
+mod gitlab;
 mod llm;
 mod logger;
 mod markdown;
@@ -12,9 +19,12 @@ mod multiplexer;
 mod naming;
 mod nerdfont;
 mod prompt;
+mod prompt_library;
 mod spinner;
 mod state;
 mod template;
+#[cfg(test)]
+mod test_support;
 mod workflow;
 
 use anyhow::Result;
@@ -197,7 +197,11 @@ pub trait Multiplexer: Send + Sync {
             let pane_id = if let Some(resolved) = adjusted_command {
                 // Spawn with handshake so we can send the command after shell is ready
                 let handshake = self.create_handshake()?;
-                let script = handshake.script_content(&shell);
+                let script = format!(
+                    "{}{}",
+                    util::export_prefix(options.env),
+                    handshake.script_content(&shell)
+                );
 
                 let spawned_id = if is_first {
                     self.respawn_pane(&pane_ids[0], working_dir, Some(&script))?
@@ -218,7 +222,12 @@ pub trait Multiplexer: Send + Sync {
                 };
 
                 handshake.wait()?;
-                self.send_keys(&spawned_id, &resolved.command)?;
+                match options.wait_command {
+                    Some(wait) => {
+                        self.send_keys(&spawned_id, &format!("{} && {}", wait, resolved.command))?
+                    }
+                    None => self.send_keys(&spawned_id, &resolved.command)?,
+                }
 
                 // Set working status for agent panes with injected prompts
                 if resolved.prompt_injected
@@ -66,6 +66,10 @@ pub struct PaneSetupOptions<'a> {
     pub run_commands: bool,
     /// Path to the prompt file for agent panes
     pub prompt_file_path: Option<&'a std::path::Path>,
+    /// Extra environment variables exported in panes that run a command
+    pub env: &'a [(String, String)],
+    /// Command that must succeed before pane commands start (background hooks)
+    pub wait_command: Option<&'a str>,
 }
 
 /// Backend type for multiplexer selection
@@ -165,6 +165,20 @@ pub fn adjust_command<'a>(
     Cow::Borrowed(command)
 }
 
+/// Build a POSIX `export K='V' ...; ` prefix for a pane startup script.
+///
+/// Returns an empty string when there is nothing to export.
+pub fn export_prefix(env: &[(String, String)]) -> String {
+    if env.is_empty() {
+        return String::new();
+    }
+    let assignments: Vec<String> = env
+        .iter()
+        .map(|(k, v)| format!("{}='{}'", k, v.replace('\'', "'\\''")))
+        .collect();
+    format!("export {}; ", assignments.join(" "))
+}
+
 /// Escape a string for embedding inside a double-quoted shell context.
 ///
 /// Escapes: backslash, double quote, dollar sign, backtick.
@@ -195,6 +209,22 @@ pub fn escape_for_sh_c_inner_single_quote(s: &str) -> String {
     escape_for_double_quotes(&single_escaped)
 }
 
+/// Quote a value as a single shell word. Values made only of characters that
+/// no shell treats specially are returned as-is.
+///
+/// Example: `it's` -> `'it'\''s'`
+pub fn shell_quote(s: &str) -> String {
+    let plain = !s.is_empty()
+        && s.chars().all(|c| {
+            c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | ':' | '@' | '+' | ',')
+        });
+    if plain {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+}
+
 /// Wrap a command in `sh -c '...'` for execution in non-POSIX shells.
 ///
 /// Used when the default shell (nushell, fish, etc.) doesn't support
@@ -367,6 +397,14 @@ mod tests {
 
     // --- escape_for_double_quotes tests ---
 
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("feature/auth-2"), "feature/auth-2");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("/tmp/it's"), "'/tmp/it'\\''s'");
+        assert_eq!(shell_quote("$(touch x)"), "'$(touch x)'");
+    }
+
     #[test]
     fn test_escape_for_double_quotes_simple() {
         assert_eq!(escape_for_double_quotes("hello"), "hello");
@@ -554,4 +592,17 @@ mod tests {
         assert!(!resolved.prompt_injected);
         assert_eq!(resolved.command, "vim");
     }
+
+    #[test]
+    fn test_export_prefix() {
+        assert_eq!(export_prefix(&[]), "");
+        let env = vec![
+            ("WM_PORT".to_string(), "4000".to_string()),
+            ("QUOTED".to_string(), "it's".to_string()),
+        ];
+        assert_eq!(
+            export_prefix(&env),
+            "export WM_PORT='4000' QUOTED='it'\\''s'; "
+        );
+    }
 }
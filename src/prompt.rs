@@ -1,9 +1,11 @@
-use anyhow::{Context, Result};
-use serde::Deserialize;
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Deserializer};
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::config::{Config, FileConfig, HookEntry, PaneConfig};
+
 #[derive(Debug, Clone)]
 pub enum Prompt {
     Inline(String),
@@ -23,10 +25,100 @@ impl Prompt {
     }
 }
 
+/// Settings a prompt's frontmatter can carry, so a prompt file describes a
+/// whole task. Command-line flags take precedence over all of them.
 #[derive(Debug, Deserialize, Default)]
 pub struct PromptMetadata {
     #[serde(default)]
     pub foreach: Option<BTreeMap<String, Vec<String>>>,
+
+    /// One-line summary shown by `workmux prompts list`
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Agent(s) to run, like repeating `--agent`
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub agent: Vec<String>,
+
+    /// Base branch, commit or tag to branch from, like `--base`
+    #[serde(default)]
+    pub base: Option<String>,
+
+    /// Branch to create when none is given on the command line
+    #[serde(default)]
+    pub branch: Option<String>,
+
+    /// Worktree and window name, like `--name`
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Template for branch names in multi-worktree modes, like `--branch-template`
+    #[serde(default)]
+    pub branch_template: Option<String>,
+
+    /// Generate the branch name from the prompt, like `--auto-name`
+    #[serde(default)]
+    pub auto_name: bool,
+
+    /// Pane layout replacing the configured `panes`
+    #[serde(default)]
+    pub panes: Option<Vec<PaneConfig>>,
+
+    /// Hooks run after the configured `post_create` hooks
+    #[serde(default)]
+    pub post_create: Vec<HookEntry>,
+
+    /// File operations run in addition to the configured `files`
+    #[serde(default)]
+    pub files: FileConfig,
+
+    /// Labels added to the PR opened for the branch with `workmux pr create`
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub labels: Vec<String>,
+}
+
+impl PromptMetadata {
+    /// Layer the prompt's panes, hooks and file operations on top of `config`.
+    pub fn apply_to(&self, mut config: Config) -> Config {
+        fn append<T: Clone>(items: &mut Option<Vec<T>>, extra: &[T]) {
+            if !extra.is_empty() {
+                items.get_or_insert_default().extend_from_slice(extra);
+            }
+        }
+
+        if let Some(panes) = &self.panes {
+            config.panes = Some(panes.clone());
+        }
+        append(&mut config.post_create, &self.post_create);
+        let files = &self.files;
+        append(
+            &mut config.files.copy,
+            files.copy.as_deref().unwrap_or_default(),
+        );
+        append(
+            &mut config.files.symlink,
+            files.symlink.as_deref().unwrap_or_default(),
+        );
+        append(
+            &mut config.files.template,
+            files.template.as_deref().unwrap_or_default(),
+        );
+        config
+    }
+}
+
+/// Accept either a single string or a list of strings.
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
 }
 
 #[derive(Debug)]
@@ -87,11 +179,16 @@ pub fn parse_prompt_document(prompt: &Prompt) -> Result<PromptDocument> {
 
     let (frontmatter_yaml, body) = split_frontmatter(content);
 
-    let meta = if let Some(ref yaml) = frontmatter_yaml {
+    let meta: PromptMetadata = if let Some(ref yaml) = frontmatter_yaml {
         serde_yaml::from_str(yaml).context("Failed to parse YAML frontmatter")?
     } else {
         PromptMetadata::default()
     };
+    if meta.branch.is_some() && meta.auto_name {
+        return Err(anyhow!(
+            "Set either 'branch' or 'auto_name' in the prompt frontmatter, not both"
+        ));
+    }
 
     Ok(PromptDocument {
         body: body.to_string(),
@@ -242,6 +339,59 @@ mod tests {
         assert!(doc.meta.foreach.is_none());
     }
 
+    #[test]
+    fn parse_prompt_document_with_task_settings() {
+        let content = "---\nagent: codex\nbase: develop\nbranch: fix-login\nlabels: agent\npanes:\n  - command: <agent>\n    focus: true\npost_create:\n  - npm install\nfiles:\n  symlink: [node_modules]\n---\nFix the login form";
+        let doc = parse_prompt_document(&Prompt::Inline(content.to_string())).unwrap();
+
+        assert_eq!(doc.meta.agent, vec!["codex"]);
+        assert_eq!(doc.meta.base.as_deref(), Some("develop"));
+        assert_eq!(doc.meta.branch.as_deref(), Some("fix-login"));
+        assert_eq!(doc.meta.labels, vec!["agent"]);
+        assert!(!doc.meta.auto_name);
+        assert_eq!(doc.body, "Fix the login form");
+    }
+
+    #[test]
+    fn parse_prompt_document_rejects_branch_with_auto_name() {
+        let content = "---\nbranch: fix-login\nauto_name: true\n---\nFix it";
+        let err = parse_prompt_document(&Prompt::Inline(content.to_string())).unwrap_err();
+        assert!(err.to_string().contains("not both"));
+    }
+
+    #[test]
+    fn apply_to_replaces_panes_and_appends_hooks_and_files() {
+        let config: Config = serde_yaml::from_str(
+            "panes:\n  - command: vim\n  - command: <agent>\npost_create:\n  - mise install\nfiles:\n  copy: [.env]\n",
+        )
+        .unwrap();
+        let meta: PromptMetadata = serde_yaml::from_str(
+            "panes:\n  - command: <agent>\npost_create:\n  - npm install\nfiles:\n  copy: [.env.test]\n  symlink: [node_modules]\n",
+        )
+        .unwrap();
+
+        let config = meta.apply_to(config);
+        assert_eq!(config.panes.unwrap().len(), 1);
+        assert_eq!(
+            config.post_create.unwrap(),
+            vec![
+                HookEntry::Command("mise install".to_string()),
+                HookEntry::Command("npm install".to_string()),
+            ]
+        );
+        assert_eq!(config.files.copy.unwrap().len(), 2);
+        assert_eq!(config.files.symlink.unwrap(), vec!["node_modules"]);
+    }
+
+    #[test]
+    fn apply_to_without_settings_keeps_config() {
+        let config: Config = serde_yaml::from_str("post_create:\n  - mise install\n").unwrap();
+        let config = PromptMetadata::default().apply_to(config);
+        assert!(config.panes.is_none());
+        assert_eq!(config.post_create.unwrap().len(), 1);
+        assert!(config.files.copy.is_none());
+    }
+
     #[test]
     fn foreach_from_frontmatter_creates_rows() {
         let mut map = BTreeMap::new();
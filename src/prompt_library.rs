@@ -0,0 +1,286 @@
+//! Named, reusable prompts kept in `.workmux/prompts/` in the repository and in
+//! `~/.config/workmux/prompts/`.
+//!
+//! A prompt's name is its path relative to the library directory without the
+//! `.md` extension (`review`, `partials/tests-first`). Project prompts shadow
+//! global prompts with the same name. Prompts can pull in each other with
+//! `{% include %}` and `{% extends %}`, resolved by the template loader.
+
+use anyhow::{Result, anyhow};
+use std::path::{Component, Path, PathBuf};
+
+use crate::git;
+use crate::prompt::{Prompt, parse_prompt_document};
+
+/// Library directory relative to the repository root
+pub const PROJECT_PROMPTS_DIR: &str = ".workmux/prompts";
+
+/// Where a library prompt was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptScope {
+    Project,
+    Global,
+}
+
+impl PromptScope {
+    pub fn label(self) -> &'static str {
+        match self {
+            PromptScope::Project => "project",
+            PromptScope::Global => "global",
+        }
+    }
+}
+
+/// A prompt in the library
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibraryPrompt {
+    pub name: String,
+    pub path: PathBuf,
+    pub scope: PromptScope,
+}
+
+impl LibraryPrompt {
+    /// The frontmatter `description`, or else the first line of the prompt
+    pub fn description(&self) -> String {
+        let Ok(doc) = parse_prompt_document(&Prompt::FromFile(self.path.clone())) else {
+            return String::new();
+        };
+        doc.meta.description.unwrap_or_else(|| {
+            doc.body
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty() && !line.starts_with("{%"))
+                .unwrap_or_default()
+                .trim_start_matches('#')
+                .trim()
+                .to_string()
+        })
+    }
+}
+
+/// Library directories that exist, project ones first.
+///
+/// The main worktree's library is also searched from linked worktrees, so
+/// prompts that aren't committed yet are still found.
+pub fn library_dirs() -> Vec<(PromptScope, PathBuf)> {
+    let mut dirs = Vec::new();
+    let roots = [
+        git::get_repo_root().ok(),
+        git::get_main_worktree_root().ok(),
+    ];
+    for root in roots.into_iter().flatten() {
+        let dir = root.join(PROJECT_PROMPTS_DIR);
+        if dir.is_dir() && !dirs.iter().any(|(_, known)| known == &dir) {
+            dirs.push((PromptScope::Project, dir));
+        }
+    }
+    if let Some(home) = home::home_dir() {
+        let dir = home.join(".config/workmux/prompts");
+        if dir.is_dir() {
+            dirs.push((PromptScope::Global, dir));
+        }
+    }
+    dirs
+}
+
+/// All prompts in the library, sorted by name
+pub fn list() -> Vec<LibraryPrompt> {
+    list_in(&library_dirs())
+}
+
+fn list_in(dirs: &[(PromptScope, PathBuf)]) -> Vec<LibraryPrompt> {
+    let mut prompts: Vec<LibraryPrompt> = Vec::new();
+    for (scope, dir) in dirs {
+        let pattern = dir.join("**/*.md");
+        let Ok(paths) = glob::glob(&pattern.to_string_lossy()) else {
+            continue;
+        };
+        for path in paths.flatten() {
+            let Some(name) = path
+                .strip_prefix(dir)
+                .ok()
+                .map(|rel| rel.with_extension("").to_string_lossy().replace('\\', "/"))
+            else {
+                continue;
+            };
+            if !prompts.iter().any(|known| known.name == name) {
+                prompts.push(LibraryPrompt {
+                    name,
+                    path,
+                    scope: *scope,
+                });
+            }
+        }
+    }
+    prompts.sort_by(|a, b| a.name.cmp(&b.name));
+    prompts
+}
+
+/// Find a prompt by name, listing the available prompts when there's none
+pub fn find(name: &str) -> Result<LibraryPrompt> {
+    let dirs = library_dirs();
+    if let Some(prompt) = find_in(&dirs, name)? {
+        return Ok(prompt);
+    }
+    let available: Vec<String> = list_in(&dirs).into_iter().map(|p| p.name).collect();
+    if available.is_empty() {
+        return Err(anyhow!(
+            "Prompt '{}' not found: no prompts in {}/ or ~/.config/workmux/prompts/",
+            name,
+            PROJECT_PROMPTS_DIR
+        ));
+    }
+    Err(anyhow!(
+        "Prompt '{}' not found. Available prompts: {}",
+        name,
+        available.join(", ")
+    ))
+}
+
+fn find_in(dirs: &[(PromptScope, PathBuf)], name: &str) -> Result<Option<LibraryPrompt>> {
+    // Names stay inside the library directories
+    if !Path::new(name)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return Err(anyhow!(
+            "Invalid prompt name '{}': must be a relative path without '..'",
+            name
+        ));
+    }
+    let name = name.strip_suffix(".md").unwrap_or(name);
+    Ok(dirs.iter().find_map(|(scope, dir)| {
+        let path = dir.join(format!("{}.md", name));
+        path.is_file().then(|| LibraryPrompt {
+            name: name.to_string(),
+            path,
+            scope: *scope,
+        })
+    }))
+}
+
+/// Template loader for `{% include %}` and `{% extends %}` in prompts.
+/// Frontmatter of the included prompt is left out.
+pub fn load_template(name: &str) -> Result<Option<String>, minijinja::Error> {
+    load_template_in(&library_dirs(), name)
+}
+
+fn load_template_in(
+    dirs: &[(PromptScope, PathBuf)],
+    name: &str,
+) -> Result<Option<String>, minijinja::Error> {
+    let to_template_error = |e: anyhow::Error| {
+        minijinja::Error::new(minijinja::ErrorKind::InvalidOperation, format!("{:#}", e))
+    };
+    let Some(prompt) = find_in(dirs, name).map_err(to_template_error)? else {
+        return Ok(None);
+    };
+    let doc = parse_prompt_document(&Prompt::FromFile(prompt.path)).map_err(to_template_error)?;
+    Ok(Some(doc.body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        let path = dir.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn project_prompts_shadow_global_ones() {
+        let project = TempDir::new().unwrap();
+        let global = TempDir::new().unwrap();
+        write(project.path(), "review.md", "Review the project");
+        write(
+            project.path(),
+            "partials/tests-first.md",
+            "Write tests first",
+        );
+        write(global.path(), "review.md", "Review anything");
+        write(global.path(), "explain.md", "Explain the code");
+        let dirs = vec![
+            (PromptScope::Project, project.path().to_path_buf()),
+            (PromptScope::Global, global.path().to_path_buf()),
+        ];
+
+        let listed: Vec<(String, PromptScope)> = list_in(&dirs)
+            .into_iter()
+            .map(|p| (p.name, p.scope))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("explain".to_string(), PromptScope::Global),
+                ("partials/tests-first".to_string(), PromptScope::Project),
+                ("review".to_string(), PromptScope::Project),
+            ]
+        );
+
+        let review = find_in(&dirs, "review.md").unwrap().unwrap();
+        assert_eq!(review.scope, PromptScope::Project);
+        assert!(find_in(&dirs, "partials/tests-first").unwrap().is_some());
+        assert!(find_in(&dirs, "missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn names_cannot_leave_the_library() {
+        let root = TempDir::new().unwrap();
+        let library = root.path().join("prompts");
+        write(&library, "review.md", "Review");
+        write(root.path(), "secret.md", "Secret");
+        let dirs = vec![(PromptScope::Project, library.clone())];
+
+        assert!(find_in(&dirs, "../secret").is_err());
+        assert!(find_in(&dirs, &root.path().join("secret").to_string_lossy()).is_err());
+        assert!(load_template_in(&dirs, "../secret").is_err());
+        assert_eq!(
+            load_template_in(&dirs, "review").unwrap().as_deref(),
+            Some("Review")
+        );
+    }
+
+    #[test]
+    fn load_template_reports_broken_prompts() {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "broken.md",
+            "---
+description: [unclosed
+---
+Body",
+        );
+        let dirs = vec![(PromptScope::Project, dir.path().to_path_buf())];
+
+        assert!(load_template_in(&dirs, "broken").is_err());
+        assert_eq!(load_template_in(&dirs, "missing").unwrap(), None);
+    }
+
+    #[test]
+    fn description_prefers_frontmatter() {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "a.md",
+            "---\ndescription: Fix a bug\n---\n# Task\nBody",
+        );
+        write(
+            dir.path(),
+            "b.md",
+            "{% extends \"base\" %}\n\n# Review the diff\n",
+        );
+        let prompt = |name: &str| LibraryPrompt {
+            name: name.to_string(),
+            path: dir.path().join(format!("{}.md", name)),
+            scope: PromptScope::Project,
+        };
+
+        assert_eq!(prompt("a").description(), "Fix a bug");
+        assert_eq!(prompt("b").description(), "Review the diff");
+    }
+}
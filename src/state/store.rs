@@ -3,10 +3,12 @@
 use anyhow::{Context, Result};
 use std::fs;
 use std::io;
+use std::net::TcpListener;
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
 use tracing::warn;
 
-use super::types::{AgentState, GlobalSettings, PaneKey};
+use super::types::{AgentState, GlobalSettings, PaneKey, PortRegistry};
 
 /// Manages filesystem-based state persistence for workmux agents.
 ///
@@ -14,6 +16,9 @@ use super::types::{AgentState, GlobalSettings, PaneKey};
 /// ```text
 /// $XDG_STATE_HOME/workmux/           # ~/.local/state/workmux/
 /// ├── settings.json                   # Global dashboard settings
+/// ├── ports.json                      # Ports reserved per worktree
+/// ├── logs/hooks/                     # Output of each hook run
+/// ├── background/                     # Background post_create hook jobs
 /// └── agents/
 ///     ├── tmux__default__%1.json     # {backend}__{instance}__{pane_id}.json
 ///     └── wezterm__main__3.json
@@ -51,6 +56,25 @@ impl StateStore {
         self.base_path.join("settings.json")
     }
 
+    /// Path to the port registry file.
+    fn ports_path(&self) -> PathBuf {
+        self.base_path.join("ports.json")
+    }
+
+    /// Directory for hook output logs, created on demand.
+    pub fn hook_logs_dir(&self) -> Result<PathBuf> {
+        let dir = self.base_path.join("logs").join("hooks");
+        fs::create_dir_all(&dir).context("Failed to create hook logs directory")?;
+        Ok(dir)
+    }
+
+    /// Directory for background hook jobs and their status, created on demand.
+    pub fn background_jobs_dir(&self) -> Result<PathBuf> {
+        let dir = self.base_path.join("background");
+        fs::create_dir_all(&dir).context("Failed to create background jobs directory")?;
+        Ok(dir)
+    }
+
     /// Path to a specific agent's state file.
     fn agent_path(&self, key: &PaneKey) -> PathBuf {
         self.agents_dir().join(key.to_filename())
@@ -138,6 +162,90 @@ impl StateStore {
         write_atomic(&path, content.as_bytes())
     }
 
+    /// Reserve `count` ports for a worktree, reusing any it already holds.
+    ///
+    /// Entries for worktrees that no longer exist on disk are pruned first.
+    /// A ports lock file serializes concurrent `workmux add` invocations.
+    pub fn allocate_ports(
+        &self,
+        worktree_path: &Path,
+        count: u16,
+        range: RangeInclusive<u16>,
+    ) -> Result<Vec<u16>> {
+        let key = worktree_path.to_string_lossy().to_string();
+        let (start, end) = (*range.start(), *range.end());
+        self.with_port_registry(|registry| {
+            registry
+                .worktrees
+                .retain(|path, _| Path::new(path).exists());
+            registry
+                .allocate(&key, count as usize, range, is_port_free)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "No free ports left in range {}-{} (need {})",
+                        start,
+                        end,
+                        count
+                    )
+                })
+        })
+    }
+
+    /// Ports currently reserved for a worktree (empty if none).
+    pub fn get_ports(&self, worktree_path: &Path) -> Result<Vec<u16>> {
+        let key = worktree_path.to_string_lossy();
+        Ok(self
+            .read_port_registry()?
+            .worktrees
+            .remove(key.as_ref())
+            .unwrap_or_default())
+    }
+
+    /// Release all ports reserved for a worktree.
+    ///
+    /// No-op if the worktree holds no ports.
+    pub fn release_ports(&self, worktree_path: &Path) -> Result<()> {
+        let key = worktree_path.to_string_lossy().to_string();
+        self.with_port_registry(|registry| {
+            registry.worktrees.remove(&key);
+            Ok(())
+        })
+    }
+
+    /// Run `f` on the port registry while holding an exclusive lock, then save it.
+    fn with_port_registry<T>(&self, f: impl FnOnce(&mut PortRegistry) -> Result<T>) -> Result<T> {
+        let lock_file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.base_path.join("ports.lock"))
+            .context("Failed to open ports lock file")?;
+        // Released when `lock_file` is dropped
+        lock_file.lock().context("Failed to lock port registry")?;
+
+        let mut registry = self.read_port_registry()?;
+        let result = f(&mut registry)?;
+        let content = serde_json::to_string_pretty(&registry)?;
+        write_atomic(&self.ports_path(), content.as_bytes())?;
+        Ok(result)
+    }
+
+    /// Read the port registry. Returns an empty registry if missing or corrupted.
+    fn read_port_registry(&self) -> Result<PortRegistry> {
+        let path = self.ports_path();
+        match fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(registry) => Ok(registry),
+                Err(e) => {
+                    warn!(?path, error = %e, "corrupted port registry, starting fresh");
+                    Ok(PortRegistry::default())
+                }
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(PortRegistry::default()),
+            Err(e) => Err(e).context("Failed to read port registry"),
+        }
+    }
+
     /// Load agents with reconciliation against live multiplexer state.
     ///
     /// Two-layer exit detection:
@@ -210,6 +318,11 @@ fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
     Ok(())
 }
 
+/// Check that nothing is currently listening on a port.
+fn is_port_free(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
 /// Get the XDG state directory.
 ///
 /// Checks XDG_STATE_HOME first, falls back to ~/.local/state.
@@ -436,4 +549,37 @@ mod tests {
         let agents = store.list_all_agents().unwrap();
         assert_eq!(agents.len(), 1);
     }
+
+    #[test]
+    fn test_ports_persist_and_release() {
+        let (store, dir) = test_store();
+        let worktree = dir.path().join("wt");
+        fs::create_dir_all(&worktree).unwrap();
+
+        let ports = store.allocate_ports(&worktree, 2, 40000..=40999).unwrap();
+        assert_eq!(ports.len(), 2);
+        assert_eq!(store.get_ports(&worktree).unwrap(), ports);
+
+        // Reopening reuses the same ports
+        let again = store.allocate_ports(&worktree, 2, 40000..=40999).unwrap();
+        assert_eq!(again, ports);
+
+        store.release_ports(&worktree).unwrap();
+        assert!(store.get_ports(&worktree).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_ports_pruned_for_missing_worktrees() {
+        let (store, dir) = test_store();
+        let gone = dir.path().join("gone");
+        let live = dir.path().join("live");
+        fs::create_dir_all(&gone).unwrap();
+        fs::create_dir_all(&live).unwrap();
+
+        store.allocate_ports(&gone, 1, 40000..=40999).unwrap();
+        fs::remove_dir_all(&gone).unwrap();
+        store.allocate_ports(&live, 1, 40000..=40999).unwrap();
+
+        assert!(store.get_ports(&gone).unwrap().is_empty());
+    }
 }
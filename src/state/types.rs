@@ -2,6 +2,8 @@
 
 use percent_encoding::{AsciiSet, CONTROLS, percent_decode_str, utf8_percent_encode};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::ops::RangeInclusive;
 use std::path::PathBuf;
 
 /// Characters that need encoding in filenames (beyond control chars).
@@ -130,6 +132,53 @@ pub struct GlobalSettings {
     pub last_pane_id: Option<String>,
 }
 
+/// Ports reserved per worktree, stored in a single registry file.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct PortRegistry {
+    /// Reserved ports keyed by absolute worktree path
+    #[serde(default)]
+    pub worktrees: BTreeMap<String, Vec<u16>>,
+}
+
+impl PortRegistry {
+    /// Reserve `count` ports for a worktree from `range`.
+    ///
+    /// Ports the worktree already holds are kept (so reopening reuses them, even if
+    /// its own dev server is currently bound to them). New ports must be unreserved
+    /// and pass `is_free`. Returns None if the range is exhausted.
+    pub fn allocate(
+        &mut self,
+        key: &str,
+        count: usize,
+        range: RangeInclusive<u16>,
+        is_free: impl Fn(u16) -> bool,
+    ) -> Option<Vec<u16>> {
+        let taken: HashSet<u16> = self
+            .worktrees
+            .iter()
+            .filter(|(k, _)| k.as_str() != key)
+            .flat_map(|(_, ports)| ports.iter().copied())
+            .collect();
+
+        let mut ports = self.worktrees.get(key).cloned().unwrap_or_default();
+        ports.truncate(count);
+        for port in range {
+            if ports.len() >= count {
+                break;
+            }
+            if !taken.contains(&port) && !ports.contains(&port) && is_free(port) {
+                ports.push(port);
+            }
+        }
+
+        if ports.len() < count {
+            return None;
+        }
+        self.worktrees.insert(key.to_string(), ports.clone());
+        Some(ports)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -201,4 +250,49 @@ mod tests {
         assert_eq!(parsed.instance, "/private/tmp/tmux-501/default");
         assert_eq!(parsed.pane_id, "%79");
     }
+
+    #[test]
+    fn test_port_registry_allocates_unique_ports() {
+        let mut registry = PortRegistry::default();
+        let a = registry
+            .allocate("/wt/a", 2, 4000..=4010, |_| true)
+            .unwrap();
+        let b = registry
+            .allocate("/wt/b", 2, 4000..=4010, |_| true)
+            .unwrap();
+
+        assert_eq!(a, vec![4000, 4001]);
+        assert_eq!(b, vec![4002, 4003]);
+    }
+
+    #[test]
+    fn test_port_registry_reuses_existing_ports() {
+        let mut registry = PortRegistry::default();
+        let first = registry
+            .allocate("/wt/a", 1, 4000..=4010, |_| true)
+            .unwrap();
+        // The worktree's own server now holds the port; it must still be reused
+        let again = registry
+            .allocate("/wt/a", 2, 4000..=4010, |p| p != 4000)
+            .unwrap();
+
+        assert_eq!(first, vec![4000]);
+        assert_eq!(again, vec![4000, 4001]);
+    }
+
+    #[test]
+    fn test_port_registry_skips_busy_and_exhausts() {
+        let mut registry = PortRegistry::default();
+        let ports = registry
+            .allocate("/wt/a", 1, 4000..=4001, |p| p != 4000)
+            .unwrap();
+        assert_eq!(ports, vec![4001]);
+
+        assert!(
+            registry
+                .allocate("/wt/b", 1, 4000..=4001, |p| p != 4000)
+                .is_none()
+        );
+        assert!(!registry.worktrees.contains_key("/wt/b"));
+    }
 }
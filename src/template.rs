@@ -4,6 +4,11 @@ use serde_json::{Map as JsonMap, Number as JsonNumber, Value as JsonValue};
 use std::collections::{BTreeMap, HashSet};
 use std::path::Path;
 
+use crate::multiplexer::util::shell_quote;
+
+/// Branch name template for multi-worktree modes when none is given
+pub const DEFAULT_BRANCH_TEMPLATE: &str = r#"{{ base_name }}{% if agent %}-{{ agent | slugify }}{% endif %}{% for key in foreach_vars %}-{{ foreach_vars[key] | slugify }}{% endfor %}{% if num %}-{{ num }}{% endif %}"#;
+
 /// Reserved template variable names that cannot be used in foreach
 const RESERVED_TEMPLATE_KEYS: &[&str] = &["base_name", "agent", "num", "index", "foreach_vars"];
 
@@ -22,6 +27,8 @@ pub fn create_template_env() -> TemplateEnv {
     env.set_auto_escape_callback(|_| AutoEscape::None);
     env.set_keep_trailing_newline(true);
     env.add_filter("slugify", slugify_filter);
+    // `{% include %}` and `{% extends %}` resolve against the prompt library
+    env.set_loader(crate::prompt_library::load_template);
     env
 }
 
@@ -31,6 +38,40 @@ pub fn render_prompt_body(body: &str, env: &TemplateEnv, context: &JsonValue) ->
         .context("Failed to render prompt template")
 }
 
+/// Parse a `--var key=value` argument.
+pub fn parse_template_var(arg: &str) -> Result<(String, String)> {
+    let (key, value) = arg
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Invalid variable '{}'. Use the format key=value", arg))?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(anyhow!("Invalid variable '{}': name cannot be empty", arg));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Add `--var` values to the prompt context of every spec. They can't replace
+/// the built-in variables or the ones from foreach, stdin or an issue.
+pub fn add_template_vars(specs: &mut [WorktreeSpec], vars: &[(String, String)]) -> Result<()> {
+    for spec in specs {
+        let JsonValue::Object(context) = &mut spec.template_context else {
+            continue;
+        };
+        if let Some((key, _)) = vars.iter().find(|(key, _)| {
+            RESERVED_TEMPLATE_KEYS.contains(&key.as_str()) || context.contains_key(key)
+        }) {
+            return Err(anyhow!(
+                "--var '{}' conflicts with a variable workmux already sets",
+                key
+            ));
+        }
+        for (key, value) in vars {
+            context.insert(key.clone(), JsonValue::String(value.clone()));
+        }
+    }
+    Ok(())
+}
+
 /// Validate that all variables used in the template exist in the provided context.
 /// Returns an error listing missing variables and available ones if validation fails.
 pub fn validate_template_variables(
@@ -49,15 +90,17 @@ pub fn validate_template_variables(
         _ => HashSet::new(),
     };
 
-    let missing_vars: Vec<&str> = required_vars
+    // Nested lookups ("issue.title") only need their root variable
+    let mut missing_vars: Vec<&str> = required_vars
         .iter()
-        .map(|s| s.as_str())
+        .map(|s| s.split('.').next().unwrap_or(s))
         .filter(|req| !available_vars.contains(req))
         .collect();
+    missing_vars.sort();
+    missing_vars.dedup();
 
     if !missing_vars.is_empty() {
-        let mut missing_sorted = missing_vars.clone();
-        missing_sorted.sort();
+        let missing_sorted = missing_vars;
 
         let mut available_sorted: Vec<&str> = available_vars.into_iter().collect();
         available_sorted.sort();
@@ -72,6 +115,144 @@ pub fn validate_template_variables(
     Ok(())
 }
 
+/// Interpolate a pane or hook command before it is handed to the shell.
+///
+/// Only `{{ ... }}` expressions that use nothing but the variables in `context` are
+/// rendered, and each result is shell-quoted so values like branch names can't run
+/// anything. Other `{{ ... }}` (e.g. Go templates in `docker ps --format '{{.Names}}'`)
+/// and all `${VAR}` references are passed through for the shell, which gets the
+/// workmux variables in its environment.
+pub fn interpolate(value: &str, context: &JsonValue) -> Result<String> {
+    if !value.contains("{{") {
+        return Ok(value.to_string());
+    }
+    if !value.contains("{{") {
+        return Ok(value.to_string());
+    }
+    let env = create_template_env();
+    let known: HashSet<&str> = match context {
+        JsonValue::Object(map) => map.keys().map(|k| k.as_str()).collect(),
+        _ => HashSet::new(),
+    };
+
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}").map(|i| start + i + 2) else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let expression = &rest[start..end];
+        let template = env.template_from_str(expression).ok().filter(|tmpl| {
+            let vars = tmpl.undeclared_variables(false);
+            !vars.is_empty() && vars.iter().all(|v| known.contains(v.as_str()))
+        });
+        match template {
+            Some(tmpl) => out.push_str(&shell_quote(
+                &tmpl
+                    .render(context)
+                    .with_context(|| format!("Failed to render '{}'", expression))?,
+            )),
+            None => out.push_str(expression),
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Render `{{ ... }}` and `{% ... %}` in a config value. Values without them are
+/// returned unchanged, so shell syntax like `${#list[@]}` is never parsed as a template.
+pub fn render_expressions(value: &str, context: &JsonValue) -> Result<String> {
+    if !value.contains("{{") && !value.contains("{%") {
+        return Ok(value.to_string());
+    }
+    let env = create_template_env();
+    validate_template_variables(&env, value, context)?;
+    env.render_str(value, context)
+        .context("Failed to render template expression")
+}
+
+/// Expand `${VAR}` and `${VAR:-default}` references, looking variables up in `env`
+/// before the process environment. `$${` produces a literal `${`.
+///
+/// References to unset variables without a default are left untouched so a shell can
+/// still expand them later; their names are returned alongside the result. Anything
+/// that isn't a plain variable name (e.g. `${#list[@]}`) is also left as-is.
+pub fn expand_env_vars(value: &str, env: &[(&str, &str)]) -> (String, Vec<String>) {
+    let mut out = String::with_capacity(value.len());
+    let mut unset = Vec::new();
+    let mut rest = value;
+
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos..];
+
+        if let Some(escaped) = after.strip_prefix("$${") {
+            out.push_str("${");
+            rest = escaped;
+            continue;
+        }
+
+        let reference = after
+            .strip_prefix("${")
+            .and_then(|inner| closing_brace(inner).map(|end| (&inner[..end], &inner[end + 1..])));
+        let Some((body, remainder)) = reference else {
+            out.push('$');
+            rest = &after[1..];
+            continue;
+        };
+
+        let (name, default) = match body.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (body, None),
+        };
+        let is_name = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_name {
+            out.push_str(&after[..after.len() - remainder.len()]);
+            rest = remainder;
+            continue;
+        }
+
+        let current = env
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, v)| v.to_string())
+            .or_else(|| std::env::var(name).ok())
+            .filter(|v| !v.is_empty() || default.is_none());
+        match (current, default) {
+            (Some(v), _) => out.push_str(&v),
+            (None, Some(default)) => {
+                let (expanded, nested_unset) = expand_env_vars(default, env);
+                out.push_str(&expanded);
+                unset.extend(nested_unset);
+            }
+            (None, None) => {
+                out.push_str(&after[..after.len() - remainder.len()]);
+                unset.push(name.to_string());
+            }
+        }
+        rest = remainder;
+    }
+    out.push_str(rest);
+    (out, unset)
+}
+
+/// Byte offset of the `}` that closes a `${`, allowing nested `${...}` in defaults.
+fn closing_brace(inner: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(i),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
 pub fn generate_worktree_specs(
     base_name: &str,
     agents: &[String],
@@ -327,6 +508,12 @@ pub fn parse_foreach_matrix(input: &str) -> Result<Vec<BTreeMap<String, String>>
 }
 
 fn slugify_filter(input: String) -> String {
+    slugify(&input)
+}
+
+/// Lowercase ASCII letters and digits, with every other run of characters
+/// collapsed to a single dash
+pub fn slugify(input: &str) -> String {
     input
         .to_lowercase()
         .chars()
@@ -367,6 +554,52 @@ mod tests {
         assert!(parse_foreach_matrix("env:dev,prod;region:us").is_err());
     }
 
+    #[test]
+    fn parse_template_var_splits_on_first_equals() {
+        assert_eq!(
+            parse_template_var("query=a=b").unwrap(),
+            ("query".to_string(), "a=b".to_string())
+        );
+        assert!(parse_template_var("novalue").is_err());
+        assert!(parse_template_var("=value").is_err());
+    }
+
+    #[test]
+    fn add_template_vars_extends_every_spec() {
+        let env = create_test_env();
+        let rows = parse_foreach_matrix("env:dev,prod").unwrap();
+        let mut specs = generate_worktree_specs(
+            "deploy",
+            &[],
+            None,
+            Some(&rows),
+            &env,
+            "{{ base_name }}-{{ env }}",
+        )
+        .unwrap();
+        let vars = vec![
+            ("audience".to_string(), "ops".to_string()),
+            ("audience".to_string(), "devs".to_string()),
+        ];
+        add_template_vars(&mut specs, &vars).unwrap();
+        for spec in &specs {
+            assert_eq!(spec.template_context["audience"], "devs");
+        }
+
+        let rendered = render_prompt_body(
+            "{{ env }} for {{ audience }}",
+            &env,
+            &specs[1].template_context,
+        )
+        .unwrap();
+        assert_eq!(rendered, "prod for devs");
+
+        for key in ["env", "base_name"] {
+            let err = add_template_vars(&mut specs, &[(key.to_string(), "x".to_string())]);
+            assert!(err.is_err(), "{} should conflict", key);
+        }
+    }
+
     #[test]
     fn generate_specs_with_agents() {
         let env = create_test_env();
@@ -710,6 +943,24 @@ mod tests {
         assert!(valid.is_ok());
     }
 
+    #[test]
+    fn validate_template_variables_checks_the_root_of_nested_lookups() {
+        let env = create_test_env();
+        let context = serde_json::json!({ "issue": { "title": "Crash" } });
+
+        let valid =
+            validate_template_variables(&env, "{{ issue.title }} {{ issue.body }}", &context);
+        assert!(valid.is_ok());
+
+        let invalid = validate_template_variables(&env, "{{ isue.title }}", &context);
+        assert!(
+            invalid
+                .unwrap_err()
+                .to_string()
+                .contains("undefined variables: isue\n")
+        );
+    }
+
     #[test]
     fn validate_template_variables_handles_conditionals() {
         let env = create_test_env();
@@ -818,4 +1069,60 @@ mod tests {
             .context("Failed to render prompt template")?;
         Ok(Prompt::Inline(rendered))
     }
+
+    #[test]
+    fn expand_env_vars_handles_defaults_and_leaves_unset_references() {
+        let env = [("WM_PORT", "4000"), ("EMPTY", "")];
+        let (out, unset) = expand_env_vars(
+            "serve --port ${WM_PORT:-3000} ${EMPTY:-fallback} ${WORKMUX_TEST_UNSET_VAR} ${#list[@]} $${LITERAL} $HOME",
+            &env,
+        );
+        assert_eq!(
+            out,
+            "serve --port 4000 fallback ${WORKMUX_TEST_UNSET_VAR} ${#list[@]} ${LITERAL} $HOME"
+        );
+        assert_eq!(unset, vec!["WORKMUX_TEST_UNSET_VAR"]);
+
+        let (out, _) = expand_env_vars("${WORKMUX_TEST_UNSET_VAR:-${WM_PORT}}", &env);
+        assert_eq!(out, "4000");
+    }
+
+    #[test]
+    fn interpolate_renders_and_quotes_template_expressions() {
+        let context = serde_json::json!({
+            "branch": "feature/auth",
+            "worktree_path": "/tmp/my worktree",
+            "evil": "x$(touch pwned)'",
+        });
+        let out = interpolate(
+            "echo {{ branch | slugify }} {{ worktree_path }} {{ evil }}",
+            &context,
+        )
+        .unwrap();
+        assert_eq!(
+            out,
+            "echo feature-auth '/tmp/my worktree' 'x$(touch pwned)'\\'''"
+        );
+    }
+
+    #[test]
+    fn interpolate_passes_unknown_expressions_through() {
+        let context = serde_json::json!({ "handle": "wt" });
+
+        let out = interpolate("docker ps --format '{{.Names}}' # {{ handle }}", &context);
+        assert_eq!(out.unwrap(), "docker ps --format '{{.Names}}' # wt");
+
+        let out = interpolate("gh pr view --template '{{ worktree }}'", &context);
+        assert_eq!(out.unwrap(), "gh pr view --template '{{ worktree }}'");
+    }
+
+    #[test]
+    fn interpolate_leaves_shell_variables_to_the_shell() {
+        let out = interpolate(
+            "FOO=1; echo ${FOO:-0} ${PWD} ${WM_PORT:-3000} {{ port }}",
+            &serde_json::json!({ "port": 4000 }),
+        )
+        .unwrap();
+        assert_eq!(out, "FOO=1; echo ${FOO:-0} ${PWD} ${WM_PORT:-3000} 4000");
+    }
 }
@@ -0,0 +1,57 @@
+//! Fixtures shared by unit tests across modules.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+/// Serve one canned JSON response per request, in order, returning the base URL
+/// and a handle that yields the raw requests once all were answered.
+///
+/// Each response is an HTTP status line (e.g. `"200 OK"`) and a body.
+pub fn stub_server_with_status(
+    responses: Vec<(&'static str, String)>,
+) -> (String, thread::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body_in = vec![0; content_length];
+            reader.read_exact(&mut body_in).unwrap();
+            request.push_str(&String::from_utf8(body_in).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            requests.push(request);
+        }
+        requests
+    });
+    (url, handle)
+}
+
+/// Like [`stub_server_with_status`], answering every request with `200 OK`.
+pub fn stub_server(responses: Vec<String>) -> (String, thread::JoinHandle<Vec<String>>) {
+    stub_server_with_status(responses.into_iter().map(|body| ("200 OK", body)).collect())
+}
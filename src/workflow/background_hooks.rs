@@ -0,0 +1,431 @@
+//! Hooks that run outside the blocking `workmux` process: background `post_create`
+//! hooks (pane commands wait for them to succeed) and `post_remove` hooks deferred
+//! until a window has closed.
+
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::config::{Config, HookSpec};
+use crate::multiplexer::util::shell_quote;
+use crate::multiplexer::{AgentStatus, Multiplexer, create_backend, detect_backend};
+use crate::state::{PaneKey, StateStore};
+
+use super::hooks::{self, HookContext};
+
+/// How often waiting panes poll the job status.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long a job may stay without a runner before waiting panes give up.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Everything the detached runner needs, written next to its status file.
+#[derive(Debug, Serialize, Deserialize)]
+struct BackgroundJob {
+    phase: String,
+    handle: String,
+    workdir: PathBuf,
+    env: Vec<(String, String)>,
+    hooks: Vec<HookSpec>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum JobState {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JobStatus {
+    state: JobState,
+    /// Hooks that have finished
+    completed: usize,
+    total: usize,
+    /// Hooks currently running
+    current: Vec<String>,
+    error: Option<String>,
+    /// Pid of the detached runner, unset until it has started
+    #[serde(default)]
+    pid: Option<u32>,
+}
+
+/// Start background hooks in a detached `workmux` process.
+///
+/// Returns the shell command that pane commands should be prefixed with
+/// (`<wait> && <command>`) so they only start once the hooks succeed.
+pub fn spawn(hooks: Vec<HookSpec>, ctx: &HookContext) -> Result<String> {
+    let job_path = write_job(hooks, ctx)?;
+    let exe = std::env::current_exe().context("Failed to locate workmux executable")?;
+    let mut cmd = Command::new(&exe);
+    cmd.arg("_run-background-hooks")
+        .arg(&job_path)
+        .current_dir(ctx.workdir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    cmd.spawn().context("Failed to start background hooks")?;
+    debug!(job = %job_path.display(), "background_hooks:spawned");
+
+    Ok(format!(
+        "{} _wait-background-hooks {}",
+        shell_quote(&exe.to_string_lossy()),
+        shell_quote(&job_path.to_string_lossy())
+    ))
+}
+
+/// Prepare hooks to be run later by a shell script, without starting them.
+///
+/// Returns the shell command that runs the hooks.
+pub fn run_command(hooks: Vec<HookSpec>, ctx: &HookContext) -> Result<String> {
+    let job_path = write_job(hooks, ctx)?;
+    let exe = std::env::current_exe().context("Failed to locate workmux executable")?;
+    Ok(format!(
+        "{} _run-background-hooks {}",
+        shell_quote(&exe.to_string_lossy()),
+        shell_quote(&job_path.to_string_lossy())
+    ))
+}
+
+fn write_job(hooks: Vec<HookSpec>, ctx: &HookContext) -> Result<PathBuf> {
+    let dir = jobs_dir()?;
+    let slug: String = format!("{}-{}", ctx.phase, ctx.handle)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let job_path = dir.join(format!("{}.json", slug));
+
+    let job = BackgroundJob {
+        phase: ctx.phase.to_string(),
+        handle: ctx.handle.to_string(),
+        workdir: ctx.workdir.to_path_buf(),
+        env: ctx
+            .env
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        hooks,
+    };
+    write_status(
+        &job_path,
+        &JobStatus {
+            state: JobState::Running,
+            completed: 0,
+            total: job.hooks.len(),
+            current: Vec::new(),
+            error: None,
+            pid: None,
+        },
+    )?;
+    fs::write(&job_path, serde_json::to_string_pretty(&job)?)
+        .context("Failed to write background hook job")?;
+    Ok(job_path)
+}
+
+/// Entry point of the detached runner (`workmux _run-background-hooks <job>`).
+pub fn run_job(job_path: &Path) -> Result<()> {
+    let job: BackgroundJob = serde_json::from_str(
+        &fs::read_to_string(job_path).context("Failed to read background hook job")?,
+    )?;
+    let env = hooks::env_refs(&job.env);
+    let ctx = HookContext {
+        phase: &job.phase,
+        handle: &job.handle,
+        workdir: &job.workdir,
+        env: &env,
+    };
+
+    let total = job.hooks.len();
+    let pid = Some(std::process::id());
+    let mut done = 0;
+    write_status(
+        job_path,
+        &JobStatus {
+            state: JobState::Running,
+            completed: 0,
+            total,
+            current: Vec::new(),
+            error: None,
+            pid,
+        },
+    )?;
+    let result = hooks::run_specs(&job.hooks, &ctx, |completed, current| {
+        done = completed;
+        let status = JobStatus {
+            state: JobState::Running,
+            completed,
+            total,
+            current,
+            error: None,
+            pid,
+        };
+        if let Err(e) = write_status(job_path, &status) {
+            warn!(error = %e, "background_hooks:failed to write status");
+        }
+    });
+
+    let status = match &result {
+        Ok(_) => JobStatus {
+            state: JobState::Succeeded,
+            completed: total,
+            total,
+            current: Vec::new(),
+            error: None,
+            pid,
+        },
+        Err(e) => JobStatus {
+            state: JobState::Failed,
+            completed: done,
+            total,
+            current: Vec::new(),
+            error: Some(format!("{:#}", e)),
+            pid,
+        },
+    };
+    write_status(job_path, &status)?;
+    result.map(|_| ())
+}
+
+/// Block until the background job finishes (`workmux _wait-background-hooks <job>`).
+///
+/// Shows the setup icon on the current window while waiting. Fails if any hook
+/// failed, so `<wait> && <command>` never starts the command.
+pub fn wait(job_path: &Path) -> Result<()> {
+    let config = Config::load(None).unwrap_or_default();
+    let mux = create_backend(detect_backend());
+    let pane_id = mux.current_pane_id();
+    if let Some(pane) = &pane_id {
+        if config.status_format.unwrap_or(true) {
+            let _ = mux.ensure_status_format(pane);
+        }
+        let _ = mux.set_status(pane, config.status_icons.setup(), false);
+    }
+
+    let spinner = indicatif::ProgressBar::new_spinner();
+    spinner.enable_steady_tick(Duration::from_millis(120));
+    let outcome = poll_until_done(job_path, |status| {
+        let current = if status.current.is_empty() {
+            "starting".to_string()
+        } else {
+            status.current.join(", ")
+        };
+        spinner.set_message(format!(
+            "Waiting for setup hooks ({}/{}): {}",
+            status.completed, status.total, current
+        ));
+    });
+    spinner.finish_and_clear();
+
+    if let Some(pane) = &pane_id {
+        match &outcome {
+            Ok(()) => match agent_status_icon(mux.as_ref(), pane, &config) {
+                Some((icon, auto_clear)) => {
+                    let _ = mux.set_status(pane, icon, auto_clear);
+                }
+                None => {
+                    let _ = mux.clear_status(pane);
+                }
+            },
+            Err(_) => {
+                let _ = mux.set_status(pane, config.status_icons.waiting(), true);
+            }
+        }
+    }
+    if outcome.is_ok() {
+        println!("✓ Setup hooks finished");
+    }
+    outcome
+}
+
+/// The icon for the agent status last recorded for this pane, so finishing setup
+/// puts back a status reported meanwhile instead of wiping it.
+fn agent_status_icon<'a>(
+    mux: &dyn Multiplexer,
+    pane_id: &str,
+    config: &'a Config,
+) -> Option<(&'a str, bool)> {
+    let pane_key = PaneKey {
+        backend: mux.name().to_string(),
+        instance: mux.instance_id(),
+        pane_id: pane_id.to_string(),
+    };
+    let agent = StateStore::new().ok()?.get_agent(&pane_key).ok()??;
+    let icons = &config.status_icons;
+    Some(match agent.status? {
+        AgentStatus::Working => (icons.working(), false),
+        AgentStatus::Waiting => (icons.waiting(), true),
+        AgentStatus::Done => (icons.done(), true),
+    })
+}
+
+/// Poll the job status until it reaches a terminal state.
+///
+/// A job whose runner has died (or never started within [`STARTUP_TIMEOUT`])
+/// without recording a result is treated as failed.
+fn poll_until_done(job_path: &Path, mut on_progress: impl FnMut(&JobStatus)) -> Result<()> {
+    loop {
+        let status = read_status(job_path)?;
+        match status.state {
+            JobState::Running => {
+                if let Some(reason) = runner_gone(job_path, &status) {
+                    // The runner writes its result before exiting, so look once more
+                    let status = read_status(job_path)?;
+                    if status.state == JobState::Running {
+                        return Err(anyhow!("Background setup failed: {}", reason));
+                    }
+                    continue;
+                }
+                on_progress(&status);
+                std::thread::sleep(POLL_INTERVAL);
+            }
+            JobState::Succeeded => return Ok(()),
+            JobState::Failed => {
+                return Err(anyhow!(
+                    "Background setup failed: {}",
+                    status.error.unwrap_or_default()
+                ));
+            }
+        }
+    }
+}
+
+/// Why a running job can no longer finish, if its runner is gone.
+fn runner_gone(job_path: &Path, status: &JobStatus) -> Option<String> {
+    match status.pid {
+        Some(pid) if !process_alive(pid) => Some(format!(
+            "hook runner (pid {}) exited without reporting a result",
+            pid
+        )),
+        Some(_) => None,
+        None => {
+            let age = fs::metadata(status_path(job_path))
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())?;
+            (age > STARTUP_TIMEOUT).then(|| {
+                format!(
+                    "hook runner did not start within {}s",
+                    STARTUP_TIMEOUT.as_secs()
+                )
+            })
+        }
+    }
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    // SAFETY: signal 0 only checks whether the process exists.
+    let ret = unsafe { libc::kill(pid as i32, 0) };
+    ret == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Without a cheap liveness check the runner is assumed alive, so waiting only
+/// ends once it reports a result.
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    true
+}
+
+fn jobs_dir() -> Result<PathBuf> {
+    StateStore::new()?.background_jobs_dir()
+}
+
+fn status_path(job_path: &Path) -> PathBuf {
+    job_path.with_extension("status.json")
+}
+
+fn write_status(job_path: &Path, status: &JobStatus) -> Result<()> {
+    let path = status_path(job_path);
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, serde_json::to_string(status)?)?;
+    fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+fn read_status(job_path: &Path) -> Result<JobStatus> {
+    let content = fs::read_to_string(status_path(job_path))
+        .with_context(|| format!("No background hook job at {}", job_path.display()))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_roundtrip_next_to_job() {
+        let dir = tempfile::tempdir().unwrap();
+        let job_path = dir.path().join("wt.json");
+        let status = JobStatus {
+            state: JobState::Failed,
+            completed: 1,
+            total: 2,
+            current: vec![],
+            error: Some("post_create hook 'install' exited with code 1".to_string()),
+            pid: None,
+        };
+
+        write_status(&job_path, &status).unwrap();
+
+        assert!(dir.path().join("wt.status.json").exists());
+        let read = read_status(&job_path).unwrap();
+        assert_eq!(read.state, JobState::Failed);
+        assert_eq!(read.error, status.error);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn wait_fails_when_runner_died_without_result() {
+        let dir = tempfile::tempdir().unwrap();
+        let job_path = dir.path().join("wt.json");
+        let mut child = Command::new("true").spawn().unwrap();
+        let dead_pid = child.id();
+        child.wait().unwrap();
+        write_status(
+            &job_path,
+            &JobStatus {
+                state: JobState::Running,
+                completed: 0,
+                total: 1,
+                current: vec!["install".to_string()],
+                error: None,
+                pid: Some(dead_pid),
+            },
+        )
+        .unwrap();
+
+        let err = poll_until_done(&job_path, |_| {}).unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("exited without reporting a result")
+        );
+    }
+
+    #[test]
+    fn running_job_with_live_runner_is_not_gone() {
+        let dir = tempfile::tempdir().unwrap();
+        let job_path = dir.path().join("wt.json");
+        let status = JobStatus {
+            state: JobState::Running,
+            completed: 0,
+            total: 1,
+            current: vec![],
+            error: None,
+            pid: Some(std::process::id()),
+        };
+        write_status(&job_path, &status).unwrap();
+
+        assert_eq!(runner_gone(&job_path, &status), None);
+    }
+}
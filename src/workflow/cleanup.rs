@@ -4,13 +4,15 @@ use std::path::Path;
 use std::time::SystemTime;
 use std::{thread, time::Duration};
 
+use crate::git;
 use crate::multiplexer::tmux;
 use crate::multiplexer::{Multiplexer, util::prefixed};
-use crate::{cmd, git};
 use tracing::{debug, info, warn};
 
 use super::context::WorkflowContext;
 use super::types::{CleanupResult, DeferredCleanup};
+use super::{background_hooks, hooks, ports};
+use crate::config::HookEntry;
 
 const WINDOW_CLOSE_DELAY_MS: u64 = 300;
 
@@ -74,6 +76,47 @@ fn is_inside_matching_window(
     }
 }
 
+fn run_pre_remove_hooks(
+    hooks: &[HookEntry],
+    handle: &str,
+    worktree_path: &Path,
+    env: &[(&str, &str)],
+) -> Result<()> {
+    hooks::run_hooks(
+        hooks,
+        &hooks::HookContext {
+            phase: "pre_remove",
+            handle,
+            workdir: worktree_path,
+            env,
+        },
+    )?;
+    Ok(())
+}
+
+/// Run post_remove hooks from the project root. The worktree is already gone,
+/// so a failure is reported but not returned.
+fn run_post_remove_hooks(
+    context: &WorkflowContext,
+    hooks: &[HookEntry],
+    handle: &str,
+    env: &[(String, String)],
+) {
+    let env = hooks::env_refs(env);
+    if let Err(e) = hooks::run_hooks(
+        hooks,
+        &hooks::HookContext {
+            phase: "post_remove",
+            handle,
+            workdir: &context.main_worktree_root,
+            env: &env,
+        },
+    ) {
+        warn!(error = %e, "cleanup:post-remove hooks failed");
+        eprintln!("Warning: {:#}", e);
+    }
+}
+
 /// Centralized function to clean up tmux and git resources.
 /// `branch_name` is used for git operations (branch deletion).
 /// `handle` is used for tmux operations (window lookup/kill).
@@ -117,9 +160,33 @@ pub fn cleanup(
         deferred_cleanup: None,
     };
 
+    let post_remove_hooks = context
+        .config
+        .post_remove
+        .as_ref()
+        .filter(|hooks| !hooks.is_empty());
+    // Resolved before removal, since neither the canonical path nor the reserved
+    // ports can be looked up afterwards.
+    let post_remove_env = if post_remove_hooks.is_some() {
+        hooks::worktree_env(
+            handle,
+            branch_name,
+            worktree_path,
+            &context.main_worktree_root,
+        )
+    } else {
+        Vec::new()
+    };
+
     // Helper closure to perform the actual filesystem and git cleanup.
     // This avoids code duplication while enforcing the correct operational order.
     let perform_fs_git_cleanup = |result: &mut CleanupResult| -> Result<()> {
+        // Resolve now: reserved ports are keyed by the canonical path, which can no
+        // longer be resolved once the directory is moved to the trash.
+        let ports_key = worktree_path
+            .canonicalize()
+            .unwrap_or_else(|_| worktree_path.to_path_buf());
+
         // Run pre-remove hooks before removing the worktree directory.
         // Skip if the worktree directory doesn't exist (e.g., user manually deleted it).
         if worktree_path.exists() {
@@ -129,30 +196,20 @@ pub fn cleanup(
                     count = pre_remove_hooks.len(),
                     "cleanup:running pre-remove hooks"
                 );
-                // Resolve absolute paths for environment variables.
-                // canonicalize() ensures symlinks are resolved and paths are absolute.
-                let abs_worktree_path = worktree_path
-                    .canonicalize()
-                    .unwrap_or_else(|_| worktree_path.to_path_buf());
-                let abs_project_root = context
-                    .main_worktree_root
-                    .canonicalize()
-                    .unwrap_or_else(|_| context.main_worktree_root.clone());
-                let worktree_path_str = abs_worktree_path.to_string_lossy();
-                let project_root_str = abs_project_root.to_string_lossy();
-                let hook_env = [
-                    ("WORKMUX_HANDLE", handle),
-                    ("WM_HANDLE", handle),
-                    ("WM_WORKTREE_PATH", worktree_path_str.as_ref()),
-                    ("WM_PROJECT_ROOT", project_root_str.as_ref()),
-                ];
-                for command in pre_remove_hooks {
-                    // Run the hook with the worktree path as the working directory.
-                    // This allows for relative paths like `node_modules` in the command.
-                    cmd::shell_command_with_env(command, worktree_path, &hook_env).with_context(
-                        || format!("Failed to run pre-remove command: '{}'", command),
-                    )?;
-                }
+                let hook_env = hooks::worktree_env(
+                    handle,
+                    branch_name,
+                    worktree_path,
+                    &context.main_worktree_root,
+                );
+                // Run the hooks with the worktree path as the working directory.
+                // This allows for relative paths like `node_modules` in the command.
+                run_pre_remove_hooks(
+                    pre_remove_hooks,
+                    handle,
+                    worktree_path,
+                    &hooks::env_refs(&hook_env),
+                )?;
             }
         } else {
             debug!(
@@ -239,6 +296,11 @@ pub fn cleanup(
             info!(branch = branch_name, "cleanup:local branch deleted");
         }
 
+        // Release reserved ports so other worktrees can use them
+        if let Err(e) = ports::release(&ports_key) {
+            warn!(error = %e, "cleanup:failed to release ports");
+        }
+
         // 4. Best-effort deletion of the trash directory.
         // If the shell is inside this directory, remove_dir_all on the root might fail
         // immediately. Clearing children first ensures we reclaim the space.
@@ -310,25 +372,18 @@ pub fn cleanup(
                 count = pre_remove_hooks.len(),
                 "cleanup:running pre-remove hooks"
             );
-            let abs_worktree_path = worktree_path
-                .canonicalize()
-                .unwrap_or_else(|_| worktree_path.to_path_buf());
-            let abs_project_root = context
-                .main_worktree_root
-                .canonicalize()
-                .unwrap_or_else(|_| context.main_worktree_root.clone());
-            let worktree_path_str = abs_worktree_path.to_string_lossy();
-            let project_root_str = abs_project_root.to_string_lossy();
-            let hook_env = [
-                ("WORKMUX_HANDLE", handle),
-                ("WM_HANDLE", handle),
-                ("WM_WORKTREE_PATH", worktree_path_str.as_ref()),
-                ("WM_PROJECT_ROOT", project_root_str.as_ref()),
-            ];
-            for command in pre_remove_hooks {
-                cmd::shell_command_with_env(command, worktree_path, &hook_env)
-                    .with_context(|| format!("Failed to run pre-remove command: '{}'", command))?;
-            }
+            let hook_env = hooks::worktree_env(
+                handle,
+                branch_name,
+                worktree_path,
+                &context.main_worktree_root,
+            );
+            run_pre_remove_hooks(
+                pre_remove_hooks,
+                handle,
+                worktree_path,
+                &hooks::env_refs(&hook_env),
+            )?;
         }
 
         // Clean up prompt files immediately (harmless, doesn't affect CWD)
@@ -368,6 +423,25 @@ pub fn cleanup(
             );
             let trash_path = parent.join(&trash_name);
 
+            // post_remove hooks must wait for the deferred removal, so they run
+            // from the same script via a prepared background job.
+            let post_remove_command = match post_remove_hooks {
+                Some(hooks) => {
+                    let env = hooks::env_refs(&post_remove_env);
+                    let specs = hooks.iter().map(HookEntry::to_spec).collect();
+                    Some(background_hooks::run_command(
+                        specs,
+                        &hooks::HookContext {
+                            phase: "post_remove",
+                            handle,
+                            workdir: &context.main_worktree_root,
+                            env: &env,
+                        },
+                    )?)
+                }
+                None => None,
+            };
+
             result.deferred_cleanup = Some(DeferredCleanup {
                 worktree_path: worktree_path.to_path_buf(),
                 trash_path,
@@ -375,11 +449,14 @@ pub fn cleanup(
                 keep_branch,
                 force,
                 git_common_dir: context.git_common_dir.clone(),
+                post_remove_command,
             });
             debug!(
                 worktree = %worktree_path.display(),
                 "cleanup:deferred destructive cleanup until window close"
             );
+        } else if let Some(hooks) = post_remove_hooks {
+            run_post_remove_hooks(context, hooks, handle, &post_remove_env);
         }
     } else {
         // Not running inside any matching window, so kill ALL matching windows first
@@ -418,6 +495,10 @@ pub fn cleanup(
         }
         // Now that windows are gone, clean up filesystem and git state.
         perform_fs_git_cleanup(&mut result)?;
+
+        if let Some(hooks) = post_remove_hooks {
+            run_post_remove_hooks(context, hooks, handle, &post_remove_env);
+        }
     }
 
     Ok(result)
@@ -461,6 +542,10 @@ pub fn navigate_to_target_and_close(
         }
         // 4. Delete trash
         cmds.push(format!("rm -rf {} >/dev/null 2>&1", trash));
+        // 5. Run post_remove hooks
+        if let Some(cmd) = &dc.post_remove_command {
+            cmds.push(format!("{} >/dev/null 2>&1", cmd));
+        }
 
         format!("; {}", cmds.join("; "))
     }
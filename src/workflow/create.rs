@@ -1,7 +1,7 @@
 use anyhow::{Context, Result, anyhow};
 use std::path::Path;
 
-use crate::{git, spinner};
+use crate::{git, spinner, template};
 use tracing::{debug, info, warn};
 
 /// Check if a path is registered as a git worktree.
@@ -28,6 +28,32 @@ fn is_registered_worktree(path: &Path) -> Result<bool> {
     Ok(false)
 }
 
+/// Resolve `${VAR}` and `{{ ... }}` references in `worktree_dir`.
+/// Unlike commands, the path is never seen by a shell, so unset variables are an error.
+fn interpolate_worktree_dir(
+    worktree_dir: &str,
+    handle: &str,
+    branch_name: &str,
+    project_root: &Path,
+) -> Result<String> {
+    let context = serde_json::json!({
+        "handle": handle,
+        "branch": branch_name,
+        "project_root": project_root,
+    });
+    let rendered = template::render_expressions(worktree_dir, &context)
+        .context("Failed to interpolate worktree_dir")?;
+    let (resolved, unset) = template::expand_env_vars(&rendered, &[]);
+    if let Some(name) = unset.first() {
+        return Err(anyhow!(
+            "worktree_dir references unset environment variable '{}'. Use ${{{}:-default}} to provide a fallback.",
+            name,
+            name
+        ));
+    }
+    Ok(resolved)
+}
+
 use super::cleanup;
 use super::context::WorkflowContext;
 use super::setup;
@@ -176,7 +202,13 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
     // Always use main_worktree_root (not repo_root) to ensure consistent paths even when
     // running from inside an existing worktree.
     let base_dir = if let Some(ref worktree_dir) = context.config.worktree_dir {
-        let path = Path::new(worktree_dir);
+        let worktree_dir = interpolate_worktree_dir(
+            worktree_dir,
+            handle,
+            branch_name,
+            &context.main_worktree_root,
+        )?;
+        let path = Path::new(&worktree_dir);
         if path.is_absolute() {
             // Use absolute path as-is
             path.to_path_buf()
@@ -241,6 +273,36 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         })?;
     }
 
+    // Run pre-create hooks from the project root; a failing hook aborts creation
+    if options.run_hooks
+        && let Some(pre_create) = &context.config.pre_create
+        && !pre_create.is_empty()
+    {
+        info!(
+            branch = branch_name,
+            count = pre_create.len(),
+            "create:running pre-create hooks"
+        );
+        let mut hook_env = super::hooks::worktree_env(
+            handle,
+            branch_name,
+            &worktree_path,
+            &context.main_worktree_root,
+        );
+        if let Some(base) = &base_branch_for_creation {
+            hook_env.push(("WM_BASE_BRANCH".to_string(), base.clone()));
+        }
+        super::hooks::run_hooks(
+            pre_create,
+            &super::hooks::HookContext {
+                phase: "pre_create",
+                handle,
+                workdir: &context.main_worktree_root,
+                env: &super::hooks::env_refs(&hook_env),
+            },
+        )?;
+    }
+
     // Create worktree
     info!(
         branch = branch_name,
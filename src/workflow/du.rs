@@ -0,0 +1,370 @@
+use anyhow::{Result, anyhow};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::config::Config;
+use crate::git;
+use crate::multiplexer::{Multiplexer, util};
+
+/// Disk usage of a worktree, split by where the bytes come from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiskUsage {
+    /// Files tracked by git
+    pub tracked: u64,
+    /// Files ignored by git, excluding anything materialised by `files.copy`
+    pub ignored: u64,
+    /// Files materialised by `files.copy`
+    pub copied: u64,
+}
+
+impl DiskUsage {
+    pub fn total(&self) -> u64 {
+        self.tracked + self.ignored + self.copied
+    }
+}
+
+/// An ignored directory that can be deleted to reclaim space
+#[derive(Debug, Clone)]
+pub struct IgnoredDir {
+    /// Path relative to the worktree root
+    pub rel_path: PathBuf,
+    pub size: u64,
+}
+
+/// Disk usage report for a single worktree
+#[derive(Debug, Clone)]
+pub struct WorktreeUsage {
+    pub handle: String,
+    pub branch: String,
+    pub path: PathBuf,
+    pub is_main: bool,
+    pub has_mux_window: bool,
+    pub usage: DiskUsage,
+    /// Ignored directories listed in `du.clean` that are not covered by `files.copy`
+    pub ignored_dirs: Vec<IgnoredDir>,
+}
+
+impl WorktreeUsage {
+    /// Bytes that `workmux du --clean` would reclaim.
+    pub fn reclaimable(&self) -> u64 {
+        self.ignored_dirs.iter().map(|d| d.size).sum()
+    }
+}
+
+/// Measure disk usage for every worktree. Worktrees are measured in parallel.
+pub fn disk_usage(config: &Config, mux: &dyn Multiplexer) -> Result<Vec<WorktreeUsage>> {
+    if !git::is_git_repo()? {
+        return Err(anyhow!("Not in a git repository"));
+    }
+
+    let worktrees = git::list_worktrees()?;
+    let main_worktree_root = git::get_main_worktree_root()?;
+    let mux_windows: HashSet<String> = if mux.is_running().unwrap_or(false) {
+        mux.get_all_window_names().unwrap_or_default()
+    } else {
+        HashSet::new()
+    };
+    let copy_patterns: Vec<String> = config
+        .files
+        .copy
+        .iter()
+        .flatten()
+        .map(|entry| entry.pattern().to_string())
+        .collect();
+    let clean_dirs = config.du.clone().unwrap_or_default().clean();
+    let prefix = config.window_prefix();
+    let counter = SizeCounter::default();
+
+    let results: Vec<Result<WorktreeUsage>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = worktrees
+            .into_iter()
+            .map(|(path, branch)| {
+                let copy_patterns = &copy_patterns;
+                let clean_dirs = &clean_dirs;
+                let counter = &counter;
+                let is_main = path == main_worktree_root;
+                let handle = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or(&branch)
+                    .to_string();
+                let has_mux_window = mux_windows.contains(&util::prefixed(prefix, &handle));
+
+                scope.spawn(move || -> Result<WorktreeUsage> {
+                    let (usage, ignored_dirs) = if path.exists() {
+                        measure(&path, copy_patterns, clean_dirs, counter)?
+                    } else {
+                        (DiskUsage::default(), Vec::new())
+                    };
+                    debug!(handle = %handle, ?usage, "du:measured");
+                    Ok(WorktreeUsage {
+                        handle,
+                        branch,
+                        path,
+                        is_main,
+                        has_mux_window,
+                        usage,
+                        ignored_dirs,
+                    })
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|h| {
+                h.join()
+                    .unwrap_or_else(|_| Err(anyhow!("Disk usage worker panicked")))
+            })
+            .collect()
+    });
+
+    results.into_iter().collect()
+}
+
+/// Measure a single worktree. The counter is shared by the whole run so files
+/// hard-linked between worktrees are only counted once.
+fn measure(
+    worktree_path: &Path,
+    copy_patterns: &[String],
+    clean_dirs: &[String],
+    counter: &SizeCounter,
+) -> Result<(DiskUsage, Vec<IgnoredDir>)> {
+    let copied = copied_paths(worktree_path, copy_patterns)?;
+
+    let mut usage = DiskUsage::default();
+    for file in git::list_tracked_files(worktree_path)? {
+        usage.tracked += counter.size_of(&worktree_path.join(file), &[]);
+    }
+
+    for path in &copied {
+        usage.copied += counter.size_of(path, &[]);
+    }
+
+    let mut ignored_dirs = Vec::new();
+    for entry in git::list_ignored_entries(worktree_path)? {
+        let is_dir = entry.ends_with('/');
+        let rel_path = PathBuf::from(entry.trim_end_matches('/'));
+        let full_path = worktree_path.join(&rel_path);
+
+        if copied.iter().any(|c| full_path.starts_with(c)) {
+            continue; // Already counted as copied
+        }
+
+        let size = counter.size_of(&full_path, &copied);
+        usage.ignored += size;
+
+        // Only listed directories with nothing copied inside are safe to delete
+        if is_dir
+            && is_cleanable(&rel_path, clean_dirs)
+            && !copied.iter().any(|c| c.starts_with(&full_path))
+        {
+            ignored_dirs.push(IgnoredDir { rel_path, size });
+        }
+    }
+
+    ignored_dirs.sort_by_key(|d| std::cmp::Reverse(d.size));
+    Ok((usage, ignored_dirs))
+}
+
+/// Whether an ignored directory is listed in `du.clean`. Plain names match the
+/// directory name at any depth; entries containing `/` are globs on the full path.
+fn is_cleanable(rel_path: &Path, clean_dirs: &[String]) -> bool {
+    clean_dirs.iter().any(|entry| {
+        if entry.contains('/') {
+            glob::Pattern::new(entry.trim_end_matches('/'))
+                .is_ok_and(|pattern| pattern.matches_path(rel_path))
+        } else {
+            rel_path
+                .file_name()
+                .is_some_and(|name| name == entry.as_str())
+        }
+    })
+}
+
+/// Resolve `files.copy` patterns against the worktree. Symlinks are skipped since
+/// they take no space of their own.
+fn copied_paths(worktree_path: &Path, patterns: &[String]) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        let full_pattern = worktree_path.join(pattern).to_string_lossy().to_string();
+        for entry in glob::glob(&full_pattern)? {
+            let path = entry?;
+            let is_symlink = fs::symlink_metadata(&path)
+                .map(|m| m.file_type().is_symlink())
+                .unwrap_or(true);
+            if !is_symlink && path.starts_with(worktree_path) {
+                paths.push(path);
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Sums allocated disk blocks without following symlinks. Hard-linked files are
+/// only counted once per counter. On non-unix platforms, file lengths are summed
+/// instead.
+#[derive(Default)]
+struct SizeCounter {
+    #[cfg(unix)]
+    seen_inodes: std::sync::Mutex<HashSet<(u64, u64)>>,
+}
+
+impl SizeCounter {
+    fn size_of(&self, path: &Path, exclude: &[PathBuf]) -> u64 {
+        if exclude.iter().any(|p| p == path) {
+            return 0;
+        }
+        let Ok(meta) = fs::symlink_metadata(path) else {
+            return 0;
+        };
+        if meta.file_type().is_symlink() {
+            return 0;
+        }
+        if meta.is_dir() {
+            let Ok(entries) = fs::read_dir(path) else {
+                return 0;
+            };
+            return entries
+                .flatten()
+                .map(|e| self.size_of(&e.path(), exclude))
+                .sum();
+        }
+        self.file_size(&meta)
+    }
+
+    #[cfg(unix)]
+    fn file_size(&self, meta: &fs::Metadata) -> u64 {
+        use std::os::unix::fs::MetadataExt;
+
+        if meta.nlink() > 1
+            && !self
+                .seen_inodes
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert((meta.dev(), meta.ino()))
+        {
+            return 0;
+        }
+        meta.blocks() * 512
+    }
+
+    #[cfg(not(unix))]
+    fn file_size(&self, meta: &fs::Metadata) -> u64 {
+        meta.len()
+    }
+}
+
+/// Delete an ignored directory inside a worktree.
+pub fn remove_ignored_dir(worktree_path: &Path, dir: &IgnoredDir) -> Result<()> {
+    let full_path = worktree_path.join(&dir.rel_path);
+    // Never delete anything outside the worktree (e.g. via `..` components)
+    let canon_worktree = worktree_path.canonicalize()?;
+    let canon_dir = full_path.canonicalize()?;
+    if !canon_dir.starts_with(&canon_worktree) || canon_dir == canon_worktree {
+        return Err(anyhow!(
+            "Refusing to delete '{}' outside the worktree",
+            full_path.display()
+        ));
+    }
+    fs::remove_dir_all(&canon_dir)?;
+    Ok(())
+}
+
+/// Format a byte count as a short human-readable size (e.g., "1.5G").
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}{}", bytes, UNITS[0])
+    } else if size < 10.0 {
+        format!("{:.1}{}", size, UNITS[unit])
+    } else {
+        format!("{:.0}{}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_size_units() {
+        assert_eq!(format_size(0), "0B");
+        assert_eq!(format_size(512), "512B");
+        assert_eq!(format_size(1536), "1.5K");
+        assert_eq!(format_size(20 * 1024 * 1024), "20M");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0G");
+    }
+
+    #[test]
+    fn only_listed_directories_are_cleanable() {
+        let clean = vec!["node_modules".to_string(), "packages/*/dist".to_string()];
+
+        assert!(is_cleanable(Path::new("node_modules"), &clean));
+        assert!(is_cleanable(Path::new("web/node_modules"), &clean));
+        assert!(is_cleanable(Path::new("packages/ui/dist"), &clean));
+        assert!(!is_cleanable(Path::new("dist"), &clean));
+        assert!(!is_cleanable(Path::new(".env"), &clean));
+        assert!(!is_cleanable(Path::new("data"), &clean));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn size_counter_skips_excluded_and_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("build/keep")).unwrap();
+        fs::write(root.join("build/a.bin"), vec![1u8; 8192]).unwrap();
+        fs::write(root.join("build/keep/b.bin"), vec![1u8; 8192]).unwrap();
+        std::os::unix::fs::symlink(root.join("build/a.bin"), root.join("build/link")).unwrap();
+
+        let counter = SizeCounter::default();
+        let all = counter.size_of(&root.join("build"), &[]);
+
+        let counter = SizeCounter::default();
+        let partial = counter.size_of(&root.join("build"), &[root.join("build/keep")]);
+
+        assert!(all >= 16384);
+        assert!(partial < all);
+        assert!(partial >= 8192);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn size_counter_counts_hardlinks_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("a"), vec![1u8; 8192]).unwrap();
+        fs::hard_link(root.join("a"), root.join("b")).unwrap();
+
+        let counter = SizeCounter::default();
+        let first = counter.size_of(&root.join("a"), &[]);
+        let second = counter.size_of(&root.join("b"), &[]);
+
+        assert!(first > 0);
+        assert_eq!(second, 0);
+    }
+
+    #[test]
+    fn remove_ignored_dir_rejects_escape() {
+        let dir = tempfile::tempdir().unwrap();
+        let worktree = dir.path().join("wt");
+        fs::create_dir_all(&worktree).unwrap();
+        fs::create_dir_all(dir.path().join("outside")).unwrap();
+
+        let escape = IgnoredDir {
+            rel_path: PathBuf::from("../outside"),
+            size: 0,
+        };
+        assert!(remove_ignored_dir(&worktree, &escape).is_err());
+        assert!(dir.path().join("outside").exists());
+    }
+}
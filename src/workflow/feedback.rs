@@ -0,0 +1,214 @@
+//! Feed forge feedback (failing CI checks, review comments) back to a
+//! worktree's agent.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::forge::{FailedCheck, ReviewThread};
+use crate::multiplexer::Multiplexer;
+use crate::state::StateStore;
+
+/// Lines kept from the end of each failed check's log
+const LOG_TAIL_LINES: usize = 80;
+
+/// Build a prompt asking the agent to fix the failed checks on `branch`.
+/// Returns None when nothing failed.
+pub fn ci_failure_prompt(branch: &str, checks: &[FailedCheck]) -> Option<String> {
+    if checks.is_empty() {
+        return None;
+    }
+
+    let mut prompt = format!(
+        "CI failed on branch `{}`. Fix the failures below, then commit and push.\n",
+        branch
+    );
+    for check in checks {
+        prompt.push_str(&format!("\n## {}\n", check.name));
+        if let Some(url) = &check.url {
+            prompt.push_str(&format!("\n{}\n", url));
+        }
+        let log = check.log.as_deref().map(log_tail).unwrap_or_default();
+        if log.is_empty() {
+            prompt.push_str("\nNo log available.\n");
+            continue;
+        }
+        let fence = code_fence(&log);
+        prompt.push_str(&format!("\n{}\n{}\n{}\n", fence, log, fence));
+    }
+    Some(prompt)
+}
+
+/// Build a prompt asking the agent to address unresolved review threads.
+/// `request` names the PR, e.g. "PR #12". Returns None when there are none.
+pub fn review_prompt(request: &str, threads: &[ReviewThread]) -> Option<String> {
+    if threads.is_empty() {
+        return None;
+    }
+
+    let mut prompt = format!(
+        "Reviewers left comments on {}. Address each one, then commit and push.\n",
+        request
+    );
+    if threads
+        .iter()
+        .flat_map(|thread| &thread.comments)
+        .any(|comment| comment.body.contains("```suggestion"))
+    {
+        prompt.push_str(
+            "`suggestion` blocks hold the replacement the reviewer proposes for the commented line.\n",
+        );
+    }
+    for thread in threads {
+        match thread.line {
+            Some(line) => prompt.push_str(&format!("\n## {}:{}\n", thread.path, line)),
+            None => prompt.push_str(&format!("\n## {}\n", thread.path)),
+        }
+        for comment in &thread.comments {
+            let author = if comment.author.is_empty() {
+                "ghost"
+            } else {
+                &comment.author
+            };
+            prompt.push_str(&format!(
+                "\n**{}**:\n\n{}\n",
+                author,
+                comment.body.replace("\r\n", "\n").trim()
+            ));
+        }
+    }
+    Some(prompt)
+}
+
+/// A markdown code fence that `content` doesn't contain, so it can be wrapped
+/// safely
+pub fn code_fence(content: &str) -> String {
+    let mut fence = "```".to_string();
+    while content.contains(&fence) {
+        fence.push('`');
+    }
+    fence
+}
+
+/// The last lines of a CI log, without ANSI colors, carriage-return progress
+/// output and trailing blank lines
+fn log_tail(log: &str) -> String {
+    let lines: Vec<String> = log
+        .lines()
+        .map(|line| {
+            // Progress bars and GitLab section markers redraw the line after \r
+            let line = line.rsplit('\r').next().unwrap_or_default();
+            console::strip_ansi_codes(line).trim_end().to_string()
+        })
+        .collect();
+    let end = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .map_or(0, |i| i + 1);
+    let start = end.saturating_sub(LOG_TAIL_LINES);
+    lines[start..end].join("\n")
+}
+
+/// The pane of the agent running in a worktree, if there is one
+pub fn find_agent_pane(mux: &dyn Multiplexer, worktree_path: &Path) -> Result<Option<String>> {
+    let agents = StateStore::new()?.load_reconciled_agents(mux)?;
+    let canonical = worktree_path.canonicalize().ok();
+    Ok(agents
+        .into_iter()
+        .find(|agent| {
+            agent.path.starts_with(worktree_path)
+                || canonical
+                    .as_ref()
+                    .is_some_and(|root| agent.path.starts_with(root))
+        })
+        .map(|agent| agent.pane_id))
+}
+
+/// Paste a prompt into an agent's pane and submit it
+pub fn send_to_agent(mux: &dyn Multiplexer, pane_id: &str, prompt: &str) -> Result<()> {
+    mux.paste_multiline(pane_id, prompt)?;
+    mux.send_key(pane_id, "Enter")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::ReviewComment;
+
+    #[test]
+    fn ci_failure_prompt_lists_checks_with_log_tails() {
+        assert_eq!(ci_failure_prompt("feat", &[]), None);
+
+        let checks = vec![
+            FailedCheck {
+                name: "CI / test".to_string(),
+                url: Some("https://ci.example/1".to_string()),
+                log: Some("\x1b[31merror\x1b[0m: boom\n```\n\n".to_string()),
+            },
+            FailedCheck {
+                name: "lint".to_string(),
+                url: None,
+                log: None,
+            },
+        ];
+        assert_eq!(
+            ci_failure_prompt("feat", &checks).unwrap(),
+            "CI failed on branch `feat`. Fix the failures below, then commit and push.\n\
+             \n## CI / test\n\nhttps://ci.example/1\n\n````\nerror: boom\n```\n````\n\
+             \n## lint\n\nNo log available.\n"
+        );
+    }
+
+    #[test]
+    fn review_prompt_lists_threads_with_comments() {
+        assert_eq!(review_prompt("PR #3", &[]), None);
+
+        let threads = vec![
+            ReviewThread {
+                path: "src/a.rs".to_string(),
+                line: Some(10),
+                comments: vec![
+                    ReviewComment {
+                        author: "rev".to_string(),
+                        body: "Try this:\r\n```suggestion\r\nlet x = 1;\r\n```\r\n".to_string(),
+                    },
+                    ReviewComment {
+                        author: String::new(),
+                        body: "Agreed".to_string(),
+                    },
+                ],
+            },
+            ReviewThread {
+                path: "README.md".to_string(),
+                line: None,
+                comments: vec![ReviewComment {
+                    author: "ann".to_string(),
+                    body: "Outdated".to_string(),
+                }],
+            },
+        ];
+        assert_eq!(
+            review_prompt("PR #3", &threads).unwrap(),
+            "Reviewers left comments on PR #3. Address each one, then commit and push.\n\
+             `suggestion` blocks hold the replacement the reviewer proposes for the commented line.\n\
+             \n## src/a.rs:10\n\
+             \n**rev**:\n\nTry this:\n```suggestion\nlet x = 1;\n```\n\
+             \n**ghost**:\n\nAgreed\n\
+             \n## README.md\n\
+             \n**ann**:\n\nOutdated\n"
+        );
+    }
+
+    #[test]
+    fn log_tail_keeps_the_last_lines() {
+        let log: String = (1..=100).map(|i| format!("line {}\n", i)).collect();
+        let tail = log_tail(&log);
+        assert_eq!(tail.lines().count(), LOG_TAIL_LINES);
+        assert_eq!(tail.lines().next(), Some("line 21"));
+        assert_eq!(tail.lines().last(), Some("line 100"));
+
+        assert_eq!(
+            log_tail("downloading 10%\rdownloading 100%\n"),
+            "downloading 100%"
+        );
+    }
+}
@@ -0,0 +1,331 @@
+//! Copy strategies for `files.copy` entries.
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::fs;
+use std::io;
+use std::path::Path;
+use tracing::debug;
+
+use crate::config::CopyMode;
+
+/// Number of files above which a progress bar is shown.
+const PROGRESS_THRESHOLD: u64 = 500;
+
+/// How each file in a copy operation was materialised.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CopyStats {
+    pub reflinked: u64,
+    pub hardlinked: u64,
+    pub copied: u64,
+    /// Files left alone by incremental mode because they were already up to date
+    pub skipped: u64,
+}
+
+/// Copy a file or directory tree from `source` to `dest` using `mode`.
+///
+/// Symlinks are recreated as symlinks. Reflinks and hardlinks fall back to a
+/// regular copy when the filesystem doesn't support them (or source and dest are
+/// on different devices).
+pub fn copy_path(source: &Path, dest: &Path, mode: CopyMode) -> Result<CopyStats> {
+    let mut stats = CopyStats::default();
+    let meta = fs::symlink_metadata(source)
+        .with_context(|| format!("Failed to read metadata for {:?}", source))?;
+
+    if !meta.is_dir() {
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create parent directory for {:?}", dest))?;
+        }
+        copy_entry(source, dest, &meta, mode, &mut stats)?;
+        return Ok(stats);
+    }
+
+    let total = count_files(source);
+    let progress = if total >= PROGRESS_THRESHOLD {
+        let pb = ProgressBar::new(total);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("{msg} [{bar:30.blue}] {pos}/{len} files")
+                .unwrap()
+                .progress_chars("=> "),
+        );
+        pb.set_message(format!("Copying {} ({})", source.display(), mode));
+        Some(pb)
+    } else {
+        None
+    };
+
+    let result = copy_dir(source, dest, mode, &mut stats, progress.as_ref());
+    if let Some(pb) = progress {
+        pb.finish_and_clear();
+    }
+    result?;
+    Ok(stats)
+}
+
+fn copy_dir(
+    source: &Path,
+    dest: &Path,
+    mode: CopyMode,
+    stats: &mut CopyStats,
+    progress: Option<&ProgressBar>,
+) -> Result<()> {
+    fs::create_dir_all(dest).with_context(|| format!("Failed to create directory {:?}", dest))?;
+    for entry in fs::read_dir(source).with_context(|| format!("Failed to read {:?}", source))? {
+        let entry = entry?;
+        let src_path = entry.path();
+        let dest_path = dest.join(entry.file_name());
+        let meta = fs::symlink_metadata(&src_path)?;
+        if meta.is_dir() {
+            copy_dir(&src_path, &dest_path, mode, stats, progress)?;
+        } else {
+            copy_entry(&src_path, &dest_path, &meta, mode, stats)?;
+            if let Some(pb) = progress {
+                pb.inc(1);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn copy_entry(
+    source: &Path,
+    dest: &Path,
+    meta: &fs::Metadata,
+    mode: CopyMode,
+    stats: &mut CopyStats,
+) -> Result<()> {
+    if meta.file_type().is_symlink() {
+        let target = fs::read_link(source)?;
+        remove_existing(dest)?;
+
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&target, dest)
+            .with_context(|| format!("Failed to create symlink {:?}", dest))?;
+
+        #[cfg(windows)]
+        {
+            if source.is_dir() {
+                std::os::windows::fs::symlink_dir(&target, dest)
+            } else {
+                std::os::windows::fs::symlink_file(&target, dest)
+            }
+            .with_context(|| format!("Failed to create symlink {:?}", dest))?;
+        }
+
+        stats.copied += 1;
+        return Ok(());
+    }
+
+    match mode {
+        CopyMode::Copy => {
+            plain_copy(source, dest)?;
+            stats.copied += 1;
+        }
+        CopyMode::Reflink => reflink_or_copy(source, dest, stats)?,
+        CopyMode::Hardlink => {
+            remove_existing(dest)?;
+            match fs::hard_link(source, dest) {
+                Ok(()) => stats.hardlinked += 1,
+                Err(e) => {
+                    debug!(path = %source.display(), error = %e, "file_copy:hardlink failed, copying");
+                    plain_copy(source, dest)?;
+                    stats.copied += 1;
+                }
+            }
+        }
+        CopyMode::Incremental => {
+            if is_up_to_date(meta, dest) {
+                stats.skipped += 1;
+            } else {
+                reflink_or_copy(source, dest, stats)?;
+                // Preserve mtime so the next run can detect unchanged files
+                if let Ok(modified) = meta.modified() {
+                    let file = fs::File::open(dest)?;
+                    file.set_modified(modified)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A destination is up to date if it is a regular file with the same size and mtime.
+fn is_up_to_date(source_meta: &fs::Metadata, dest: &Path) -> bool {
+    let Ok(dest_meta) = fs::symlink_metadata(dest) else {
+        return false;
+    };
+    dest_meta.is_file()
+        && dest_meta.len() == source_meta.len()
+        && matches!(
+            (dest_meta.modified(), source_meta.modified()),
+            (Ok(a), Ok(b)) if a == b
+        )
+}
+
+fn reflink_or_copy(source: &Path, dest: &Path, stats: &mut CopyStats) -> Result<()> {
+    remove_existing(dest)?;
+    match reflink(source, dest) {
+        Ok(()) => stats.reflinked += 1,
+        Err(e) => {
+            debug!(path = %source.display(), error = %e, "file_copy:reflink unsupported, copying");
+            // A failed clone may leave an empty file behind
+            let _ = fs::remove_file(dest);
+            plain_copy(source, dest)?;
+            stats.copied += 1;
+        }
+    }
+    Ok(())
+}
+
+fn plain_copy(source: &Path, dest: &Path) -> Result<()> {
+    remove_existing(dest)?;
+    fs::copy(source, dest)
+        .with_context(|| format!("Failed to copy file {:?} to {:?}", source, dest))?;
+    Ok(())
+}
+
+/// Remove a file or symlink at `dest` so it can be replaced (never follows symlinks).
+fn remove_existing(dest: &Path) -> Result<()> {
+    match fs::symlink_metadata(dest) {
+        Ok(meta) if meta.is_dir() => fs::remove_dir_all(dest)
+            .with_context(|| format!("Failed to remove existing directory at {:?}", dest)),
+        Ok(_) => fs::remove_file(dest)
+            .with_context(|| format!("Failed to remove existing file at {:?}", dest)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Clone a file with copy-on-write (btrfs, xfs, bcachefs via FICLONE).
+#[cfg(target_os = "linux")]
+fn reflink(source: &Path, dest: &Path) -> io::Result<()> {
+    use std::os::fd::AsRawFd;
+    use std::os::unix::fs::PermissionsExt;
+
+    let src = fs::File::open(source)?;
+    let perms = src.metadata()?.permissions();
+    let dst = fs::File::create(dest)?;
+    // SAFETY: both descriptors are valid for the duration of the call.
+    let ret = unsafe { libc::ioctl(dst.as_raw_fd(), libc::FICLONE, src.as_raw_fd()) };
+    if ret != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    dst.set_permissions(fs::Permissions::from_mode(perms.mode()))?;
+    Ok(())
+}
+
+/// Clone a file with copy-on-write (APFS via clonefile).
+#[cfg(target_os = "macos")]
+fn reflink(source: &Path, dest: &Path) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let src = CString::new(source.as_os_str().as_bytes())?;
+    let dst = CString::new(dest.as_os_str().as_bytes())?;
+    // SAFETY: both pointers are valid NUL-terminated strings.
+    let ret = unsafe { libc::clonefile(src.as_ptr(), dst.as_ptr(), 0) };
+    if ret != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn reflink(_source: &Path, _dest: &Path) -> io::Result<()> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+fn count_files(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|e| match e.file_type() {
+            Ok(ft) if ft.is_dir() => count_files(&e.path()),
+            _ => 1,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    fn make_tree(root: &Path) {
+        fs::create_dir_all(root.join("pkg/lib")).unwrap();
+        fs::write(root.join("pkg/index.js"), "module.exports = 1;").unwrap();
+        fs::write(root.join("pkg/lib/util.js"), "util").unwrap();
+        std::os::unix::fs::symlink("index.js", root.join("pkg/link.js")).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_modes_produce_same_tree() {
+        for mode in [
+            CopyMode::Copy,
+            CopyMode::Reflink,
+            CopyMode::Hardlink,
+            CopyMode::Incremental,
+        ] {
+            let src = tempfile::tempdir().unwrap();
+            let dst = tempfile::tempdir().unwrap();
+            make_tree(src.path());
+
+            copy_path(&src.path().join("pkg"), &dst.path().join("pkg"), mode).unwrap();
+
+            let out = dst.path().join("pkg");
+            assert_eq!(fs::read_to_string(out.join("lib/util.js")).unwrap(), "util");
+            assert_eq!(
+                fs::read_link(out.join("link.js")).unwrap(),
+                Path::new("index.js")
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hardlink_shares_inode() {
+        use std::os::unix::fs::MetadataExt;
+
+        let src = tempfile::tempdir().unwrap();
+        let dst = tempfile::tempdir().unwrap();
+        make_tree(src.path());
+
+        let stats = copy_path(
+            &src.path().join("pkg/index.js"),
+            &dst.path().join("index.js"),
+            CopyMode::Hardlink,
+        )
+        .unwrap();
+
+        assert_eq!(stats.hardlinked, 1);
+        let a = fs::metadata(src.path().join("pkg/index.js")).unwrap();
+        let b = fs::metadata(dst.path().join("index.js")).unwrap();
+        assert_eq!(a.ino(), b.ino());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn incremental_skips_unchanged_files() {
+        let src = tempfile::tempdir().unwrap();
+        let dst = tempfile::tempdir().unwrap();
+        make_tree(src.path());
+        let (from, to) = (src.path().join("pkg"), dst.path().join("pkg"));
+
+        let first = copy_path(&from, &to, CopyMode::Incremental).unwrap();
+        assert_eq!(first.skipped, 0);
+
+        fs::write(from.join("lib/util.js"), "changed").unwrap();
+        let second = copy_path(&from, &to, CopyMode::Incremental).unwrap();
+
+        assert_eq!(second.skipped, 1);
+        assert_eq!(
+            fs::read_to_string(to.join("lib/util.js")).unwrap(),
+            "changed"
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! Compare and copy `files.copy` paths between the main worktree and a linked worktree.
+
+use anyhow::{Context, Result, anyhow};
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use tracing::debug;
+
+use crate::config::CopyMode;
+
+use super::file_copy;
+
+/// Direction of a sync between the main worktree and a linked worktree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncDirection {
+    /// Linked worktree -> main worktree
+    Pull,
+    /// Main worktree -> linked worktree
+    Push,
+}
+
+impl SyncDirection {
+    /// Returns (source, destination) roots for this direction.
+    pub fn roots<'a>(&self, main_root: &'a Path, worktree_root: &'a Path) -> (&'a Path, &'a Path) {
+        match self {
+            SyncDirection::Pull => (worktree_root, main_root),
+            SyncDirection::Push => (main_root, worktree_root),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChange {
+    /// Only exists in the source
+    Added,
+    /// Exists on both sides with different content
+    Modified,
+}
+
+impl FileChange {
+    pub fn marker(&self) -> char {
+        match self {
+            FileChange::Added => 'A',
+            FileChange::Modified => 'M',
+        }
+    }
+}
+
+/// A file that differs between source and destination
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    /// Path relative to both roots
+    pub rel_path: PathBuf,
+    pub change: FileChange,
+}
+
+/// Find files matched by `patterns` in `source_root` that are missing or different
+/// in `dest_root`. Directories are expanded recursively; symlinks are skipped.
+/// Files only present in the destination are never reported (sync never deletes).
+pub fn diff(source_root: &Path, dest_root: &Path, patterns: &[String]) -> Result<Vec<FileDiff>> {
+    let canon_source_root = source_root
+        .canonicalize()
+        .with_context(|| format!("Failed to canonicalize {:?}", source_root))?;
+
+    let mut files = Vec::new();
+    for pattern in patterns {
+        let full_pattern = source_root.join(pattern).to_string_lossy().to_string();
+        for entry in glob::glob(&full_pattern)? {
+            let path = entry?;
+            let canon = path
+                .canonicalize()
+                .with_context(|| format!("Failed to canonicalize {:?}", path))?;
+            if !canon.starts_with(&canon_source_root) {
+                return Err(anyhow!(
+                    "Path traversal detected for pattern '{}'. The resolved path '{}' is outside '{}'.",
+                    pattern,
+                    path.display(),
+                    source_root.display()
+                ));
+            }
+            collect_files(source_root, &path, &mut files)?;
+        }
+    }
+    files.sort();
+    files.dedup();
+
+    let mut diffs = Vec::new();
+    for rel_path in files {
+        let dest = dest_root.join(&rel_path);
+        let change = match fs::symlink_metadata(&dest) {
+            Err(_) => Some(FileChange::Added),
+            Ok(meta) if !meta.is_file() => Some(FileChange::Modified),
+            Ok(_) => (!same_contents(&source_root.join(&rel_path), &dest)?)
+                .then_some(FileChange::Modified),
+        };
+        if let Some(change) = change {
+            diffs.push(FileDiff { rel_path, change });
+        }
+    }
+    Ok(diffs)
+}
+
+/// Copy the given files from `source_root` to `dest_root`.
+pub fn apply(source_root: &Path, dest_root: &Path, diffs: &[FileDiff]) -> Result<()> {
+    let canon_dest_root = dest_root
+        .canonicalize()
+        .with_context(|| format!("Failed to canonicalize {:?}", dest_root))?;
+
+    for diff in diffs {
+        if diff.rel_path.is_absolute()
+            || diff
+                .rel_path
+                .components()
+                .any(|c| matches!(c, Component::ParentDir))
+        {
+            return Err(anyhow!(
+                "Refusing to copy '{}': path escapes the worktree",
+                diff.rel_path.display()
+            ));
+        }
+        let dest = dest_root.join(&diff.rel_path);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create parent directory for {:?}", dest))?;
+            // A symlinked parent directory could redirect the write elsewhere
+            if !parent.canonicalize()?.starts_with(&canon_dest_root) {
+                return Err(anyhow!(
+                    "Refusing to copy '{}': destination resolves outside '{}'",
+                    diff.rel_path.display(),
+                    dest_root.display()
+                ));
+            }
+        }
+        file_copy::copy_path(&source_root.join(&diff.rel_path), &dest, CopyMode::Copy)?;
+        debug!(path = %diff.rel_path.display(), "files_sync:copied");
+    }
+    Ok(())
+}
+
+fn collect_files(root: &Path, path: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let meta = fs::symlink_metadata(path)?;
+    if meta.is_dir() {
+        for entry in fs::read_dir(path)? {
+            collect_files(root, &entry?.path(), files)?;
+        }
+    } else if meta.is_file() {
+        let rel = path
+            .strip_prefix(root)
+            .with_context(|| format!("Path {:?} is outside {:?}", path, root))?;
+        files.push(rel.to_path_buf());
+    }
+    Ok(())
+}
+
+fn same_contents(a: &Path, b: &Path) -> Result<bool> {
+    if fs::metadata(a)?.len() != fs::metadata(b)?.len() {
+        return Ok(false);
+    }
+    let mut fa = fs::File::open(a)?;
+    let mut fb = fs::File::open(b)?;
+    let mut buf_a = [0u8; 8192];
+    let mut buf_b = [0u8; 8192];
+    loop {
+        let n = fa.read(&mut buf_a)?;
+        if n == 0 {
+            return Ok(true);
+        }
+        fb.read_exact(&mut buf_b[..n])?;
+        if buf_a[..n] != buf_b[..n] {
+            return Ok(false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_reports_added_and_modified() {
+        let main = tempfile::tempdir().unwrap();
+        let wt = tempfile::tempdir().unwrap();
+        fs::write(main.path().join(".env"), "A=1").unwrap();
+        fs::write(wt.path().join(".env"), "A=2").unwrap();
+        fs::create_dir_all(wt.path().join("cache")).unwrap();
+        fs::write(wt.path().join("cache/new.bin"), "x").unwrap();
+        fs::write(wt.path().join("same.txt"), "same").unwrap();
+        fs::write(main.path().join("same.txt"), "same").unwrap();
+
+        let patterns = vec![".env".into(), "cache".into(), "same.txt".into()];
+        let diffs = diff(wt.path(), main.path(), &patterns).unwrap();
+
+        assert_eq!(
+            diffs,
+            vec![
+                FileDiff {
+                    rel_path: PathBuf::from(".env"),
+                    change: FileChange::Modified,
+                },
+                FileDiff {
+                    rel_path: PathBuf::from("cache/new.bin"),
+                    change: FileChange::Added,
+                },
+            ]
+        );
+
+        apply(wt.path(), main.path(), &diffs).unwrap();
+        assert_eq!(fs::read_to_string(main.path().join(".env")).unwrap(), "A=2");
+        assert!(diff(wt.path(), main.path(), &patterns).unwrap().is_empty());
+    }
+
+    #[test]
+    fn apply_rejects_parent_components() {
+        let src = tempfile::tempdir().unwrap();
+        let dst = tempfile::tempdir().unwrap();
+        let diffs = vec![FileDiff {
+            rel_path: PathBuf::from("../escape"),
+            change: FileChange::Added,
+        }];
+        assert!(apply(src.path(), dst.path(), &diffs).is_err());
+    }
+}
@@ -0,0 +1,367 @@
+use anyhow::{Result, anyhow};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+use crate::config::{Config, GcConfig};
+use crate::forge;
+use crate::git::{self, BranchTip};
+use crate::github::PrSummary;
+use crate::multiplexer::{Multiplexer, util};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Which policies mark a worktree as collectable. A worktree is collected if
+/// any enabled policy matches.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GcPolicy {
+    /// Last commit on the branch is older than this many days
+    pub older_than_days: Option<u64>,
+    /// Branch is fully merged into the main branch
+    pub merged: bool,
+    /// Upstream remote-tracking branch has been deleted
+    pub gone: bool,
+    /// Pull request for the branch is closed or merged
+    pub pr_closed: bool,
+}
+
+impl GcPolicy {
+    pub fn from_config(config: &GcConfig) -> Self {
+        Self {
+            older_than_days: config.older_than_days,
+            merged: config.merged(),
+            gone: config.gone(),
+            pr_closed: config.pr_closed(),
+        }
+    }
+
+    /// Returns true if no policy is enabled.
+    pub fn is_empty(&self) -> bool {
+        self.older_than_days.is_none() && !self.merged && !self.gone && !self.pr_closed
+    }
+}
+
+/// Why a worktree was selected for collection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GcReason {
+    /// No commits for the given number of days
+    Stale(u64),
+    Merged,
+    Gone,
+    /// PR number and state (MERGED or CLOSED)
+    PrClosed(u32, String),
+}
+
+impl fmt::Display for GcReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GcReason::Stale(days) => write!(f, "stale ({}d)", days),
+            GcReason::Merged => write!(f, "merged"),
+            GcReason::Gone => write!(f, "upstream gone"),
+            GcReason::PrClosed(number, state) => {
+                write!(f, "PR #{} {}", number, state.to_lowercase())
+            }
+        }
+    }
+}
+
+/// Why a matching worktree is kept anyway
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcSkip {
+    UncommittedChanges,
+    WindowOpen,
+}
+
+impl fmt::Display for GcSkip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GcSkip::UncommittedChanges => write!(f, "uncommitted changes"),
+            GcSkip::WindowOpen => write!(f, "window open"),
+        }
+    }
+}
+
+/// A worktree matched by at least one gc policy
+#[derive(Debug, Clone)]
+pub struct GcCandidate {
+    pub handle: String,
+    pub branch: String,
+    pub path: PathBuf,
+    pub reasons: Vec<GcReason>,
+    /// Branch has commits of its own and all of them are on the main branch
+    pub merged: bool,
+    /// Set when the worktree matched but must not be removed
+    pub skip: Option<GcSkip>,
+}
+
+impl GcCandidate {
+    /// Whether the worktree can be removed without asking: not skipped, and
+    /// nothing would be lost because the branch is fully merged.
+    pub fn removable_without_prompt(&self) -> bool {
+        self.skip.is_none() && self.merged
+    }
+}
+
+/// Per-branch facts that the policies are evaluated against.
+#[derive(Debug, Clone, Default)]
+struct BranchFacts {
+    age_days: Option<u64>,
+    merged: bool,
+    gone: bool,
+    pr: Option<PrSummary>,
+}
+
+/// Evaluate the policy against a branch, returning every matching reason.
+fn evaluate(policy: &GcPolicy, facts: &BranchFacts) -> Vec<GcReason> {
+    let mut reasons = Vec::new();
+
+    if let (Some(limit), Some(age)) = (policy.older_than_days, facts.age_days)
+        && age >= limit
+    {
+        reasons.push(GcReason::Stale(age));
+    }
+    if policy.merged && facts.merged {
+        reasons.push(GcReason::Merged);
+    }
+    if policy.gone && facts.gone {
+        reasons.push(GcReason::Gone);
+    }
+    if policy.pr_closed
+        && let Some(pr) = &facts.pr
+        && matches!(pr.state.as_str(), "MERGED" | "CLOSED")
+    {
+        reasons.push(GcReason::PrClosed(pr.number, pr.state.clone()));
+    }
+
+    reasons
+}
+
+/// Whole days elapsed between a commit timestamp and now.
+fn age_in_days(committed_at: i64, now: i64) -> u64 {
+    (now.saturating_sub(committed_at) / SECONDS_PER_DAY).max(0) as u64
+}
+
+/// Find all worktrees matched by the policy.
+///
+/// The main worktree and detached worktrees are never candidates. Matching
+/// worktrees with uncommitted changes, or (unless `include_open`) an open
+/// window, are returned with `skip` set.
+pub fn find_candidates(
+    config: &Config,
+    mux: &dyn Multiplexer,
+    policy: &GcPolicy,
+    include_open: bool,
+) -> Result<Vec<GcCandidate>> {
+    if !git::is_git_repo()? {
+        return Err(anyhow!("Not in a git repository"));
+    }
+
+    let worktrees = git::list_worktrees()?;
+    let main_branch = match config.main_branch.clone() {
+        Some(branch) => branch,
+        None => git::get_default_branch()?,
+    };
+    let main_worktree_root = git::get_main_worktree_root()?;
+
+    let tips: HashMap<String, BranchTip> = git::get_branch_tips().unwrap_or_default();
+
+    // Always computed: besides the `merged` policy, it tells callers that remove
+    // without prompting whether a branch can be deleted safely.
+    let base = git::get_merge_base(&main_branch)?;
+    let unmerged: HashSet<String> = git::get_unmerged_branches(&base)?;
+
+    let gone = if policy.gone {
+        git::get_gone_branches().unwrap_or_default()
+    } else {
+        HashSet::new()
+    };
+
+    let prs = if policy.pr_closed {
+        forge::for_repo(Some(&main_worktree_root), config)
+            .list_prs(&main_worktree_root)
+            .unwrap_or_default()
+    } else {
+        HashMap::new()
+    };
+
+    let mux_windows: HashSet<String> = if mux.is_running().unwrap_or(false) {
+        mux.get_all_window_names().unwrap_or_default()
+    } else {
+        HashSet::new()
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let main_sha = tips.get(&main_branch).map(|t| t.sha.as_str());
+    let prefix = config.window_prefix();
+
+    let mut candidates = Vec::new();
+    for (path, branch) in worktrees {
+        if branch == main_branch || branch == "(detached)" || path == main_worktree_root {
+            continue;
+        }
+
+        let tip = tips.get(&branch);
+        // A branch sitting exactly on main has no work of its own yet; treating it
+        // as "merged" would collect every freshly created worktree.
+        let has_own_commits = tip.is_some_and(|t| Some(t.sha.as_str()) != main_sha);
+
+        let facts = BranchFacts {
+            age_days: tip.map(|t| age_in_days(t.committed_at, now)),
+            merged: !unmerged.contains(&branch) && has_own_commits,
+            gone: gone.contains(&branch),
+            pr: prs.get(&branch).cloned(),
+        };
+
+        let reasons = evaluate(policy, &facts);
+        if reasons.is_empty() {
+            continue;
+        }
+
+        let handle = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&branch)
+            .to_string();
+
+        // If the status can't be read, assume there are changes worth keeping
+        let skip = if path.exists() && git::has_uncommitted_changes(&path).unwrap_or(true) {
+            Some(GcSkip::UncommittedChanges)
+        } else if !include_open && mux_windows.contains(&util::prefixed(prefix, &handle)) {
+            Some(GcSkip::WindowOpen)
+        } else {
+            None
+        };
+
+        debug!(handle = %handle, branch = %branch, ?reasons, ?skip, "gc:candidate");
+
+        candidates.push(GcCandidate {
+            handle,
+            branch,
+            path,
+            reasons,
+            merged: facts.merged,
+            skip,
+        });
+    }
+
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(number: u32, state: &str) -> PrSummary {
+        PrSummary {
+            number,
+            title: "Test".to_string(),
+            state: state.to_string(),
+            is_draft: false,
+            checks: None,
+        }
+    }
+
+    #[test]
+    fn evaluate_empty_policy_matches_nothing() {
+        let facts = BranchFacts {
+            age_days: Some(365),
+            merged: true,
+            gone: true,
+            pr: Some(pr(1, "MERGED")),
+        };
+        assert!(evaluate(&GcPolicy::default(), &facts).is_empty());
+    }
+
+    #[test]
+    fn evaluate_age_threshold_is_inclusive() {
+        let policy = GcPolicy {
+            older_than_days: Some(7),
+            ..Default::default()
+        };
+        let young = BranchFacts {
+            age_days: Some(6),
+            ..Default::default()
+        };
+        let old = BranchFacts {
+            age_days: Some(7),
+            ..Default::default()
+        };
+        assert!(evaluate(&policy, &young).is_empty());
+        assert_eq!(evaluate(&policy, &old), vec![GcReason::Stale(7)]);
+    }
+
+    #[test]
+    fn evaluate_collects_all_matching_reasons() {
+        let policy = GcPolicy {
+            older_than_days: None,
+            merged: true,
+            gone: true,
+            pr_closed: true,
+        };
+        let facts = BranchFacts {
+            age_days: Some(1),
+            merged: true,
+            gone: true,
+            pr: Some(pr(42, "MERGED")),
+        };
+        assert_eq!(
+            evaluate(&policy, &facts),
+            vec![
+                GcReason::Merged,
+                GcReason::Gone,
+                GcReason::PrClosed(42, "MERGED".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn evaluate_open_pr_is_not_closed() {
+        let policy = GcPolicy {
+            pr_closed: true,
+            ..Default::default()
+        };
+        let facts = BranchFacts {
+            pr: Some(pr(3, "OPEN")),
+            ..Default::default()
+        };
+        assert!(evaluate(&policy, &facts).is_empty());
+    }
+
+    #[test]
+    fn only_merged_clean_candidates_are_removed_without_prompt() {
+        let candidate = |merged, skip| GcCandidate {
+            handle: "feature".to_string(),
+            branch: "feature".to_string(),
+            path: PathBuf::from("/tmp/feature"),
+            reasons: vec![GcReason::Stale(30)],
+            merged,
+            skip,
+        };
+
+        assert!(candidate(true, None).removable_without_prompt());
+        assert!(!candidate(false, None).removable_without_prompt());
+        assert!(!candidate(true, Some(GcSkip::UncommittedChanges)).removable_without_prompt());
+        assert!(!candidate(true, Some(GcSkip::WindowOpen)).removable_without_prompt());
+    }
+
+    #[test]
+    fn age_in_days_rounds_down_and_clamps() {
+        let now = 10 * SECONDS_PER_DAY;
+        assert_eq!(age_in_days(now - SECONDS_PER_DAY - 1, now), 1);
+        assert_eq!(age_in_days(now + 100, now), 0);
+    }
+
+    #[test]
+    fn reason_display() {
+        assert_eq!(GcReason::Stale(30).to_string(), "stale (30d)");
+        assert_eq!(
+            GcReason::PrClosed(7, "CLOSED".to_string()).to_string(),
+            "PR #7 closed"
+        );
+    }
+}
@@ -0,0 +1,694 @@
+//! Runs lifecycle hooks with timeouts, parallel groups and per-hook logs.
+
+use anyhow::{Context, Result, anyhow};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{debug, info, warn};
+
+use crate::config::{HookEntry, HookSpec};
+use crate::state::StateStore;
+use crate::template;
+
+/// Hook logs older than this are deleted when new hooks run.
+const LOG_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// How often a running hook is polled for exit or timeout.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Where and with what environment a set of hooks runs
+pub struct HookContext<'a> {
+    /// Hook phase, e.g. "post_create" (used in output and log names)
+    pub phase: &'a str,
+    pub handle: &'a str,
+    pub workdir: &'a Path,
+    pub env: &'a [(&'a str, &'a str)],
+}
+
+impl HookContext<'_> {
+    fn var(&self, name: &str) -> Option<&str> {
+        self.env.iter().find(|(k, _)| *k == name).map(|(_, v)| *v)
+    }
+
+    /// Variables available to `{{ ... }}` in hook commands, taken from the hook env.
+    fn template_context(&self) -> serde_json::Value {
+        let mut vars = serde_json::Map::new();
+        vars.insert("handle".into(), self.handle.into());
+        for (key, env_name) in [
+            ("branch", "WM_BRANCH_NAME"),
+            ("worktree_path", "WM_WORKTREE_PATH"),
+            ("project_root", "WM_PROJECT_ROOT"),
+            ("target_branch", "WM_TARGET_BRANCH"),
+        ] {
+            if let Some(value) = self.var(env_name) {
+                vars.insert(key.into(), value.into());
+            }
+        }
+        let ports: Vec<u16> = (1..)
+            .map_while(|i| self.var(&format!("WM_PORT_{}", i)))
+            .filter_map(|p| p.parse().ok())
+            .collect();
+        if let Some(first) = ports.first() {
+            vars.insert("port".into(), (*first).into());
+            vars.insert("ports".into(), ports.into());
+        }
+        serde_json::Value::Object(vars)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookStatus {
+    Succeeded,
+    Failed(Option<i32>),
+    TimedOut(u64),
+    /// The `when` condition was not met
+    Skipped,
+}
+
+/// Result of running a single hook
+#[derive(Debug, Clone)]
+pub struct HookOutcome {
+    pub name: String,
+    pub status: HookStatus,
+    pub duration: Duration,
+    pub log_path: Option<PathBuf>,
+    pub continue_on_error: bool,
+}
+
+impl HookOutcome {
+    fn is_fatal(&self) -> bool {
+        matches!(self.status, HookStatus::Failed(_) | HookStatus::TimedOut(_))
+            && !self.continue_on_error
+    }
+}
+
+/// The `WM_*` environment every hook for a worktree receives: handle, branch,
+/// canonical worktree and project paths, and the worktree's reserved ports.
+pub fn worktree_env(
+    handle: &str,
+    branch: &str,
+    worktree_path: &Path,
+    project_root: &Path,
+) -> Vec<(String, String)> {
+    // canonicalize() ensures symlinks are resolved and paths are absolute
+    let abs_worktree_path = worktree_path
+        .canonicalize()
+        .unwrap_or_else(|_| worktree_path.to_path_buf());
+    let abs_project_root = project_root
+        .canonicalize()
+        .unwrap_or_else(|_| project_root.to_path_buf());
+    let mut env = vec![
+        ("WORKMUX_HANDLE".to_string(), handle.to_string()),
+        ("WM_HANDLE".to_string(), handle.to_string()),
+        ("WM_BRANCH_NAME".to_string(), branch.to_string()),
+        (
+            "WM_WORKTREE_PATH".to_string(),
+            abs_worktree_path.to_string_lossy().into_owned(),
+        ),
+        (
+            "WM_PROJECT_ROOT".to_string(),
+            abs_project_root.to_string_lossy().into_owned(),
+        ),
+    ];
+    env.extend(super::ports::lookup(&abs_worktree_path).unwrap_or_default());
+    env
+}
+
+/// Borrow an owned environment as the pairs `HookContext::env` expects.
+pub fn env_refs(env: &[(String, String)]) -> Vec<(&str, &str)> {
+    env.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect()
+}
+
+/// Run hooks in order. Consecutive hooks sharing a `parallel` group run concurrently.
+///
+/// Stops at the first failing hook unless it has `continue_on_error`, and returns
+/// an error naming the failed hook and its log file.
+pub fn run_hooks(hooks: &[HookEntry], ctx: &HookContext) -> Result<Vec<HookOutcome>> {
+    let specs: Vec<HookSpec> = hooks.iter().map(HookEntry::to_spec).collect();
+    run_specs(&specs, ctx, |_, _| {})
+}
+
+/// Like `run_hooks`, calling `on_stage(completed, names)` before each stage starts.
+pub fn run_specs(
+    specs: &[HookSpec],
+    ctx: &HookContext,
+    mut on_stage: impl FnMut(usize, Vec<String>),
+) -> Result<Vec<HookOutcome>> {
+    // Resolve `{{ ... }}` in every command before running any
+    let context = ctx.template_context();
+    let specs: Vec<HookSpec> = specs
+        .iter()
+        .map(|spec| interpolate_spec(spec, ctx, &context))
+        .collect::<Result<_>>()?;
+    let specs = specs.as_slice();
+
+    let log_dir = match StateStore::new().and_then(|s| s.hook_logs_dir()) {
+        Ok(dir) => {
+            prune_old_logs(&dir);
+            Some(dir)
+        }
+        Err(e) => {
+            warn!(error = %e, "hooks:log directory unavailable, running without logs");
+            None
+        }
+    };
+
+    let mut outcomes = Vec::new();
+    for stage in stages(specs) {
+        on_stage(
+            outcomes.len(),
+            stage.iter().map(|(_, spec)| display_name(spec)).collect(),
+        );
+        let stage_outcomes: Vec<HookOutcome> = if stage.len() == 1 {
+            let (idx, spec) = stage[0];
+            vec![run_one(idx, spec, ctx, log_dir.as_deref(), false)]
+        } else {
+            thread::scope(|scope| {
+                let handles: Vec<_> = stage
+                    .iter()
+                    .map(|&(idx, spec)| {
+                        let log_dir = log_dir.as_deref();
+                        scope.spawn(move || run_one(idx, spec, ctx, log_dir, true))
+                    })
+                    .collect();
+                handles
+                    .into_iter()
+                    .zip(&stage)
+                    .map(|(h, &(_, spec))| {
+                        h.join().unwrap_or_else(|_| HookOutcome {
+                            name: display_name(spec),
+                            status: HookStatus::Failed(None),
+                            duration: Duration::ZERO,
+                            log_path: None,
+                            continue_on_error: spec.continue_on_error,
+                        })
+                    })
+                    .collect()
+            })
+        };
+
+        for outcome in &stage_outcomes {
+            report(outcome);
+        }
+        let fatal = stage_outcomes.iter().find(|o| o.is_fatal()).cloned();
+        outcomes.extend(stage_outcomes);
+
+        if let Some(failed) = fatal {
+            let reason = match failed.status {
+                HookStatus::TimedOut(secs) => format!("timed out after {}s", secs),
+                HookStatus::Failed(Some(code)) => format!("exited with code {}", code),
+                _ => "failed".to_string(),
+            };
+            let log_hint = failed
+                .log_path
+                .map(|p| format!(" (log: {})", p.display()))
+                .unwrap_or_default();
+            return Err(anyhow!(
+                "{} hook '{}' {}{}",
+                ctx.phase,
+                failed.name,
+                reason,
+                log_hint
+            ));
+        }
+    }
+    Ok(outcomes)
+}
+
+/// Interpolate a hook's command. Its `env` is left for the shell to expand, since
+/// the command runs with it.
+fn interpolate_spec(
+    spec: &HookSpec,
+    ctx: &HookContext,
+    context: &serde_json::Value,
+) -> Result<HookSpec> {
+    let command = template::interpolate(&spec.command, context).with_context(|| {
+        format!(
+            "Failed to interpolate {} hook '{}'",
+            ctx.phase,
+            display_name(spec)
+        )
+    })?;
+    Ok(HookSpec {
+        command,
+        ..spec.clone()
+    })
+}
+
+/// Group hooks into stages: each stage is one hook, or a run of consecutive hooks
+/// that share the same `parallel` group.
+fn stages(specs: &[HookSpec]) -> Vec<Vec<(usize, &HookSpec)>> {
+    let mut stages: Vec<Vec<(usize, &HookSpec)>> = Vec::new();
+    for (idx, spec) in specs.iter().enumerate() {
+        let joins_previous = spec.parallel.is_some()
+            && stages
+                .last()
+                .and_then(|s| s.last())
+                .is_some_and(|(_, prev)| prev.parallel == spec.parallel);
+        match stages.last_mut() {
+            Some(stage) if joins_previous => stage.push((idx, spec)),
+            _ => stages.push(vec![(idx, spec)]),
+        }
+    }
+    stages
+}
+
+fn display_name(spec: &HookSpec) -> String {
+    spec.name.clone().unwrap_or_else(|| {
+        let first_line = spec.command.lines().next().unwrap_or_default().trim();
+        if first_line.chars().count() > 60 {
+            format!("{}…", first_line.chars().take(59).collect::<String>())
+        } else {
+            first_line.to_string()
+        }
+    })
+}
+
+fn run_one(
+    idx: usize,
+    spec: &HookSpec,
+    ctx: &HookContext,
+    log_dir: Option<&Path>,
+    in_parallel: bool,
+) -> HookOutcome {
+    let name = display_name(spec);
+    let start = Instant::now();
+    let outcome = |status, log_path| HookOutcome {
+        name: name.clone(),
+        status,
+        duration: start.elapsed(),
+        log_path,
+        continue_on_error: spec.continue_on_error,
+    };
+
+    if let Some(condition) = &spec.when
+        && !condition_holds(condition, spec, ctx)
+    {
+        debug!(phase = ctx.phase, hook = %name, "hooks:skipped, condition not met");
+        return outcome(HookStatus::Skipped, None);
+    }
+
+    let log_path = log_dir.map(|dir| dir.join(log_file_name(ctx, idx, &name)));
+    let log_file = log_path.as_ref().and_then(|p| match File::create(p) {
+        Ok(f) => Some(Arc::new(Mutex::new(f))),
+        Err(e) => {
+            warn!(path = %p.display(), error = %e, "hooks:failed to create log file");
+            None
+        }
+    });
+    if let Some(log) = &log_file
+        && let Ok(mut f) = log.lock()
+    {
+        let _ = writeln!(f, "$ {}\n", spec.command);
+    }
+
+    info!(phase = ctx.phase, hook = %name, "hooks:start");
+    // Timed or parallel hooks get their own process group so the whole tree can be
+    // killed; they can't read from the terminal since they're in the background.
+    let detached = spec.timeout.is_some() || in_parallel;
+    let mut cmd = shell(&spec.command, spec, ctx);
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    if detached {
+        cmd.stdin(Stdio::null());
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.process_group(0);
+        }
+    }
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            warn!(hook = %name, error = %e, "hooks:failed to spawn");
+            return outcome(HookStatus::Failed(None), log_path);
+        }
+    };
+
+    let prefix = in_parallel.then(|| format!("[{}] ", name));
+    let stdout = child.stdout.take().map(|out| {
+        let (log, prefix) = (log_file.clone(), prefix.clone());
+        thread::spawn(move || tee(out, io::stdout(), log, prefix))
+    });
+    let stderr = child.stderr.take().map(|err| {
+        let (log, prefix) = (log_file.clone(), prefix.clone());
+        thread::spawn(move || tee(err, io::stderr(), log, prefix))
+    });
+
+    let status = wait(&mut child, spec.timeout);
+    for handle in [stdout, stderr].into_iter().flatten() {
+        let _ = handle.join();
+    }
+    info!(phase = ctx.phase, hook = %name, ?status, elapsed = ?start.elapsed(), "hooks:complete");
+    outcome(status, log_path)
+}
+
+fn shell(command: &str, spec: &HookSpec, ctx: &HookContext) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command).current_dir(ctx.workdir);
+    for (key, value) in ctx.env {
+        cmd.env(key, value);
+    }
+    for (key, value) in &spec.env {
+        cmd.env(key, value);
+    }
+    cmd
+}
+
+fn condition_holds(condition: &str, spec: &HookSpec, ctx: &HookContext) -> bool {
+    shell(condition, spec, ctx)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// Wait for the child, killing its process group once `timeout` seconds pass.
+fn wait(child: &mut Child, timeout: Option<u64>) -> HookStatus {
+    let deadline = timeout.map(|secs| (secs, Instant::now() + Duration::from_secs(secs)));
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return HookStatus::Succeeded,
+            Ok(Some(status)) => return HookStatus::Failed(status.code()),
+            Ok(None) => {}
+            Err(_) => return HookStatus::Failed(None),
+        }
+        if let Some((secs, at)) = deadline
+            && Instant::now() >= at
+        {
+            kill_tree(child);
+            let _ = child.wait();
+            return HookStatus::TimedOut(secs);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Kill the child together with the process group it leads. Without process
+/// groups only the child itself is killed.
+#[cfg(unix)]
+fn kill_tree(child: &mut Child) {
+    // SAFETY: kill(2) with a negative pid signals the process group we created.
+    unsafe {
+        libc::kill(-(child.id() as i32), libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_tree(child: &mut Child) {
+    let _ = child.kill();
+}
+
+/// Copy output to the terminal and the log file. Parallel hooks are copied line
+/// by line with a name prefix so their output stays readable.
+fn tee(
+    source: impl Read,
+    mut terminal: impl Write,
+    log: Option<Arc<Mutex<File>>>,
+    prefix: Option<String>,
+) {
+    let mut reader = BufReader::new(source);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        let read = match &prefix {
+            Some(_) => reader.read_until(b'\n', &mut buf),
+            None => reader.fill_buf().map(|chunk| {
+                buf.extend_from_slice(chunk);
+                chunk.len()
+            }),
+        };
+        match read {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                if prefix.is_none() {
+                    reader.consume(n);
+                }
+            }
+        }
+        if let Some(log) = &log
+            && let Ok(mut f) = log.lock()
+        {
+            let _ = f.write_all(&buf);
+        }
+        if let Some(p) = &prefix {
+            let _ = terminal.write_all(p.as_bytes());
+        }
+        let _ = terminal.write_all(&buf);
+        let _ = terminal.flush();
+    }
+}
+
+fn report(outcome: &HookOutcome) {
+    let elapsed = format_duration(outcome.duration);
+    match &outcome.status {
+        HookStatus::Succeeded => println!("  ✓ {} ({})", outcome.name, elapsed),
+        HookStatus::Skipped => println!("  - {} (skipped)", outcome.name),
+        HookStatus::TimedOut(secs) => {
+            eprintln!("  ✗ {} (timed out after {}s)", outcome.name, secs)
+        }
+        HookStatus::Failed(_) if outcome.continue_on_error => {
+            eprintln!(
+                "  ! {} (failed after {}, continuing)",
+                outcome.name, elapsed
+            )
+        }
+        HookStatus::Failed(_) => eprintln!("  ✗ {} (failed after {})", outcome.name, elapsed),
+    }
+}
+
+fn log_file_name(ctx: &HookContext, idx: usize, name: &str) -> String {
+    let ts = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let slug: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .take(40)
+        .collect();
+    format!(
+        "{}-{}-{}-{}-{}.log",
+        ts,
+        ctx.phase,
+        ctx.handle.replace('/', "-"),
+        idx + 1,
+        slug.trim_matches('-')
+    )
+}
+
+fn prune_old_logs(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let expired = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.elapsed().ok())
+            .is_some_and(|age| age > LOG_RETENTION);
+        if expired {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Format a duration as "0.4s", "12.3s" or "2m05s".
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs < 60.0 {
+        format!("{:.1}s", secs)
+    } else {
+        let total = duration.as_secs();
+        format!("{}m{:02}s", total / 60, total % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(command: &str) -> HookSpec {
+        HookSpec {
+            command: command.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn ctx(dir: &Path) -> HookContext<'_> {
+        HookContext {
+            phase: "test",
+            handle: "wt",
+            workdir: dir,
+            env: &[("WM_HANDLE", "wt")],
+        }
+    }
+
+    #[test]
+    fn stages_group_consecutive_parallel_hooks() {
+        let mut a = spec("a");
+        a.parallel = Some("deps".into());
+        let mut b = spec("b");
+        b.parallel = Some("deps".into());
+        let c = spec("c");
+        let mut d = spec("d");
+        d.parallel = Some("deps".into());
+
+        let specs = vec![a, b, c, d];
+        let sizes: Vec<usize> = stages(&specs).iter().map(|s| s.len()).collect();
+        assert_eq!(sizes, vec![2, 1, 1]);
+    }
+
+    #[test]
+    fn template_context_comes_from_hook_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = HookContext {
+            env: &[
+                ("WM_BRANCH_NAME", "feature/auth"),
+                ("WM_PROJECT_ROOT", "/repo"),
+                ("WM_PORT", "4000"),
+                ("WM_PORT_1", "4000"),
+                ("WM_PORT_2", "4001"),
+            ],
+            ..ctx(dir.path())
+        };
+        assert_eq!(
+            ctx.template_context(),
+            serde_json::json!({
+                "handle": "wt",
+                "branch": "feature/auth",
+                "project_root": "/repo",
+                "port": 4000,
+                "ports": [4000, 4001],
+            })
+        );
+    }
+
+    #[test]
+    fn run_one_times_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut hook = spec("sleep 5");
+        hook.timeout = Some(1);
+
+        let outcome = run_one(0, &hook, &ctx(dir.path()), None, false);
+        assert_eq!(outcome.status, HookStatus::TimedOut(1));
+        assert!(outcome.duration < Duration::from_secs(4));
+    }
+
+    #[test]
+    fn run_one_respects_when_and_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let logs = tempfile::tempdir().unwrap();
+
+        let mut skipped = spec("touch ran");
+        skipped.when = Some("test -f missing".into());
+        let outcome = run_one(0, &skipped, &ctx(dir.path()), Some(logs.path()), false);
+        assert_eq!(outcome.status, HookStatus::Skipped);
+        assert!(!dir.path().join("ran").exists());
+
+        let mut with_env = spec("echo \"$WM_HANDLE-$EXTRA\" > out");
+        with_env.env.insert("EXTRA".into(), "x".into());
+        let outcome = run_one(1, &with_env, &ctx(dir.path()), Some(logs.path()), false);
+        assert_eq!(outcome.status, HookStatus::Succeeded);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("out")).unwrap(),
+            "wt-x\n"
+        );
+        assert!(outcome.log_path.unwrap().exists());
+    }
+
+    #[test]
+    fn interpolation_leaves_hook_env_and_shell_syntax_to_the_shell() {
+        let dir = tempfile::tempdir().unwrap();
+        let logs = tempfile::tempdir().unwrap();
+        let ctx = ctx(dir.path());
+        let context = ctx.template_context();
+
+        let mut serve = spec("echo serve --port ${PORT:-3000} > port");
+        serve.env.insert("PORT".into(), "4000".into());
+        let serve = interpolate_spec(&serve, &ctx, &context).unwrap();
+        assert_eq!(serve.command, "echo serve --port ${PORT:-3000} > port");
+        let outcome = run_one(0, &serve, &ctx, Some(logs.path()), false);
+        assert_eq!(outcome.status, HookStatus::Succeeded);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("port")).unwrap(),
+            "serve --port 4000\n"
+        );
+
+        let script = interpolate_spec(
+            &spec("FOO=1; echo ${FOO:-0} '{{.Names}}' {{ handle }} > out"),
+            &ctx,
+            &context,
+        )
+        .unwrap();
+        let outcome = run_one(1, &script, &ctx, Some(logs.path()), false);
+        assert_eq!(outcome.status, HookStatus::Succeeded);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("out")).unwrap(),
+            "1 {{.Names}} wt\n"
+        );
+    }
+
+    #[test]
+    fn interpolated_values_cannot_inject_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = HookContext {
+            env: &[("WM_BRANCH_NAME", "x$(touch pwned)")],
+            ..ctx(dir.path())
+        };
+
+        let hook = interpolate_spec(
+            &spec("echo {{ branch }} > out"),
+            &ctx,
+            &ctx.template_context(),
+        )
+        .unwrap();
+        let outcome = run_one(0, &hook, &ctx, None, false);
+
+        assert_eq!(outcome.status, HookStatus::Succeeded);
+        assert!(!dir.path().join("pwned").exists());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("out")).unwrap(),
+            "x$(touch pwned)\n"
+        );
+    }
+
+    #[test]
+    fn worktree_env_uses_canonical_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let worktree = dir.path().join("wt");
+        fs::create_dir(&worktree).unwrap();
+
+        let env = worktree_env("wt", "feature/x", &worktree.join("."), dir.path());
+        let var = |name: &str| {
+            env.iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.clone())
+                .unwrap()
+        };
+
+        assert_eq!(var("WM_HANDLE"), "wt");
+        assert_eq!(var("WORKMUX_HANDLE"), "wt");
+        assert_eq!(var("WM_BRANCH_NAME"), "feature/x");
+        assert_eq!(
+            var("WM_WORKTREE_PATH"),
+            worktree.canonicalize().unwrap().to_string_lossy()
+        );
+        assert_eq!(
+            var("WM_PROJECT_ROOT"),
+            dir.path().canonicalize().unwrap().to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn format_duration_units() {
+        assert_eq!(format_duration(Duration::from_millis(400)), "0.4s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m05s");
+    }
+}
@@ -0,0 +1,96 @@
+//! Start worktrees from forge issues (`workmux add --issue`).
+
+use anyhow::{Context, Result};
+
+use crate::forge::{self, Issue};
+use crate::spinner;
+use crate::template::slugify;
+
+/// Prompt used when `--issue` is given without one. Any prompt can use the
+/// issue's fields as `{{ issue.* }}`.
+pub const DEFAULT_ISSUE_PROMPT: &str = "Resolve issue #{{ issue.number }}: {{ issue.title }}\n\
+{% if issue.body %}\n{{ issue.body }}\n{% endif %}";
+
+/// Longest title slug kept in a branch name
+const MAX_SLUG_LEN: usize = 40;
+
+/// Fetch issues from the current repository's forge, in the given order
+pub fn fetch_issues(numbers: &[u32]) -> Result<Vec<Issue>> {
+    let forge = forge::current();
+    numbers
+        .iter()
+        .map(|&number| {
+            let issue = spinner::with_spinner(&format!("Fetching issue #{}", number), || {
+                forge.get_issue(number)
+            })
+            .with_context(|| format!("Failed to fetch issue #{}", number))?;
+            println!("Issue #{}: {}", issue.number, issue.title);
+            Ok(issue)
+        })
+        .collect()
+}
+
+/// Branch name for an issue: its number followed by a slug of its title,
+/// e.g. "123-fix-login-redirect"
+pub fn branch_name(issue: &Issue) -> String {
+    let slug = slugify(&issue.title);
+    // The slug is ASCII, so any byte index is a char boundary
+    let slug = if slug.len() > MAX_SLUG_LEN {
+        let cut = &slug[..MAX_SLUG_LEN];
+        cut.rfind('-').map_or(cut, |end| &cut[..end])
+    } else {
+        &slug
+    };
+    if slug.is_empty() {
+        format!("issue-{}", issue.number)
+    } else {
+        format!("{}-{}", issue.number, slug)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::create_template_env;
+
+    fn issue(number: u32, title: &str, body: &str) -> Issue {
+        Issue {
+            number,
+            title: title.to_string(),
+            body: body.to_string(),
+            labels: vec!["bug".to_string()],
+            url: String::new(),
+        }
+    }
+
+    #[test]
+    fn branch_name_slugs_and_shortens_the_title() {
+        assert_eq!(
+            branch_name(&issue(12, "Fix login redirect!", "")),
+            "12-fix-login-redirect"
+        );
+        assert_eq!(
+            branch_name(&issue(
+                7,
+                "Support configuring the dashboard refresh interval per project",
+                ""
+            )),
+            "7-support-configuring-the-dashboard"
+        );
+        assert_eq!(branch_name(&issue(3, "日本語", "")), "issue-3");
+    }
+
+    #[test]
+    fn default_prompt_includes_the_body_when_present() {
+        let env = create_template_env();
+        let render = |issue: &Issue| {
+            env.render_str(DEFAULT_ISSUE_PROMPT, serde_json::json!({ "issue": issue }))
+                .unwrap()
+        };
+        assert_eq!(
+            render(&issue(12, "Crash on start", "Steps: {{ run }}")),
+            "Resolve issue #12: Crash on start\n\nSteps: {{ run }}\n"
+        );
+        assert_eq!(render(&issue(13, "Typo", "")), "Resolve issue #13: Typo\n");
+    }
+}
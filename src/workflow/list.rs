@@ -1,8 +1,9 @@
 use anyhow::{Result, anyhow};
 use std::collections::HashSet;
 
+use crate::github::PrSummary;
 use crate::multiplexer::{Multiplexer, util};
-use crate::{config, git, github, spinner};
+use crate::{config, forge, git, spinner};
 
 use super::types::WorktreeInfo;
 
@@ -42,8 +43,10 @@ pub fn list(
 
     // Batch fetch all PRs if requested (single API call)
     let pr_map = if fetch_pr_status {
+        let repo_root = git::get_main_worktree_root()?;
+        let forge = forge::for_repo(Some(&repo_root), config);
         spinner::with_spinner("Fetching PR status", || {
-            Ok(github::list_prs().unwrap_or_default())
+            Ok(forge.list_prs(&repo_root).unwrap_or_default())
         })?
     } else {
         std::collections::HashMap::new()
@@ -75,8 +78,15 @@ pub fn list(
                 false
             };
 
-            // Lookup PR info from batch fetch
-            let pr_info = pr_map.get(&branch).cloned();
+            // Lookup PR info from batch fetch, falling back to the PR recorded
+            // by `workmux pr create` while the forge catches up
+            let pr_info = if fetch_pr_status {
+                pr_map.get(&branch).cloned().or_else(|| {
+                    git::get_branch_pr_in(&branch, Some(&path)).map(PrSummary::recorded)
+                })
+            } else {
+                None
+            };
 
             WorktreeInfo {
                 branch,
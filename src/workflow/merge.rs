@@ -1,11 +1,20 @@
 use anyhow::{Context, Result, anyhow};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::{cmd, git};
-use tracing::{debug, info};
+use crate::config::HookEntry;
+use crate::forge::{self, Forge};
+use crate::github::{CheckState, PrSummary};
+use crate::{git, llm, spinner};
+use tracing::{debug, info, warn};
 
 use super::cleanup;
 use super::context::WorkflowContext;
-use super::types::MergeResult;
+use super::types::{MergeResult, PrMergeOptions};
+
+/// How often the forge is polled while waiting for checks or a merge
+const PR_POLL_INTERVAL: Duration = Duration::from_secs(15);
 
 /// Merge a branch into the target branch and clean up
 #[allow(clippy::too_many_arguments)]
@@ -15,6 +24,7 @@ pub fn merge(
     ignore_uncommitted: bool,
     rebase: bool,
     squash: bool,
+    generate_message: bool,
     keep: bool,
     no_verify: bool,
     notification: bool,
@@ -26,6 +36,7 @@ pub fn merge(
         ignore_uncommitted,
         rebase,
         squash,
+        generate_message,
         keep,
         no_verify,
         "merge:start"
@@ -58,80 +69,15 @@ pub fn merge(
         "merge:worktree resolved"
     );
 
-    // Determine the target branch:
-    // 1. Use explicit --into if provided
-    // 2. Otherwise, check if branch has a stored base (from workmux add)
-    // 3. Fall back to main_branch
-    let detected_base: Option<String> = if into_branch.is_some() {
-        None // User explicitly specified target, no auto-detection needed
-    } else {
-        match git::get_branch_base(&branch_to_merge) {
-            Ok(base) => {
-                // Verify the base branch still exists
-                if git::branch_exists(&base)? {
-                    info!(
-                        branch = %branch_to_merge,
-                        base = %base,
-                        "merge:auto-detected base branch"
-                    );
-                    Some(base)
-                } else {
-                    info!(
-                        branch = %branch_to_merge,
-                        base = %base,
-                        "merge:base branch not found, defaulting to main"
-                    );
-                    None
-                }
-            }
-            Err(_) => {
-                debug!(
-                    branch = %branch_to_merge,
-                    "merge:no base config found, defaulting to main"
-                );
-                None
-            }
-        }
+    let source = MergeSource {
+        handle,
+        branch: &branch_to_merge,
+        worktree_path: &worktree_path,
     };
 
-    let target_branch = into_branch
-        .map(|s| s.to_string())
-        .or(detected_base)
-        .unwrap_or_else(|| context.main_branch.clone());
-    let target_branch = target_branch.as_str();
-
-    // Resolve the worktree path and window handle for the TARGET branch.
-    // We prioritize finding an existing worktree for the target branch to support
-    // workflows where 'main' is checked out in a linked worktree (issue #29).
-    let (target_worktree_path, target_window_name) = match git::get_worktree_path(target_branch) {
-        Ok(path) => {
-            // Target is checked out in a worktree (could be main root or a linked worktree)
-            if path == context.main_worktree_root {
-                // It's in the main root. Use the main branch name as the window handle.
-                (path, context.main_branch.clone())
-            } else {
-                // It's in a linked worktree. Use the directory name as the handle.
-                let handle = path
-                    .file_name()
-                    .and_then(|s| s.to_str())
-                    .ok_or_else(|| anyhow!("Invalid worktree path for target branch"))?
-                    .to_string();
-                (path, handle)
-            }
-        }
-        Err(_) => {
-            // Target branch is NOT checked out anywhere.
-            // We fallback to using the main worktree root to perform the merge.
-            debug!(
-                target = target_branch,
-                "merge:target branch has no worktree, using main worktree"
-            );
-            (
-                context.main_worktree_root.clone(),
-                context.main_branch.clone(),
-            )
-        }
-    };
+    let target = resolve_target(&branch_to_merge, into_branch, context)?;
+    let target_branch = target.branch.as_str();
+    let target_worktree_path = target.worktree_path.clone();
 
     // Handle changes in the source worktree
     // Only check for unstaged/untracked when worktree will be deleted (!keep)
@@ -194,30 +140,14 @@ pub fn merge(
         && !hooks.is_empty()
     {
         info!(count = hooks.len(), "merge:running pre-merge hooks");
-
-        let abs_worktree_path = worktree_path
-            .canonicalize()
-            .unwrap_or_else(|_| worktree_path.clone());
-        let abs_project_root = context
-            .main_worktree_root
-            .canonicalize()
-            .unwrap_or_else(|_| context.main_worktree_root.clone());
-        let worktree_path_str = abs_worktree_path.to_string_lossy();
-        let project_root_str = abs_project_root.to_string_lossy();
-
-        let hook_env = [
-            ("WORKMUX_HANDLE", handle),
-            ("WM_BRANCH_NAME", branch_to_merge.as_str()),
-            ("WM_TARGET_BRANCH", target_branch),
-            ("WM_WORKTREE_PATH", worktree_path_str.as_ref()),
-            ("WM_PROJECT_ROOT", project_root_str.as_ref()),
-            ("WM_HANDLE", handle),
-        ];
-
-        for command in hooks {
-            cmd::shell_command_with_env(command, &worktree_path, &hook_env)
-                .with_context(|| format!("Pre-merge hook failed: '{}'", command))?;
-        }
+        run_merge_hooks(
+            "pre_merge",
+            hooks,
+            &source,
+            target_branch,
+            &worktree_path,
+            context,
+        )?;
     }
 
     // Helper closure to generate the error message for merge conflicts
@@ -275,10 +205,27 @@ pub fn merge(
             return Err(conflict_err(&branch_to_merge));
         }
 
-        // Prompt the user to provide a commit message for the squashed changes.
-        println!("Staged squashed changes. Please provide a commit message in your editor.");
-        git::commit_with_editor(&target_worktree_path)
-            .context("Failed to commit squashed changes. You may need to commit them manually.")?;
+        // Prompt the user to provide (or review) a commit message for the squashed changes.
+        let message = if generate_message {
+            generate_squash_message(&target_worktree_path, context)
+        } else {
+            None
+        };
+        match message {
+            Some(message) => {
+                println!(
+                    "Staged squashed changes. Review the generated commit message in your editor."
+                );
+                git::commit_with_editor_message(&target_worktree_path, &message)
+            }
+            None => {
+                println!(
+                    "Staged squashed changes. Please provide a commit message in your editor."
+                );
+                git::commit_with_editor(&target_worktree_path)
+            }
+        }
+        .context("Failed to commit squashed changes. You may need to commit them manually.")?;
         info!(branch = %branch_to_merge, "merge:squash merge committed");
     } else {
         // Default merge commit workflow
@@ -300,6 +247,25 @@ pub fn merge(
         ));
     }
 
+    // Run post-merge hooks in the target worktree. The merge already happened,
+    // so a failure is reported but doesn't stop cleanup.
+    if let Some(hooks) = &context.config.post_merge
+        && !hooks.is_empty()
+    {
+        info!(count = hooks.len(), "merge:running post-merge hooks");
+        if let Err(e) = run_merge_hooks(
+            "post_merge",
+            hooks,
+            &source,
+            target_branch,
+            &target_worktree_path,
+            context,
+        ) {
+            warn!(error = %e, "merge:post-merge hooks failed");
+            eprintln!("Warning: {:#}", e);
+        }
+    }
+
     // Skip cleanup if --keep flag is used
     if keep {
         info!(branch = %branch_to_merge, "merge:skipping cleanup (--keep)");
@@ -325,7 +291,7 @@ pub fn merge(
     cleanup::navigate_to_target_and_close(
         context.mux.as_ref(),
         &context.prefix,
-        &target_window_name,
+        &target.window_name,
         handle,
         &cleanup_result,
     )?;
@@ -337,6 +303,460 @@ pub fn merge(
     })
 }
 
+/// Merge a branch's PR through the forge, wait until the forge reports it
+/// merged, then clean up like a local merge
+pub fn merge_via_pr(
+    name: &str,
+    ignore_uncommitted: bool,
+    options: &PrMergeOptions,
+    keep: bool,
+    no_verify: bool,
+    notification: bool,
+    context: &WorkflowContext,
+) -> Result<MergeResult> {
+    info!(
+        name = name,
+        ignore_uncommitted,
+        method = ?options.method,
+        wait = options.wait,
+        auto = options.auto,
+        keep,
+        no_verify,
+        "merge:via-pr start"
+    );
+
+    context.chdir_to_main_worktree()?;
+
+    let (worktree_path, branch_to_merge) = git::find_worktree(name)
+        .with_context(|| format!("No worktree found with name '{}'", name))?;
+    let handle = worktree_path
+        .file_name()
+        .and_then(std::ffi::OsStr::to_str)
+        .ok_or_else(|| {
+            anyhow!(
+                "Could not derive handle from worktree path: {}",
+                worktree_path.display()
+            )
+        })?;
+
+    // Only what has been pushed is part of the PR; anything else would be
+    // lost when the worktree is removed
+    if !ignore_uncommitted
+        && (git::has_tracked_changes(&worktree_path)?
+            || (!keep && git::has_untracked_files(&worktree_path)?))
+    {
+        return Err(anyhow!(
+            "Worktree for '{}' has changes that are not part of the PR. Commit and push them, or use --ignore-uncommitted.",
+            branch_to_merge
+        ));
+    }
+    let status = git::get_git_status(&worktree_path);
+    if !status.has_upstream {
+        return Err(anyhow!(
+            "Branch '{}' has not been pushed. Open a PR with 'workmux pr create' first.",
+            branch_to_merge
+        ));
+    }
+    if status.ahead > 0 {
+        return Err(anyhow!(
+            "Branch '{}' has {} unpushed commit(s). Push them so they are part of the PR.",
+            branch_to_merge,
+            status.ahead
+        ));
+    }
+
+    let forge = forge::for_repo(Some(&worktree_path), &context.config);
+    let label = forge.request_label();
+    let pr = forge
+        .pr_status(&worktree_path, &branch_to_merge)?
+        .ok_or_else(|| {
+            anyhow!(
+                "No {} found for branch '{}'. Open one with 'workmux pr create'.",
+                label,
+                branch_to_merge
+            )
+        })?;
+    let number = pr.number;
+
+    let target = resolve_target(&branch_to_merge, None, context)?;
+    let target_branch = target.branch.as_str();
+    let source = MergeSource {
+        handle,
+        branch: &branch_to_merge,
+        worktree_path: &worktree_path,
+    };
+
+    match pr.state.as_str() {
+        "MERGED" => println!("{} #{} is already merged", label, number),
+        "OPEN" => {
+            if pr.is_draft {
+                return Err(anyhow!(
+                    "{} #{} is a draft. Mark it ready for review before merging.",
+                    label,
+                    number
+                ));
+            }
+
+            if !no_verify
+                && let Some(hooks) = &context.config.pre_merge
+                && !hooks.is_empty()
+            {
+                info!(count = hooks.len(), "merge:running pre-merge hooks");
+                run_merge_hooks(
+                    "pre_merge",
+                    hooks,
+                    &source,
+                    target_branch,
+                    &worktree_path,
+                    context,
+                )?;
+            }
+
+            let deadline = Instant::now() + options.timeout;
+            if options.wait {
+                wait_for_checks(
+                    forge.as_ref(),
+                    &worktree_path,
+                    &branch_to_merge,
+                    label,
+                    pr,
+                    deadline,
+                )?;
+            }
+
+            forge.merge_pr(&worktree_path, number, options.method, options.auto)?;
+            info!(number, auto = options.auto, "merge:forge merge requested");
+            if options.auto {
+                println!(
+                    "Auto-merge enabled for {} #{}. Waiting for it to merge \
+                     (Ctrl-C stops waiting; run 'workmux merge --via-pr' again to clean up)...",
+                    label, number
+                );
+            }
+            wait_for_merge(
+                forge.as_ref(),
+                &worktree_path,
+                &branch_to_merge,
+                label,
+                number,
+                deadline,
+            )?;
+        }
+        _ => {
+            return Err(anyhow!(
+                "{} #{} was closed without being merged",
+                label,
+                number
+            ));
+        }
+    }
+
+    // Bring the local target branch up to date with the merge, from the remote
+    // the PR branch was pushed to
+    let remote = git::get_branch_remote(&branch_to_merge)?;
+    git::fetch_remote(&remote)?;
+    if let Ok(path) = git::get_worktree_path(target_branch)
+        && !git::has_tracked_changes(&path)?
+        && let Err(e) =
+            git::fast_forward_in_worktree(&path, &format!("{}/{}", remote, target_branch))
+    {
+        warn!(target = target_branch, error = %e, "merge:could not fast-forward target branch");
+    }
+
+    if notification {
+        show_notification(&format!(
+            "Merged {} #{} into '{}'",
+            label, number, target_branch
+        ));
+    }
+
+    if let Some(hooks) = &context.config.post_merge
+        && !hooks.is_empty()
+    {
+        info!(count = hooks.len(), "merge:running post-merge hooks");
+        if let Err(e) = run_merge_hooks(
+            "post_merge",
+            hooks,
+            &source,
+            target_branch,
+            &target.worktree_path,
+            context,
+        ) {
+            warn!(error = %e, "merge:post-merge hooks failed");
+            eprintln!("Warning: {:#}", e);
+        }
+    }
+
+    if keep {
+        info!(branch = %branch_to_merge, "merge:skipping cleanup (--keep)");
+        return Ok(MergeResult {
+            branch_merged: branch_to_merge,
+            main_branch: target_branch.to_string(),
+            had_staged_changes: false,
+        });
+    }
+
+    // The branch was merged on the forge, so git doesn't see it as merged
+    // locally; force the cleanup
+    info!(branch = %branch_to_merge, "merge:cleanup start");
+    let cleanup_result = cleanup::cleanup(
+        context,
+        &branch_to_merge,
+        handle,
+        &worktree_path,
+        true,
+        false,
+    )?;
+
+    cleanup::navigate_to_target_and_close(
+        context.mux.as_ref(),
+        &context.prefix,
+        &target.window_name,
+        handle,
+        &cleanup_result,
+    )?;
+
+    Ok(MergeResult {
+        branch_merged: branch_to_merge,
+        main_branch: target_branch.to_string(),
+        had_staged_changes: false,
+    })
+}
+
+/// Whether a PR's checks allow merging: true when they passed (or there are
+/// none), false while they are running, an error when they failed
+fn checks_passed(pr: &PrSummary, label: &str) -> Result<bool> {
+    match &pr.checks {
+        None | Some(CheckState::Success) => Ok(true),
+        Some(CheckState::Pending { .. }) => Ok(false),
+        Some(CheckState::Failure { passed, total }) => Err(anyhow!(
+            "Checks failed on {} #{} ({}/{} passed)",
+            label,
+            pr.number,
+            passed,
+            total
+        )),
+    }
+}
+
+/// Poll the forge until the PR's checks pass, giving up at `deadline`
+fn wait_for_checks(
+    forge: &dyn Forge,
+    workdir: &Path,
+    branch: &str,
+    label: &str,
+    mut pr: PrSummary,
+    deadline: Instant,
+) -> Result<()> {
+    let mut last_progress = None;
+    while !checks_passed(&pr, label)? {
+        if let Some(CheckState::Pending { passed, total }) = pr.checks
+            && last_progress != Some((passed, total))
+        {
+            println!(
+                "Waiting for checks on {} #{} ({}/{} passed)...",
+                label, pr.number, passed, total
+            );
+            last_progress = Some((passed, total));
+        }
+        if Instant::now() >= deadline {
+            return Err(anyhow!(
+                "Timed out waiting for checks on {} #{}. Run 'workmux merge --via-pr' again to keep waiting, or merge it by hand.",
+                label,
+                pr.number
+            ));
+        }
+        thread::sleep(PR_POLL_INTERVAL);
+        pr = forge
+            .pr_status(workdir, branch)?
+            .ok_or_else(|| anyhow!("{} #{} is no longer found", label, pr.number))?;
+    }
+    Ok(())
+}
+
+/// Poll the forge until it reports the PR merged, giving up at `deadline`
+fn wait_for_merge(
+    forge: &dyn Forge,
+    workdir: &Path,
+    branch: &str,
+    label: &str,
+    number: u32,
+    deadline: Instant,
+) -> Result<()> {
+    loop {
+        let pr = forge.pr_status(workdir, branch)?;
+        match pr.as_ref().map(|pr| pr.state.as_str()) {
+            Some("MERGED") => return Ok(()),
+            Some("CLOSED") => {
+                return Err(anyhow!(
+                    "{} #{} was closed without being merged",
+                    label,
+                    number
+                ));
+            }
+            _ if Instant::now() >= deadline => {
+                return Err(anyhow!(
+                    "Timed out waiting for {} #{} to merge. Run 'workmux merge --via-pr' again once it has merged to clean up, or merge it by hand.",
+                    label,
+                    number
+                ));
+            }
+            _ => thread::sleep(PR_POLL_INTERVAL),
+        }
+    }
+}
+
+/// The branch to merge into, and the worktree and window where it is checked out
+struct MergeTarget {
+    branch: String,
+    worktree_path: PathBuf,
+    window_name: String,
+}
+
+/// Determine the target branch and where to merge it
+fn resolve_target(
+    branch_to_merge: &str,
+    into_branch: Option<&str>,
+    context: &WorkflowContext,
+) -> Result<MergeTarget> {
+    // Determine the target branch:
+    // 1. Use explicit --into if provided
+    // 2. Otherwise, check if branch has a stored base (from workmux add)
+    // 3. Fall back to main_branch
+    let detected_base: Option<String> = if into_branch.is_some() {
+        None // User explicitly specified target, no auto-detection needed
+    } else {
+        match git::get_branch_base(branch_to_merge) {
+            Ok(base) => {
+                // Verify the base branch still exists
+                if git::branch_exists(&base)? {
+                    info!(
+                        branch = %branch_to_merge,
+                        base = %base,
+                        "merge:auto-detected base branch"
+                    );
+                    Some(base)
+                } else {
+                    info!(
+                        branch = %branch_to_merge,
+                        base = %base,
+                        "merge:base branch not found, defaulting to main"
+                    );
+                    None
+                }
+            }
+            Err(_) => {
+                debug!(
+                    branch = %branch_to_merge,
+                    "merge:no base config found, defaulting to main"
+                );
+                None
+            }
+        }
+    };
+
+    let target_branch = into_branch
+        .map(|s| s.to_string())
+        .or(detected_base)
+        .unwrap_or_else(|| context.main_branch.clone());
+    let target_branch = target_branch.as_str();
+
+    // Resolve the worktree path and window handle for the TARGET branch.
+    // We prioritize finding an existing worktree for the target branch to support
+    // workflows where 'main' is checked out in a linked worktree (issue #29).
+    let (target_worktree_path, target_window_name) = match git::get_worktree_path(target_branch) {
+        Ok(path) => {
+            // Target is checked out in a worktree (could be main root or a linked worktree)
+            if path == context.main_worktree_root {
+                // It's in the main root. Use the main branch name as the window handle.
+                (path, context.main_branch.clone())
+            } else {
+                // It's in a linked worktree. Use the directory name as the handle.
+                let handle = path
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .ok_or_else(|| anyhow!("Invalid worktree path for target branch"))?
+                    .to_string();
+                (path, handle)
+            }
+        }
+        Err(_) => {
+            // Target branch is NOT checked out anywhere.
+            // We fallback to using the main worktree root to perform the merge.
+            debug!(
+                target = target_branch,
+                "merge:target branch has no worktree, using main worktree"
+            );
+            (
+                context.main_worktree_root.clone(),
+                context.main_branch.clone(),
+            )
+        }
+    };
+
+    Ok(MergeTarget {
+        branch: target_branch.to_string(),
+        worktree_path: target_worktree_path,
+        window_name: target_window_name,
+    })
+}
+
+/// The worktree being merged, as seen by merge hooks
+struct MergeSource<'a> {
+    handle: &'a str,
+    branch: &'a str,
+    worktree_path: &'a Path,
+}
+
+/// Run pre- or post-merge hooks in `workdir` with the merge's environment
+/// (WM_BRANCH_NAME, WM_TARGET_BRANCH, ports, ...)
+fn run_merge_hooks(
+    phase: &str,
+    hooks: &[HookEntry],
+    source: &MergeSource,
+    target_branch: &str,
+    workdir: &Path,
+    context: &WorkflowContext,
+) -> Result<()> {
+    let mut hook_env = super::hooks::worktree_env(
+        source.handle,
+        source.branch,
+        source.worktree_path,
+        &context.main_worktree_root,
+    );
+    hook_env.push(("WM_TARGET_BRANCH".to_string(), target_branch.to_string()));
+
+    super::hooks::run_hooks(
+        hooks,
+        &super::hooks::HookContext {
+            phase,
+            handle: source.handle,
+            workdir,
+            env: &super::hooks::env_refs(&hook_env),
+        },
+    )?;
+    Ok(())
+}
+
+/// Generate a commit message for the staged squash with the configured LLM.
+/// Returns None (falling back to an empty editor) if generation fails.
+fn generate_squash_message(worktree_path: &Path, context: &WorkflowContext) -> Option<String> {
+    let auto_name = context.config.auto_name.clone().unwrap_or_default();
+    let result = git::get_staged_diff(worktree_path).and_then(|diff| {
+        spinner::with_spinner("Generating commit message", || {
+            llm::generate_commit_message(&diff, &auto_name)
+        })
+    });
+    match result {
+        Ok(message) => Some(message),
+        Err(e) => {
+            warn!(error = %e, "merge:commit message generation failed");
+            eprintln!("Warning: Failed to generate commit message: {:#}", e);
+            None
+        }
+    }
+}
+
 /// Shows a system notification on macOS or Linux
 fn show_notification(message: &str) {
     #[cfg(target_os = "macos")]
@@ -366,3 +786,40 @@ fn show_notification(message: &str) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr_with_checks(checks: Option<CheckState>) -> PrSummary {
+        PrSummary {
+            checks,
+            ..PrSummary::recorded(12)
+        }
+    }
+
+    #[test]
+    fn checks_passed_waits_for_pending_and_rejects_failures() {
+        assert!(checks_passed(&pr_with_checks(None), "PR").unwrap());
+        assert!(checks_passed(&pr_with_checks(Some(CheckState::Success)), "PR").unwrap());
+        assert!(
+            !checks_passed(
+                &pr_with_checks(Some(CheckState::Pending {
+                    passed: 1,
+                    total: 3
+                })),
+                "PR"
+            )
+            .unwrap()
+        );
+        let err = checks_passed(
+            &pr_with_checks(Some(CheckState::Failure {
+                passed: 2,
+                total: 3,
+            })),
+            "MR",
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Checks failed on MR #12 (2/3 passed)");
+    }
+}
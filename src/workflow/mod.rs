@@ -1,10 +1,19 @@
 // Module declarations
+pub mod background_hooks;
 mod cleanup;
 mod context;
 mod create;
+pub mod du;
+pub mod feedback;
+mod file_copy;
+pub mod files_sync;
+pub mod gc;
+pub mod hooks;
+pub mod issue;
 mod list;
 mod merge;
 mod open;
+pub mod ports;
 pub mod pr;
 pub mod prompt_loader;
 mod remove;
@@ -14,10 +23,10 @@ pub mod types;
 // Public API re-exports
 pub use create::{create, create_with_changes};
 pub use list::list;
-pub use merge::merge;
+pub use merge::{merge, merge_via_pr};
 pub use open::open;
 pub use remove::remove;
-pub use setup::write_prompt_file;
+pub use setup::{prompt_file_path, write_prompt_file};
 
 // Re-export commonly used types for convenience
 pub use context::WorkflowContext;
@@ -100,6 +100,34 @@ pub fn open(
         None
     };
 
+    // Run on-open hooks before the window is created; a failing hook aborts the open.
+    // Unlike post_create they run on every open that creates a window, regardless
+    // of --run-hooks.
+    if let Some(on_open) = &context.config.on_open
+        && !on_open.is_empty()
+    {
+        info!(
+            handle = handle,
+            count = on_open.len(),
+            "open:running on-open hooks"
+        );
+        let hook_env = super::hooks::worktree_env(
+            &handle,
+            &branch_name,
+            &worktree_path,
+            &context.main_worktree_root,
+        );
+        super::hooks::run_hooks(
+            on_open,
+            &super::hooks::HookContext {
+                phase: "on_open",
+                handle: &handle,
+                workdir: working_dir.as_deref().unwrap_or(&worktree_path),
+                env: &super::hooks::env_refs(&hook_env),
+            },
+        )?;
+    }
+
     let options_with_workdir = SetupOptions {
         working_dir,
         config_root,
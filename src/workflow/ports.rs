@@ -0,0 +1,113 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::config::Config;
+use crate::git;
+use crate::state::StateStore;
+
+/// File written into each worktree with the reserved ports.
+pub const ENV_FILE: &str = ".env.workmux";
+
+/// Key under which a worktree's ports are stored. Resolves symlinks so `add` and
+/// `open` agree on the same entry.
+fn registry_key(worktree_path: &Path) -> PathBuf {
+    worktree_path
+        .canonicalize()
+        .unwrap_or_else(|_| worktree_path.to_path_buf())
+}
+
+/// Reserve the configured number of ports for a worktree and write `.env.workmux`.
+///
+/// Returns the reserved ports, or an empty list when `ports` is not configured.
+pub fn reserve(config: &Config, worktree_path: &Path) -> Result<Vec<u16>> {
+    let Some(ports_config) = config.ports.as_ref().filter(|p| p.count() > 0) else {
+        return Ok(Vec::new());
+    };
+
+    let store = StateStore::new()?;
+    let ports = store
+        .allocate_ports(
+            &registry_key(worktree_path),
+            ports_config.count(),
+            ports_config.range(),
+        )
+        .context("Failed to allocate ports")?;
+    debug!(path = %worktree_path.display(), ?ports, "ports:reserved");
+
+    write_env_file(worktree_path, &env_vars(&ports))?;
+    Ok(ports)
+}
+
+/// Environment variables for the ports already reserved for a worktree.
+pub fn lookup(worktree_path: &Path) -> Result<Vec<(String, String)>> {
+    let ports = StateStore::new()?.get_ports(&registry_key(worktree_path))?;
+    Ok(env_vars(&ports))
+}
+
+/// Release a worktree's ports. `worktree_path` must be the path used when
+/// reserving, so callers resolve it before the directory is removed.
+pub fn release(worktree_path: &Path) -> Result<()> {
+    StateStore::new()?.release_ports(&registry_key(worktree_path))
+}
+
+/// Build `WM_PORT` (the first port) plus `WM_PORT_1`, `WM_PORT_2`, ...
+pub fn env_vars(ports: &[u16]) -> Vec<(String, String)> {
+    let Some(first) = ports.first() else {
+        return Vec::new();
+    };
+    let mut vars = vec![("WM_PORT".to_string(), first.to_string())];
+    vars.extend(
+        ports
+            .iter()
+            .enumerate()
+            .map(|(i, port)| (format!("WM_PORT_{}", i + 1), port.to_string())),
+    );
+    vars
+}
+
+/// Write `.env.workmux` and keep it out of `git status` via `info/exclude`.
+fn write_env_file(worktree_path: &Path, vars: &[(String, String)]) -> Result<()> {
+    let content: String = vars.iter().map(|(k, v)| format!("{}={}\n", k, v)).collect();
+    fs::write(worktree_path.join(ENV_FILE), content)
+        .with_context(|| format!("Failed to write {}", ENV_FILE))?;
+
+    let exclude_path = git::get_git_common_dir()?.join("info").join("exclude");
+    let existing = fs::read_to_string(&exclude_path).unwrap_or_default();
+    if !existing.lines().any(|line| line.trim() == ENV_FILE) {
+        if let Some(parent) = exclude_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let separator = if existing.is_empty() || existing.ends_with('\n') {
+            ""
+        } else {
+            "\n"
+        };
+        fs::write(
+            &exclude_path,
+            format!("{}{}{}\n", existing, separator, ENV_FILE),
+        )
+        .context("Failed to update git info/exclude")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_vars_numbering() {
+        let vars = env_vars(&[4000, 4001]);
+        assert_eq!(
+            vars,
+            vec![
+                ("WM_PORT".to_string(), "4000".to_string()),
+                ("WM_PORT_1".to_string(), "4000".to_string()),
+                ("WM_PORT_2".to_string(), "4001".to_string()),
+            ]
+        );
+        assert!(env_vars(&[]).is_empty());
+    }
+}
@@ -3,7 +3,7 @@
 //! This module extracts domain logic for resolving pull requests and fork branches
 //! from the command layer, making it reusable and testable.
 
-use crate::{git, github, spinner};
+use crate::{forge, git, spinner};
 use anyhow::{Context, Result, anyhow};
 
 /// Abstraction for git operations used in remote detection
@@ -49,25 +49,27 @@ pub fn resolve_pr_ref(
     pr_number: u32,
     custom_branch_name: Option<&str>,
 ) -> Result<PrCheckoutResult> {
-    let pr_details = spinner::with_spinner(&format!("Fetching PR #{}", pr_number), || {
-        github::get_pr_details(pr_number)
+    let forge = forge::current();
+    let label = forge.request_label();
+    let pr_details = spinner::with_spinner(&format!("Fetching {} #{}", label, pr_number), || {
+        forge.get_pr_details(pr_number)
     })
-    .with_context(|| format!("Failed to fetch details for PR #{}", pr_number))?;
+    .with_context(|| format!("Failed to fetch details for {} #{}", label, pr_number))?;
 
     // Display PR information
-    println!("PR #{}: {}", pr_number, pr_details.title);
+    println!("{} #{}: {}", label, pr_number, pr_details.title);
     println!("Author: {}", pr_details.author.login);
     println!("Branch: {}", pr_details.head_ref_name);
 
     // Warn about PR state
     if pr_details.state != "OPEN" {
         eprintln!(
-            "⚠️  Warning: PR #{} is {}. Proceeding with checkout...",
-            pr_number, pr_details.state
+            "⚠️  Warning: {} #{} is {}. Proceeding with checkout...",
+            label, pr_number, pr_details.state
         );
     }
     if pr_details.is_draft {
-        eprintln!("⚠️  Warning: PR #{} is a DRAFT.", pr_number);
+        eprintln!("⚠️  Warning: {} #{} is a DRAFT.", label, pr_number);
     }
 
     // Determine local branch name (match gh pr checkout behavior)
@@ -76,15 +78,7 @@ pub fn resolve_pr_ref(
         .unwrap_or_else(|| pr_details.head_ref_name.clone());
 
     // Determine if this is a fork PR and ensure remote exists
-    let current_repo_owner =
-        git::get_repo_owner().context("Failed to determine repository owner from origin remote")?;
-
-    let remote_name = if pr_details.is_fork(&current_repo_owner) {
-        let fork_owner = &pr_details.head_repository_owner.login;
-        git::ensure_fork_remote(fork_owner)?
-    } else {
-        "origin".to_string()
-    };
+    let remote_name = forge.checkout_remote(&pr_details)?;
 
     // Note: We do not fetch here. The `create` workflow handles fetching
     // the remote branch to ensure the worktree base is up to date.
@@ -107,7 +101,8 @@ pub struct ForkBranchResult {
 /// Sets up the fork remote and optionally displays associated PR info.
 pub fn resolve_fork_branch(fork_spec: &git::ForkBranchSpec) -> Result<ForkBranchResult> {
     // Try to find an associated PR and display info (optional, non-blocking)
-    if let Ok(Some(pr)) = github::find_pr_by_head_ref(&fork_spec.owner, &fork_spec.branch) {
+    let forge = forge::current();
+    if let Ok(Some(pr)) = forge.find_pr_by_head_ref(&fork_spec.owner, &fork_spec.branch) {
         let state_suffix = match pr.state.as_str() {
             "OPEN" if pr.is_draft => " (draft)",
             "OPEN" => "",
@@ -115,7 +110,13 @@ pub fn resolve_fork_branch(fork_spec: &git::ForkBranchSpec) -> Result<ForkBranch
             "CLOSED" => " (closed)",
             _ => "",
         };
-        println!("PR #{}: {}{}", pr.number, pr.title, state_suffix);
+        println!(
+            "{} #{}: {}{}",
+            forge.request_label(),
+            pr.number,
+            pr.title,
+            state_suffix
+        );
     }
 
     // Ensure the fork remote exists
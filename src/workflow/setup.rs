@@ -3,12 +3,10 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::multiplexer::{CreateWindowParams, Multiplexer, PaneSetupOptions};
-use crate::{cmd, config, git, prompt::Prompt};
+use crate::{config, git, prompt::Prompt, template};
 use tracing::{debug, info};
 
-use fs_extra::dir as fs_dir;
-use fs_extra::file as fs_file;
-
+use super::file_copy;
 use super::types::CreateResult;
 
 /// Sets up the terminal window, files, and hooks for a worktree.
@@ -68,44 +66,82 @@ pub fn setup_environment(
             .context("Failed to auto-symlink CLAUDE.local.md")?;
     }
 
-    // Run post-create hooks before opening tmux so the new window appears "ready"
+    // Reserve ports before hooks run so they can be passed to templates, hooks and
+    // panes. Reopening a worktree gets back the same ports.
+    let reserved_ports = super::ports::reserve(config, worktree_path)?;
+    let port_env = super::ports::env_vars(&reserved_ports);
+
+    // Variables for file templates and `{{ ... }}` in pane commands
+    let template_context = serde_json::json!({
+        "handle": handle,
+        "branch": branch_name,
+        "worktree_path": worktree_path,
+        "project_root": repo_root,
+        "port": reserved_ports.first(),
+        "ports": reserved_ports,
+    });
+
+    if options.run_file_ops
+        && let Some(templates) = &config.files.template
+    {
+        render_file_templates(
+            file_ops_source,
+            effective_working_dir,
+            templates,
+            &template_context,
+        )
+        .context("Failed to render file templates")?;
+    }
+
+    // Resolve panes before running hooks so interpolation errors are reported up front
+    let panes = config.panes.as_deref().unwrap_or(&[]);
+    let mut resolved_panes = resolve_pane_configuration(panes, agent);
+    for (i, pane) in resolved_panes.iter_mut().enumerate() {
+        if let Some(command) = &pane.command {
+            let interpolated = template::interpolate(command, &template_context)
+                .with_context(|| format!("Failed to interpolate command of pane {}", i))?;
+            pane.command = Some(interpolated);
+        }
+    }
+
+    // Run post-create hooks before opening tmux so the new window appears "ready".
+    // Hooks marked `background: true` are started in a detached process instead,
+    // and pane commands wait for them.
     let mut hooks_run = 0;
+    let mut wait_command = None;
     if options.run_hooks
         && let Some(post_create) = &config.post_create
         && !post_create.is_empty()
     {
         hooks_run = post_create.len();
-        // Resolve absolute paths for environment variables.
-        // canonicalize() ensures symlinks are resolved and paths are absolute.
-        let abs_worktree_path = worktree_path
-            .canonicalize()
-            .unwrap_or_else(|_| worktree_path.to_path_buf());
-        let abs_project_root = repo_root
-            .canonicalize()
-            .unwrap_or_else(|_| repo_root.clone());
+        let (background, foreground): (Vec<_>, Vec<_>) = post_create
+            .iter()
+            .map(config::HookEntry::to_spec)
+            .partition(|spec| spec.background);
         let abs_config_dir = effective_working_dir
             .canonicalize()
             .unwrap_or_else(|_| effective_working_dir.to_path_buf());
-        let worktree_path_str = abs_worktree_path.to_string_lossy();
-        let project_root_str = abs_project_root.to_string_lossy();
-        let config_dir_str = abs_config_dir.to_string_lossy();
-        let hook_env = [
-            ("WORKMUX_HANDLE", handle),
-            ("WM_HANDLE", handle),
-            ("WM_WORKTREE_PATH", worktree_path_str.as_ref()),
-            ("WM_PROJECT_ROOT", project_root_str.as_ref()),
-            ("WM_CONFIG_DIR", config_dir_str.as_ref()),
-        ];
-        for (idx, command) in post_create.iter().enumerate() {
-            info!(branch = branch_name, step = idx + 1, total = hooks_run, command = %command, "setup_environment:hook start");
-            info!(command = %command, "Running post-create hook {}/{}", idx + 1, hooks_run);
-            cmd::shell_command_with_env(command, effective_working_dir, &hook_env)
-                .with_context(|| format!("Failed to run post-create command: '{}'", command))?;
-            info!(branch = branch_name, step = idx + 1, total = hooks_run, command = %command, "setup_environment:hook complete");
+        let mut hook_env =
+            super::hooks::worktree_env(handle, branch_name, worktree_path, &repo_root);
+        hook_env.push((
+            "WM_CONFIG_DIR".to_string(),
+            abs_config_dir.to_string_lossy().into_owned(),
+        ));
+        let hook_env = super::hooks::env_refs(&hook_env);
+        let hook_ctx = super::hooks::HookContext {
+            phase: "post_create",
+            handle,
+            workdir: effective_working_dir,
+            env: &hook_env,
+        };
+        super::hooks::run_specs(&foreground, &hook_ctx, |_, _| {})?;
+        if !background.is_empty() {
+            wait_command = Some(super::background_hooks::spawn(background, &hook_ctx)?);
         }
         info!(
             branch = branch_name,
             total = hooks_run,
+            background = wait_command.is_some(),
             "setup_environment:hooks complete"
         );
     }
@@ -134,10 +170,6 @@ pub fn setup_environment(
         "setup_environment:window created"
     );
 
-    // Setup panes
-    let panes = config.panes.as_deref().unwrap_or(&[]);
-    let resolved_panes = resolve_pane_configuration(panes, agent);
-
     // Validate that prompt will be consumed if one was provided
     if options.prompt_file_path.is_some() {
         validate_prompt_consumption(&resolved_panes, agent, config, options)?;
@@ -151,6 +183,8 @@ pub fn setup_environment(
             PaneSetupOptions {
                 run_commands: options.run_pane_commands,
                 prompt_file_path: options.prompt_file_path.as_deref(),
+                env: &port_env,
+                wait_command: wait_command.as_deref(),
             },
             config,
             agent,
@@ -243,8 +277,9 @@ pub fn handle_file_operations(
     let mut symlink_count = 0;
 
     // Handle copies
-    if let Some(copy_patterns) = &file_config.copy {
-        for pattern in copy_patterns {
+    if let Some(copy_entries) = &file_config.copy {
+        for copy_entry in copy_entries {
+            let pattern = copy_entry.pattern();
             let full_pattern = repo_root.join(pattern).to_string_lossy().to_string();
             for entry in glob::glob(&full_pattern)? {
                 let source_path = entry?;
@@ -270,35 +305,16 @@ pub fn handle_file_operations(
                 })?;
                 let dest_path = worktree_path.join(relative_path);
 
-                if source_path.is_dir() {
-                    // Create destination parent directory
-                    if let Some(parent) = dest_path.parent() {
-                        fs::create_dir_all(parent)?;
-                    }
-                    // Use fs_extra::dir::copy which handles recursion and symlinks correctly
-                    let mut dir_options = fs_dir::CopyOptions::new();
-                    dir_options.overwrite = true;
-                    dir_options.content_only = true;
-                    fs::create_dir_all(&dest_path)?; // Ensure dest exists
-                    fs_dir::copy(&source_path, &dest_path, &dir_options).with_context(|| {
-                        format!(
-                            "Failed to copy directory {:?} to {:?}",
-                            source_path, dest_path
-                        )
-                    })?;
-                } else {
-                    // Copy single file
-                    if let Some(parent) = dest_path.parent() {
-                        fs::create_dir_all(parent).with_context(|| {
-                            format!("Failed to create parent directory for {:?}", dest_path)
-                        })?;
-                    }
-                    let mut options = fs_file::CopyOptions::new();
-                    options.overwrite = true;
-                    fs_file::copy(&source_path, &dest_path, &options).with_context(|| {
-                        format!("Failed to copy file {:?} to {:?}", source_path, dest_path)
+                let stats = file_copy::copy_path(&source_path, &dest_path, copy_entry.mode())
+                    .with_context(|| {
+                        format!("Failed to copy {:?} to {:?}", source_path, dest_path)
                     })?;
-                }
+                debug!(
+                    path = %relative_path.display(),
+                    mode = %copy_entry.mode(),
+                    ?stats,
+                    "file_operations:copied"
+                );
                 copy_count += 1;
             }
         }
@@ -396,6 +412,88 @@ pub fn handle_file_operations(
     Ok(())
 }
 
+/// Render `files.template` entries from the repo root into the worktree.
+///
+/// Sources must stay within the repository root and destinations within the
+/// worktree. Every variable used by a template must be present in `context`.
+pub fn render_file_templates(
+    repo_root: &Path,
+    worktree_path: &Path,
+    templates: &[config::TemplateFile],
+    context: &serde_json::Value,
+) -> Result<()> {
+    let canon_repo_root = repo_root.canonicalize().with_context(|| {
+        format!(
+            "Failed to canonicalize repository root path: {:?}",
+            repo_root
+        )
+    })?;
+    let env = template::create_template_env();
+
+    for entry in templates {
+        let source_path = repo_root.join(&entry.source);
+        let canon_source_path = source_path.canonicalize().with_context(|| {
+            format!("Failed to canonicalize template source: {:?}", source_path)
+        })?;
+        if !canon_source_path.starts_with(&canon_repo_root) {
+            return Err(anyhow!(
+                "Path traversal detected for template '{}'. The resolved path '{}' is outside the repository root.",
+                entry.source,
+                source_path.display()
+            ));
+        }
+
+        let dest_rel = Path::new(entry.dest());
+        if dest_rel.is_absolute()
+            || dest_rel
+                .components()
+                .any(|c| matches!(c, std::path::Component::ParentDir))
+        {
+            return Err(anyhow!(
+                "Template destination '{}' must be a relative path inside the worktree",
+                entry.dest()
+            ));
+        }
+        let dest_path = worktree_path.join(dest_rel);
+
+        let source = fs::read_to_string(&canon_source_path)
+            .with_context(|| format!("Failed to read template {:?}", source_path))?;
+        template::validate_template_variables(&env, &source, context)
+            .with_context(|| format!("Invalid template '{}'", entry.source))?;
+        let rendered = env
+            .render_str(&source, context)
+            .with_context(|| format!("Failed to render template '{}'", entry.source))?;
+
+        if let Some(parent) = dest_path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create parent directory for {:?}", dest_path)
+            })?;
+        }
+        // Replace symlinks instead of writing through them
+        if dest_path
+            .symlink_metadata()
+            .is_ok_and(|m| m.file_type().is_symlink())
+        {
+            fs::remove_file(&dest_path)?;
+        }
+        fs::write(&dest_path, rendered)
+            .with_context(|| format!("Failed to write {:?}", dest_path))?;
+        debug!(source = %entry.source, dest = %dest_path.display(), "file_operations:template rendered");
+    }
+
+    Ok(())
+}
+
+/// Where the prompt a branch's agent was started with is stored.
+///
+/// Written to the temp directory instead of the worktree to avoid polluting git status.
+pub fn prompt_file_path(branch_name: &str) -> PathBuf {
+    // Sanitize branch name: replace path separators with dashes to avoid
+    // interpreting slashes as directory separators (e.g., "feature/foo" -> "feature-foo")
+    let safe_branch_name = branch_name.replace(['/', '\\'], "-");
+    std::env::temp_dir().join(format!("workmux-prompt-{}.md", safe_branch_name))
+}
+
 pub fn write_prompt_file(branch_name: &str, prompt: &Prompt) -> Result<PathBuf> {
     let content = match prompt {
         Prompt::Inline(text) => text.clone(),
@@ -403,18 +501,95 @@ pub fn write_prompt_file(branch_name: &str, prompt: &Prompt) -> Result<PathBuf>
             .with_context(|| format!("Failed to read prompt file '{}'", path.display()))?,
     };
 
-    // Sanitize branch name: replace path separators with dashes to avoid
-    // interpreting slashes as directory separators (e.g., "feature/foo" -> "feature-foo")
-    let safe_branch_name = branch_name.replace(['/', '\\'], "-");
-
-    // Write to temp directory instead of the worktree to avoid polluting git status
-    let prompt_filename = format!("workmux-prompt-{}.md", safe_branch_name);
-    let prompt_path = std::env::temp_dir().join(prompt_filename);
+    let prompt_path = prompt_file_path(branch_name);
     fs::write(&prompt_path, content)
         .with_context(|| format!("Failed to write prompt file '{}'", prompt_path.display()))?;
     Ok(prompt_path)
 }
 
+/// Symlink CLAUDE.local.md from main worktree if it exists and is gitignored.
+fn symlink_claude_local_md(repo_root: &Path, worktree_path: &Path) -> Result<()> {
+    let source = repo_root.join("CLAUDE.local.md");
+    if !source.exists() {
+        return Ok(());
+    }
+
+    if !git::is_path_ignored(repo_root, "CLAUDE.local.md") {
+        return Ok(());
+    }
+
+    let dest = worktree_path.join("CLAUDE.local.md");
+    if dest.symlink_metadata().is_ok() {
+        // Already exists (file, symlink, or dir) -- skip
+        return Ok(());
+    }
+
+    let relative_source = pathdiff::diff_paths(&source, worktree_path)
+        .ok_or_else(|| anyhow!("Could not create relative path for CLAUDE.local.md symlink"))?;
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&relative_source, &dest)
+        .context("Failed to symlink CLAUDE.local.md")?;
+
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_file(&relative_source, &dest)
+        .context("Failed to symlink CLAUDE.local.md")?;
+
+    info!("Symlinked CLAUDE.local.md to worktree");
+    Ok(())
+}
+
+/// Validates that a prompt will actually be consumed by an agent pane.
+///
+/// This prevents the case where a user provides `-p "some prompt"` but no pane
+/// is configured to run an agent that would receive it.
+fn validate_prompt_consumption(
+    panes: &[config::PaneConfig],
+    cli_agent: Option<&str>,
+    config: &config::Config,
+    options: &super::types::SetupOptions,
+) -> Result<()> {
+    if !options.run_pane_commands {
+        return Err(anyhow!(
+            "Prompt provided (-p/-P/-e) but pane commands are disabled (--no-pane-cmds). \
+             The prompt would be ignored."
+        ));
+    }
+
+    let effective_agent = cli_agent.or(config.agent.as_deref());
+
+    let Some(agent_cmd) = effective_agent else {
+        return Err(anyhow!(
+            "Prompt provided but no agent is configured to consume it. \
+             Set 'agent' in config or use -a/--agent flag."
+        ));
+    };
+
+    let consumes_prompt = panes.iter().any(|pane| {
+        pane.command
+            .as_deref()
+            .map(|cmd| config::is_agent_command(cmd, agent_cmd))
+            .unwrap_or(false)
+    });
+
+    if !consumes_prompt {
+        let commands: Vec<_> = panes
+            .iter()
+            .map(|p| p.command.as_deref().unwrap_or("<shell>"))
+            .collect();
+
+        return Err(anyhow!(
+            "Prompt provided, but no pane is configured to run the agent '{}'.\n\
+             Resolved pane commands: {:?}\n\
+             Ensure your panes config includes '<agent>' or runs the configured agent.",
+            agent_cmd,
+            commands
+        ));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -710,87 +885,68 @@ mod tests {
         // Cleanup
         let _ = std::fs::remove_file(path);
     }
-}
 
-/// Symlink CLAUDE.local.md from main worktree if it exists and is gitignored.
-fn symlink_claude_local_md(repo_root: &Path, worktree_path: &Path) -> Result<()> {
-    let source = repo_root.join("CLAUDE.local.md");
-    if !source.exists() {
-        return Ok(());
-    }
+    #[test]
+    fn render_file_templates_writes_rendered_output() {
+        let repo = tempfile::tempdir().unwrap();
+        let worktree = tempfile::tempdir().unwrap();
+        fs::write(
+            repo.path().join(".env.template"),
+            "DB=app_{{ handle }}\nPORT={{ port }}\nPORTS={{ ports | join(',') }}\n",
+        )
+        .unwrap();
+        let templates = vec![config::TemplateFile {
+            source: ".env.template".to_string(),
+            dest: Some("config/.env".to_string()),
+        }];
+        let context = serde_json::json!({
+            "handle": "feature",
+            "port": 4000,
+            "ports": [4000, 4001],
+        });
 
-    if !git::is_path_ignored(repo_root, "CLAUDE.local.md") {
-        return Ok(());
-    }
+        render_file_templates(repo.path(), worktree.path(), &templates, &context).unwrap();
 
-    let dest = worktree_path.join("CLAUDE.local.md");
-    if dest.symlink_metadata().is_ok() {
-        // Already exists (file, symlink, or dir) -- skip
-        return Ok(());
+        let rendered = fs::read_to_string(worktree.path().join("config/.env")).unwrap();
+        assert_eq!(rendered, "DB=app_feature\nPORT=4000\nPORTS=4000,4001\n");
     }
 
-    let relative_source = pathdiff::diff_paths(&source, worktree_path)
-        .ok_or_else(|| anyhow!("Could not create relative path for CLAUDE.local.md symlink"))?;
-
-    #[cfg(unix)]
-    std::os::unix::fs::symlink(&relative_source, &dest)
-        .context("Failed to symlink CLAUDE.local.md")?;
-
-    #[cfg(windows)]
-    std::os::windows::fs::symlink_file(&relative_source, &dest)
-        .context("Failed to symlink CLAUDE.local.md")?;
-
-    info!("Symlinked CLAUDE.local.md to worktree");
-    Ok(())
-}
+    #[test]
+    fn render_file_templates_rejects_escaping_dest() {
+        let repo = tempfile::tempdir().unwrap();
+        let worktree = tempfile::tempdir().unwrap();
+        fs::write(repo.path().join("a.tmpl"), "x").unwrap();
+        let templates = vec![config::TemplateFile {
+            source: "a.tmpl".to_string(),
+            dest: Some("../escape".to_string()),
+        }];
 
-/// Validates that a prompt will actually be consumed by an agent pane.
-///
-/// This prevents the case where a user provides `-p "some prompt"` but no pane
-/// is configured to run an agent that would receive it.
-fn validate_prompt_consumption(
-    panes: &[config::PaneConfig],
-    cli_agent: Option<&str>,
-    config: &config::Config,
-    options: &super::types::SetupOptions,
-) -> Result<()> {
-    if !options.run_pane_commands {
-        return Err(anyhow!(
-            "Prompt provided (-p/-P/-e) but pane commands are disabled (--no-pane-cmds). \
-             The prompt would be ignored."
-        ));
+        let result = render_file_templates(
+            repo.path(),
+            worktree.path(),
+            &templates,
+            &serde_json::json!({}),
+        );
+        assert!(result.is_err());
     }
 
-    let effective_agent = cli_agent.or(config.agent.as_deref());
-
-    let Some(agent_cmd) = effective_agent else {
-        return Err(anyhow!(
-            "Prompt provided but no agent is configured to consume it. \
-             Set 'agent' in config or use -a/--agent flag."
-        ));
-    };
-
-    let consumes_prompt = panes.iter().any(|pane| {
-        pane.command
-            .as_deref()
-            .map(|cmd| config::is_agent_command(cmd, agent_cmd))
-            .unwrap_or(false)
-    });
-
-    if !consumes_prompt {
-        let commands: Vec<_> = panes
-            .iter()
-            .map(|p| p.command.as_deref().unwrap_or("<shell>"))
-            .collect();
+    #[test]
+    fn render_file_templates_reports_undefined_variables() {
+        let repo = tempfile::tempdir().unwrap();
+        let worktree = tempfile::tempdir().unwrap();
+        fs::write(repo.path().join("a.tmpl"), "{{ nope }}").unwrap();
+        let templates = vec![config::TemplateFile {
+            source: "a.tmpl".to_string(),
+            dest: None,
+        }];
 
-        return Err(anyhow!(
-            "Prompt provided, but no pane is configured to run the agent '{}'.\n\
-             Resolved pane commands: {:?}\n\
-             Ensure your panes config includes '<agent>' or runs the configured agent.",
-            agent_cmd,
-            commands
-        ));
+        let err = render_file_templates(
+            repo.path(),
+            worktree.path(),
+            &templates,
+            &serde_json::json!({ "handle": "x" }),
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("nope"));
     }
-
-    Ok(())
 }
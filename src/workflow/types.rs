@@ -1,5 +1,7 @@
 use std::path::PathBuf;
+use std::time::Duration;
 
+use crate::forge::MergeMethod;
 use crate::github::PrSummary;
 use crate::prompt::Prompt;
 
@@ -31,6 +33,17 @@ pub struct MergeResult {
     pub had_staged_changes: bool,
 }
 
+/// How `merge_via_pr` merges a branch's PR
+pub struct PrMergeOptions {
+    pub method: MergeMethod,
+    /// Wait for the PR's checks to pass before merging
+    pub wait: bool,
+    /// Enable the forge's auto-merge instead of merging right away
+    pub auto: bool,
+    /// How long to wait for checks and the merge before giving up
+    pub timeout: Duration,
+}
+
 /// Result of removing a worktree
 pub struct RemoveResult {
     pub branch_removed: String,
@@ -45,6 +58,8 @@ pub struct DeferredCleanup {
     pub keep_branch: bool,
     pub force: bool,
     pub git_common_dir: PathBuf,
+    /// Shell command running the post_remove hooks once the worktree is gone
+    pub post_remove_command: Option<String>,
 }
 
 /// Result of cleanup operations
@@ -0,0 +1,83 @@
+from pathlib import Path
+
+from .conftest import (
+    MuxEnvironment,
+    create_commit,
+    get_window_name,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def _add_merged_worktree(
+    env: MuxEnvironment, workmux_exe_path: Path, repo_path: Path, branch_name: str
+) -> Path:
+    """Creates a worktree with one commit, merges it into main, and closes its window."""
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, f"feat: {branch_name}")
+    env.run_command(["git", "merge", "--no-ff", "--no-edit", branch_name], cwd=repo_path)
+    env.kill_window(get_window_name(branch_name))
+    return worktree_path
+
+
+def test_gc_dry_run_lists_without_removing(
+    mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
+):
+    """Verifies `workmux gc --dry-run` reports matches but leaves them in place."""
+    env = mux_server
+    write_workmux_config(mux_repo_path)
+    worktree_path = _add_merged_worktree(
+        env, workmux_exe_path, mux_repo_path, "gc-dry-run"
+    )
+
+    result = run_workmux_command(
+        env, workmux_exe_path, mux_repo_path, "gc --merged --dry-run"
+    )
+
+    assert "gc-dry-run" in result.stdout
+    assert "merged" in result.stdout
+    assert worktree_path.is_dir()
+
+
+def test_gc_merged_removes_only_merged(
+    mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
+):
+    """Verifies `workmux gc --merged` removes merged worktrees and keeps unmerged ones."""
+    env = mux_server
+    write_workmux_config(mux_repo_path)
+    merged_path = _add_merged_worktree(env, workmux_exe_path, mux_repo_path, "gc-merged")
+
+    run_workmux_add(env, workmux_exe_path, mux_repo_path, "gc-unmerged")
+    unmerged_path = get_worktree_path(mux_repo_path, "gc-unmerged")
+    create_commit(env, unmerged_path, "feat: unmerged work")
+    env.kill_window(get_window_name("gc-unmerged"))
+
+    run_workmux_command(env, workmux_exe_path, mux_repo_path, "gc --merged -f")
+
+    assert not merged_path.exists()
+    assert unmerged_path.is_dir()
+    branches = env.run_command(["git", "branch", "--list", "gc-merged"]).stdout
+    assert "gc-merged" not in branches
+
+
+def test_gc_skips_open_window(
+    mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
+):
+    """Verifies `workmux gc` leaves worktrees alone while their window is open."""
+    env = mux_server
+    write_workmux_config(mux_repo_path)
+    branch_name = "gc-open"
+    run_workmux_add(env, workmux_exe_path, mux_repo_path, branch_name)
+    worktree_path = get_worktree_path(mux_repo_path, branch_name)
+    create_commit(env, worktree_path, "feat: open window")
+    env.run_command(
+        ["git", "merge", "--no-ff", "--no-edit", branch_name], cwd=mux_repo_path
+    )
+
+    result = run_workmux_command(env, workmux_exe_path, mux_repo_path, "gc --merged -f")
+
+    assert "skip: window open" in result.stdout
+    assert worktree_path.is_dir()